@@ -2,148 +2,1005 @@
 // Date: 2026-02-17
 // Purpose: Audio playback for MP3 player. Uses rodio on a dedicated thread
 // so that OutputStream (not Send+Sync on Windows) is never stored in Tauri state.
-// Decode result is sent back so the frontend can show "Playback failed: ...".
+// Decode result is emitted as an audio-play-result event so the frontend can show
+// "Playback failed: ..." without play_audio blocking on slow (e.g. network) files.
+// The thread also polls the playback position to implement the optional A-B loop region,
+// and emits audio-levels (peak/RMS) events a few times per second while playing.
+// Pause and stop save the track and position so resume_last_track can continue after a restart.
+// The output device can be chosen by name; a missing device falls back to the system default.
+// When a track plays to the end, an audio-finished event carries its path so the frontend
+// can advance to the next item.
+// Queued tracks are appended to the sink one ahead of time so they play back-to-back; each
+// queued track that starts is announced with audio-queue-advanced.
+// Volume is kept on the thread and re-applied to every new track and output.
+// The outcome of the last output open is shared with AudioState, so commands fail right away
+// when there is no output device and the rest of the app keeps working without audio.
 
+use super::audio_levels::{AudioLevels, LevelMeter};
+use super::formats::{audio_decoder, AudioDecoder};
+use super::persistence::{
+    PersistenceState, AUDIO_DEVICE_KEY, LAST_SELECTED_TRACK_KEY, LAST_TRACK_POSITION_KEY,
+    PLAYBACK_SPEED_KEY, PLAYBACK_VOLUME_KEY,
+};
+use super::play_history::record_play;
+use rodio::cpal::traits::{DeviceTrait, HostTrait};
 use rodio::{Decoder, OutputStream, Sink, Source};
+use serde::Serialize;
 use std::fs::File;
 use std::io::BufReader;
-use std::sync::mpsc;
-use std::time::Duration;
-use tauri::State;
+use std::sync::mpsc::RecvTimeoutError;
+use std::sync::{mpsc, Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager, State};
+
+/// Allowed playback speed range (1.0 = normal).
+pub const MIN_PLAYBACK_SPEED: f32 = 0.5;
+pub const MAX_PLAYBACK_SPEED: f32 = 3.0;
+
+/// How often the audio thread checks the position against the loop region end.
+const POSITION_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// How often audio-levels events are emitted while playing (~5 per second).
+const LEVELS_EMIT_INTERVAL: Duration = Duration::from_millis(200);
 
 pub enum AudioCommand {
+    /// Decode and play path from start_at (or the beginning); the outcome is emitted as
+    /// audio-play-result through app.
     Play {
         path: String,
-        result_tx: Option<mpsc::Sender<Result<(), String>>>,
+        app: AppHandle,
+        start_at: Option<Duration>,
+    },
+    /// Replace the queue with paths and play the first one.
+    SetQueue {
+        paths: Vec<String>,
+        app: AppHandle,
+    },
+    /// Append paths to the queue; they start right away when nothing is loaded.
+    Enqueue {
+        paths: Vec<String>,
+        app: AppHandle,
+    },
+    /// Jump to the next queued track.
+    SkipNext {
+        app: AppHandle,
+        result_tx: mpsc::Sender<Result<(), String>>,
     },
     Stop,
     Pause,
+    SetSpeed(f32),
+    /// Linear volume, 0.0 (silent) to 1.0 (full).
+    SetVolume(f32),
+    /// A-B loop (start, end) in seconds for the current track, or None to clear.
+    SetLoop {
+        region: Option<(f64, f64)>,
+        result_tx: Option<mpsc::Sender<Result<(), String>>>,
+    },
+    /// Reports what the sink is doing now, for get_playback_status.
+    Status {
+        result_tx: mpsc::Sender<PlaybackStatus>,
+    },
+    /// Rebuild the output on the named device (None = system default), continuing the
+    /// current track at the same position.
+    SetDevice {
+        name: Option<String>,
+        app: AppHandle,
+        result_tx: Option<mpsc::Sender<Result<(), String>>>,
+    },
+}
+
+/// Payload of the audio-play-result event, emitted once per play_audio call.
+#[derive(Debug, Clone, Serialize)]
+pub struct AudioPlayResult {
+    pub path: String,
+    pub ok: bool,
+    pub error: Option<String>,
+}
+
+/// Payload of audio-finished, emitted once when the sink runs dry after playing path.
+/// Not emitted for stop_audio or when a new track replaces the current one.
+#[derive(Debug, Clone, Serialize)]
+pub struct AudioFinished {
+    pub path: String,
+}
+
+/// Payload of audio-queue-advanced, emitted whenever a track of the queue starts playing.
+#[derive(Debug, Clone, Serialize)]
+pub struct QueueAdvanced {
+    pub index: usize,
+    pub path: String,
+}
+
+/// Payload of audio-device-warning, emitted when the chosen device is missing and the
+/// system default is used instead.
+#[derive(Debug, Clone, Serialize)]
+pub struct AudioDeviceWarning {
+    pub requested: String,
+    pub message: String,
+}
+
+/// Result of get_playback_status. All zeros with playing false when nothing is loaded;
+/// duration_seconds is 0 when the decoder does not know the track length.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct PlaybackStatus {
+    pub playing: bool,
+    pub paused: bool,
+    pub position_seconds: f64,
+    pub duration_seconds: f64,
+    /// Index of the current track in the queue (0 for a single play_audio track).
+    pub queue_index: Option<usize>,
+    pub queue_length: usize,
+}
+
+/// Result of get_audio_device_status, updated by the audio thread each time it opens the
+/// output. `error` is the last open failure (None once an output opened).
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct AudioDeviceStatus {
+    pub initialized: bool,
+    pub device_name: Option<String>,
+    pub error: Option<String>,
 }
 
-/// Only the channel sender is stored; the audio thread owns the stream and sink.
+/// Result of resume_last_track: the track that was reloaded and where playback resumed.
+#[derive(Debug, Serialize)]
+pub struct ResumedTrack {
+    pub path: String,
+    pub position_secs: f64,
+}
+
+/// Only the channel sender is stored; the audio thread owns the stream and sink (see run_audio_thread).
+/// `speed` and `volume` mirror the last values sent to the thread so they can be reported
+/// without a round-trip; `device` is written by the thread.
 pub struct AudioState {
     tx: mpsc::Sender<AudioCommand>,
+    speed: Mutex<f32>,
+    volume: Mutex<f32>,
+    device: Arc<Mutex<AudioDeviceStatus>>,
 }
 
-fn try_play(path: &str, sink: &Sink) -> Result<(), String> {
+/// Opens path with the decoder formats.rs assigns to its extension.
+/// Shared by try_play and get_audio_waveform so both accept the same files.
+pub fn open_decoder(path: &str) -> Result<Decoder<BufReader<File>>, String> {
     let path_buf = std::path::PathBuf::from(path);
     let ext = path_buf
         .extension()
         .and_then(|e| e.to_str())
         .unwrap_or("")
         .to_lowercase();
-    match ext.as_str() {
-        "mp3" => {
-            let file = File::open(&path_buf).map_err(|e| {
-                if e.kind() == std::io::ErrorKind::NotFound {
-                    "File not found.".to_string()
-                } else {
-                    e.to_string()
-                }
-            })?;
-            let dec = Decoder::new_mp3(BufReader::new(file)).map_err(|e| format!("MP3: {}", e))?;
-            sink.append(dec.convert_samples::<f32>());
+    let kind = audio_decoder(&ext);
+    if kind == AudioDecoder::Unsupported {
+        return Err("M4A/AAC not supported. Use MP3, WAV, FLAC, or OGG.".to_string());
+    }
+    let file = File::open(&path_buf).map_err(|e| {
+        if e.kind() == std::io::ErrorKind::NotFound {
+            "File not found.".to_string()
+        } else {
+            e.to_string()
         }
-        "wav" => {
-            let file = File::open(&path_buf).map_err(|e| e.to_string())?;
-            let dec = Decoder::new_wav(BufReader::new(file)).map_err(|e| format!("WAV: {}", e))?;
-            sink.append(dec.convert_samples::<f32>());
+    })?;
+    let reader = BufReader::new(file);
+    let dec = match kind {
+        AudioDecoder::Mp3 => Decoder::new_mp3(reader).map_err(|e| format!("MP3: {}", e))?,
+        AudioDecoder::Wav => Decoder::new_wav(reader).map_err(|e| format!("WAV: {}", e))?,
+        AudioDecoder::Flac => Decoder::new_flac(reader).map_err(|e| format!("FLAC: {}", e))?,
+        AudioDecoder::Vorbis => {
+            Decoder::new_vorbis(reader).map_err(|e| format!("Vorbis: {}", e))?
         }
-        "flac" => {
-            let file = File::open(&path_buf).map_err(|e| e.to_string())?;
-            let dec = Decoder::new_flac(BufReader::new(file)).map_err(|e| format!("FLAC: {}", e))?;
-            sink.append(dec.convert_samples::<f32>());
+        AudioDecoder::Generic | AudioDecoder::Unsupported => {
+            Decoder::new(reader).map_err(|e| format!("Decode: {}", e))?
         }
-        "ogg" => {
-            let file = File::open(&path_buf).map_err(|e| e.to_string())?;
-            let dec = Decoder::new_vorbis(BufReader::new(file)).map_err(|e| format!("Vorbis: {}", e))?;
-            sink.append(dec.convert_samples::<f32>());
+    };
+    Ok(dec)
+}
+
+/// Appends the decoded file to the sink and returns its duration, if the decoder knows it.
+fn try_play(
+    path: &str,
+    sink: &Sink,
+    levels: &Arc<Mutex<AudioLevels>>,
+) -> Result<Option<Duration>, String> {
+    let dec = open_decoder(path)?;
+    let duration = dec.total_duration();
+    sink.append(LevelMeter::new(
+        dec.convert_samples::<f32>(),
+        Arc::clone(levels),
+    ));
+    Ok(duration)
+}
+
+/// Checks a loop region against the current track; None means the region is accepted.
+fn loop_region_error(sink: &Sink, duration: Option<Duration>, end_secs: f64) -> Option<String> {
+    if sink.empty() {
+        return Some("Nothing is playing.".to_string());
+    }
+    match duration {
+        Some(d) if end_secs > d.as_secs_f64() => Some(format!(
+            "Loop end is past the end of the track ({:.1}s).",
+            d.as_secs_f64()
+        )),
+        _ => None,
+    }
+}
+
+/// Error reported through audio-play-result when no output device can be opened.
+const NO_OUTPUT_DEVICE: &str = "No audio output device available";
+
+/// The open output stream and its sink. The stream must stay alive for the sink to play.
+struct AudioOutput {
+    _stream: OutputStream,
+    sink: Sink,
+    /// Name of the default device when opened; a different default means it was replaced or lost.
+    device_name: Option<String>,
+}
+
+/// The output device named name, or the system default when name is None or no longer
+/// present. The flag is true when a named device was requested but not found.
+pub fn find_output_device(name: Option<&str>) -> Option<(rodio::cpal::Device, bool)> {
+    let host = rodio::cpal::default_host();
+    if let Some(name) = name {
+        let found = host
+            .output_devices()
+            .ok()
+            .and_then(|mut devices| devices.find(|d| d.name().ok().as_deref() == Some(name)));
+        if let Some(device) = found {
+            return Some((device, false));
         }
-        _ => {
-            if ext.as_str() == "m4a" || ext.as_str() == "aac" {
-                return Err("M4A/AAC not supported. Use MP3, WAV, FLAC, or OGG.".to_string());
-            }
-            let file = File::open(&path_buf).map_err(|e| e.to_string())?;
-            let dec = Decoder::new(BufReader::new(file)).map_err(|e| format!("Decode: {}", e))?;
-            sink.append(dec.convert_samples::<f32>());
+        return host.default_output_device().map(|d| (d, true));
+    }
+    host.default_output_device().map(|d| (d, false))
+}
+
+/// Name of the device open_output would use now, or None if there is no output device.
+fn target_output_device_name(preferred: Option<&str>) -> Option<String> {
+    let (device, _) = find_output_device(preferred)?;
+    Some(device.name().unwrap_or_default())
+}
+
+/// Opens the preferred output device (or the default) and a sink on it with the given speed
+/// applied. When the preferred device is missing, an audio-device-warning is emitted through
+/// app and the default device is used.
+fn open_output(
+    preferred: Option<&str>,
+    speed: f32,
+    app: Option<&AppHandle>,
+) -> Result<AudioOutput, String> {
+    let (device, fell_back) =
+        find_output_device(preferred).ok_or_else(|| format!("{}.", NO_OUTPUT_DEVICE))?;
+    if let (true, Some(requested)) = (fell_back, preferred) {
+        let message = format!(
+            "Audio device \"{}\" is not available; using the default device.",
+            requested
+        );
+        eprintln!("Audio thread: {}", message);
+        if let Some(app) = app {
+            let warning = AudioDeviceWarning {
+                requested: requested.to_string(),
+                message,
+            };
+            let _ = app.emit("audio-device-warning", warning);
         }
     }
-    Ok(())
+    let device_name = device.name().ok();
+    let (stream, handle) = OutputStream::try_from_device(&device)
+        .map_err(|e| format!("{}: {}", NO_OUTPUT_DEVICE, e))?;
+    let sink = Sink::try_new(&handle).map_err(|e| format!("{}: {}", NO_OUTPUT_DEVICE, e))?;
+    sink.set_speed(speed);
+    Ok(AudioOutput {
+        _stream: stream,
+        sink,
+        device_name,
+    })
 }
 
-impl AudioState {
-    pub fn new() -> Result<Self, String> {
-        let (tx, rx) = mpsc::channel();
-        std::thread::spawn(move || {
-            let (_stream, stream_handle) = match OutputStream::try_default() {
-                Ok(x) => x,
-                Err(e) => {
-                    eprintln!("Audio thread: OutputStream failed: {}", e);
-                    return;
+/// Records the outcome of opening the output for get_audio_device_status.
+fn record_output(device: &Mutex<AudioDeviceStatus>, result: &Result<AudioOutput, String>) {
+    if let Ok(mut status) = device.lock() {
+        *status = match result {
+            Ok(out) => AudioDeviceStatus {
+                initialized: true,
+                device_name: out.device_name.clone(),
+                error: None,
+            },
+            Err(e) => AudioDeviceStatus {
+                initialized: false,
+                device_name: None,
+                error: Some(e.clone()),
+            },
+        };
+    }
+}
+
+/// Persists the track and position so resume_last_track can pick up from there.
+fn save_position(app: &AppHandle, path: &str, pos: Duration) {
+    if let Some(persistence) = app.try_state::<PersistenceState>() {
+        let _ = persistence.set(LAST_SELECTED_TRACK_KEY, path);
+        let secs = format!("{:.3}", pos.as_secs_f64());
+        let _ = persistence.set(LAST_TRACK_POSITION_KEY, &secs);
+    }
+}
+
+/// Body of the audio thread. The output is opened lazily and reopened on the next Play when it
+/// failed before or the target device changed (e.g. USB DAC unplugged, or the chosen device
+/// came back), so a missing device is reported per play instead of disabling audio for the session.
+fn run_audio_thread(rx: mpsc::Receiver<AudioCommand>, device: Arc<Mutex<AudioDeviceStatus>>) {
+    let opened = open_output(None, 1.0, None);
+    record_output(&device, &opened);
+    let mut output: Option<AudioOutput> = opened.map_err(|e| eprintln!("Audio thread: {}", e)).ok();
+    // Device chosen with set_audio_device; None follows the system default.
+    let mut preferred_device: Option<String> = None;
+    // Applied to every (re)opened sink, so speed survives a device change.
+    let mut speed: f32 = 1.0;
+    // Re-applied after every Play and device change; clearing the sink does not keep it.
+    let mut volume: f32 = 1.0;
+    // Duration of the current track (if known) and the active A-B loop in seconds.
+    let mut duration: Option<Duration> = None;
+    let mut loop_region: Option<(f64, f64)> = None;
+    // Levels are written by the playing source; the handle comes from the last Play.
+    let levels = Arc::new(Mutex::new(AudioLevels::default()));
+    let mut emitter: Option<AppHandle> = None;
+    // Path of the track loaded in the sink, for saving the position on pause/stop.
+    let mut current_path: Option<String> = None;
+    // Tracks to play in order; queue_index is the one in current_path. play_audio makes a queue
+    // of one.
+    let mut queue: Vec<String> = Vec::new();
+    let mut queue_index: usize = 0;
+    // Some(duration) once the next queued track has been appended behind the current one.
+    let mut preloaded: Option<Option<Duration>> = None;
+    let mut last_levels_emit = Instant::now();
+    loop {
+        let cmd = match rx.recv_timeout(POSITION_POLL_INTERVAL) {
+            Ok(cmd) => Some(cmd),
+            Err(RecvTimeoutError::Timeout) => None,
+            Err(RecvTimeoutError::Disconnected) => break,
+        };
+        // Track to load after the command is handled: (path, app, start_at).
+        let mut start: Option<(String, AppHandle, Option<Duration>)> = None;
+        match cmd {
+            None => {}
+            Some(AudioCommand::Play {
+                path,
+                app,
+                start_at,
+            }) => {
+                queue = vec![path.clone()];
+                queue_index = 0;
+                start = Some((path, app, start_at));
+            }
+            Some(AudioCommand::SetQueue { paths, app }) => {
+                queue = paths;
+                queue_index = 0;
+                start = queue.first().map(|p| (p.clone(), app, None));
+            }
+            Some(AudioCommand::Enqueue { paths, app }) => {
+                let idle = current_path.is_none();
+                if idle {
+                    // Nothing loaded: the first new path starts playing right away.
+                    queue_index = queue.len();
                 }
-            };
-            let sink = match Sink::try_new(&stream_handle) {
-                Ok(s) => s,
-                Err(e) => {
-                    eprintln!("Audio thread: Sink failed: {}", e);
-                    return;
+                queue.extend(paths);
+                if idle {
+                    start = queue.get(queue_index).map(|p| (p.clone(), app, None));
                 }
-            };
-            while let Ok(cmd) = rx.recv() {
-                match cmd {
-                    AudioCommand::Play { path, result_tx } => {
-                        sink.stop();
-                        sink.clear();
-                        let result = try_play(&path, &sink);
-                        if let Some(tx) = result_tx {
-                            let _ = tx.send(result);
+            }
+            Some(AudioCommand::SkipNext { app, result_tx }) => {
+                let result = match queue.get(queue_index + 1) {
+                    Some(next) => {
+                        queue_index += 1;
+                        start = Some((next.clone(), app, None));
+                        Ok(())
+                    }
+                    None => Err("No next track in the queue.".to_string()),
+                };
+                let _ = result_tx.send(result);
+            }
+            Some(AudioCommand::Stop) => {
+                if let Some(out) = &output {
+                    if let (Some(app), Some(path)) = (&emitter, current_path.take()) {
+                        if !out.sink.empty() {
+                            save_position(app, &path, out.sink.get_pos());
+                        }
+                    }
+                    out.sink.stop();
+                    out.sink.clear();
+                }
+                loop_region = None;
+                queue.clear();
+                preloaded = None;
+            }
+            Some(AudioCommand::Pause) => {
+                if let Some(out) = &output {
+                    if out.sink.is_paused() {
+                        out.sink.play();
+                    } else {
+                        out.sink.pause();
+                        if let (Some(app), Some(path)) = (&emitter, &current_path) {
+                            save_position(app, path, out.sink.get_pos());
                         }
                     }
-                    AudioCommand::Stop => {
-                        sink.stop();
-                        sink.clear();
+                }
+            }
+            Some(AudioCommand::SetSpeed(new_speed)) => {
+                // Kept on the sink, so it also applies to the next track.
+                speed = new_speed;
+                if let Some(out) = &output {
+                    out.sink.set_speed(speed);
+                }
+            }
+            Some(AudioCommand::SetVolume(new_volume)) => {
+                volume = new_volume;
+                if let Some(out) = &output {
+                    out.sink.set_volume(volume);
+                }
+            }
+            Some(AudioCommand::SetLoop { region, result_tx }) => {
+                let error = match (&output, region) {
+                    (_, None) => None,
+                    (Some(out), Some((_, end))) => loop_region_error(&out.sink, duration, end),
+                    (None, Some(_)) => Some("Nothing is playing.".to_string()),
+                };
+                let result = match error {
+                    Some(e) => Err(e),
+                    None => {
+                        loop_region = region;
+                        Ok(())
                     }
-                    AudioCommand::Pause => {
-                        if sink.is_paused() {
-                            sink.play();
-                        } else {
-                            sink.pause();
+                };
+                if let Some(tx) = result_tx {
+                    let _ = tx.send(result);
+                }
+            }
+            Some(AudioCommand::Status { result_tx }) => {
+                let status = match &output {
+                    Some(out) if !out.sink.empty() => PlaybackStatus {
+                        playing: !out.sink.is_paused(),
+                        paused: out.sink.is_paused(),
+                        position_seconds: out.sink.get_pos().as_secs_f64(),
+                        duration_seconds: duration.map(|d| d.as_secs_f64()).unwrap_or(0.0),
+                        queue_index: current_path.as_ref().map(|_| queue_index),
+                        queue_length: queue.len(),
+                    },
+                    _ => PlaybackStatus {
+                        queue_length: queue.len(),
+                        ..PlaybackStatus::default()
+                    },
+                };
+                let _ = result_tx.send(status);
+            }
+            Some(AudioCommand::SetDevice {
+                name,
+                app,
+                result_tx,
+            }) => {
+                preferred_device = name;
+                // What was playing, so it continues on the new device: (path, pos, paused).
+                let resume = match (&output, &current_path) {
+                    (Some(out), Some(path)) if !out.sink.empty() => {
+                        Some((path.clone(), out.sink.get_pos(), out.sink.is_paused()))
+                    }
+                    _ => None,
+                };
+                drop(output.take());
+                // The preloaded next track went with the old sink; it is appended again below.
+                preloaded = None;
+                let result = open_output(preferred_device.as_deref(), speed, Some(&app));
+                record_output(&device, &result);
+                let result = result.map(|out| {
+                    out.sink.set_volume(volume);
+                    if let Some((path, pos, paused)) = resume {
+                        // clear() leaves the sink paused, so the seek lands before playback starts.
+                        out.sink.clear();
+                        match try_play(&path, &out.sink, &levels) {
+                            Ok(d) => {
+                                duration = d;
+                                let _ = out.sink.try_seek(pos);
+                                if !paused {
+                                    out.sink.play();
+                                }
+                            }
+                            Err(_) => current_path = None,
                         }
                     }
+                    output = Some(out);
+                });
+                if let Some(tx) = result_tx {
+                    let _ = tx.send(result);
+                }
+                emitter = Some(app);
+            }
+        }
+        if let Some((path, app, start_at)) = start {
+            loop_region = None;
+            preloaded = None;
+            let stale = match &output {
+                Some(out) => {
+                    target_output_device_name(preferred_device.as_deref()) != out.device_name
+                }
+                None => true,
+            };
+            if stale {
+                // Drop the old stream before opening a new one on the same device.
+                drop(output.take());
+                let opened = open_output(preferred_device.as_deref(), speed, Some(&app));
+                record_output(&device, &opened);
+                output = opened.map_err(|e| eprintln!("Audio thread: {}", e)).ok();
+            }
+            let result = match &output {
+                Some(out) => {
+                    out.sink.stop();
+                    // clear() also pauses the sink; resume once the new track is appended.
+                    out.sink.clear();
+                    out.sink.set_volume(volume);
+                    try_play(&path, &out.sink, &levels).map(|d| {
+                        duration = d;
+                        // Seeking while paused just queues the position for the new source.
+                        if let Some(pos) = start_at {
+                            let _ = out.sink.try_seek(pos);
+                        }
+                        out.sink.play();
+                    })
+                }
+                None => Err(format!("{}.", NO_OUTPUT_DEVICE)),
+            };
+            current_path = result.is_ok().then(|| path.clone());
+            if result.is_ok() {
+                // A newly started track must not inherit the previous track's position.
+                save_position(&app, &path, start_at.unwrap_or_default());
+                if let Some(persistence) = app.try_state::<PersistenceState>() {
+                    let _ = record_play(&persistence, &path);
                 }
+                let advanced = QueueAdvanced {
+                    index: queue_index,
+                    path: path.clone(),
+                };
+                let _ = app.emit("audio-queue-advanced", advanced);
             }
-        });
-        Ok(AudioState { tx })
+            let payload = AudioPlayResult {
+                path,
+                ok: result.is_ok(),
+                error: result.err(),
+            };
+            let _ = app.emit("audio-play-result", payload);
+            emitter = Some(app);
+        }
+        let sink = match &output {
+            Some(out) => &out.sink,
+            None => continue,
+        };
+        if let (Some(next_duration), Some(app)) = (preloaded, &emitter) {
+            // The current track ended and the preloaded one is now playing.
+            if sink.len() <= 1 {
+                preloaded = None;
+                loop_region = None;
+                if let Some(path) = current_path.take() {
+                    let _ = app.emit("audio-finished", AudioFinished { path });
+                }
+                queue_index += 1;
+                duration = next_duration;
+                if let Some(path) = queue.get(queue_index).cloned() {
+                    save_position(app, &path, Duration::ZERO);
+                    if let Some(persistence) = app.try_state::<PersistenceState>() {
+                        let _ = record_play(&persistence, &path);
+                    }
+                    let advanced = QueueAdvanced {
+                        index: queue_index,
+                        path: path.clone(),
+                    };
+                    let _ = app.emit("audio-queue-advanced", advanced);
+                    current_path = Some(path);
+                }
+            }
+        }
+        if preloaded.is_none() && current_path.is_some() && !sink.empty() {
+            if let (Some(next), Some(app)) = (queue.get(queue_index + 1).cloned(), &emitter) {
+                // Appended while the current track plays, so there is no gap between them.
+                match try_play(&next, sink, &levels) {
+                    Ok(d) => preloaded = Some(d),
+                    Err(e) => {
+                        queue.remove(queue_index + 1);
+                        let payload = AudioPlayResult {
+                            path: next,
+                            ok: false,
+                            error: Some(e),
+                        };
+                        let _ = app.emit("audio-play-result", payload);
+                    }
+                }
+            }
+        }
+        if sink.empty() {
+            // current_path is only set while a track is loaded, so this fires once per track.
+            if let (Some(app), Some(path)) = (&emitter, current_path.take()) {
+                let _ = app.emit("audio-finished", AudioFinished { path });
+            }
+        }
+        if let Some((start, end)) = loop_region {
+            if !sink.empty() && sink.get_pos().as_secs_f64() >= end {
+                let _ = sink.try_seek(Duration::from_secs_f64(start));
+            }
+        }
+        if sink.empty() || sink.is_paused() {
+            continue;
+        }
+        if let Some(app) = &emitter {
+            if last_levels_emit.elapsed() >= LEVELS_EMIT_INTERVAL {
+                last_levels_emit = Instant::now();
+                let current = levels.lock().map(|l| *l).unwrap_or_default();
+                let _ = app.emit("audio-levels", current);
+            }
+        }
+    }
+}
+
+impl AudioState {
+    /// Starts the audio thread. Never fails: if the thread cannot be started, the error is kept
+    /// in the device status and every audio command returns it.
+    pub fn new() -> Self {
+        let (tx, rx) = mpsc::channel();
+        let device = Arc::new(Mutex::new(AudioDeviceStatus::default()));
+        let thread_device = Arc::clone(&device);
+        let spawned = std::thread::Builder::new()
+            .name("audio".to_string())
+            .spawn(move || run_audio_thread(rx, thread_device));
+        if let Err(e) = spawned {
+            eprintln!("Audio init failed: {}", e);
+            if let Ok(mut status) = device.lock() {
+                status.error = Some(format!("Audio could not be started: {}", e));
+            }
+        }
+        AudioState {
+            tx,
+            speed: Mutex::new(1.0),
+            volume: Mutex::new(1.0),
+            device,
+        }
+    }
+
+    /// Sends cmd to the audio thread. When the thread is gone, the stored init error is returned
+    /// instead of the channel error.
+    fn send(&self, cmd: AudioCommand) -> Result<(), String> {
+        self.tx.send(cmd).map_err(|_| {
+            self.device_status()
+                .error
+                .unwrap_or_else(|| "Audio is not available.".to_string())
+        })
+    }
+
+    fn device_status(&self) -> AudioDeviceStatus {
+        self.device.lock().map(|s| s.clone()).unwrap_or_default()
+    }
+
+    /// Fails immediately when no output is open and no device is present now, instead of
+    /// leaving the caller to wait for an audio-play-result error. A device plugged in since
+    /// the last failure passes, so the thread can open it on the next Play.
+    fn ensure_output(&self) -> Result<(), String> {
+        let status = self.device_status();
+        if status.initialized || find_output_device(None).is_some() {
+            return Ok(());
+        }
+        Err(status
+            .error
+            .unwrap_or_else(|| "No audio output device found.".to_string()))
+    }
+
+    /// Clamps speed to MIN..=MAX_PLAYBACK_SPEED, sends it to the audio thread, and returns the applied value.
+    /// Used by set_playback_speed and at startup to restore the persisted speed.
+    pub fn apply_speed(&self, speed: f32) -> Result<f32, String> {
+        if !speed.is_finite() {
+            return Err("Invalid playback speed.".to_string());
+        }
+        let speed = speed.clamp(MIN_PLAYBACK_SPEED, MAX_PLAYBACK_SPEED);
+        self.send(AudioCommand::SetSpeed(speed))?;
+        *self.speed.lock().map_err(|e| e.to_string())? = speed;
+        Ok(speed)
+    }
+
+    /// Clamps level to 0.0..=1.0, sends it to the audio thread, and returns the applied value.
+    /// Used by set_volume and at startup to restore the persisted volume.
+    pub fn apply_volume(&self, level: f32) -> Result<f32, String> {
+        if !level.is_finite() {
+            return Err("Invalid volume.".to_string());
+        }
+        let level = level.clamp(0.0, 1.0);
+        self.send(AudioCommand::SetVolume(level))?;
+        *self.volume.lock().map_err(|e| e.to_string())? = level;
+        Ok(level)
+    }
+
+    /// Switches output to the named device (None = system default) without waiting.
+    /// Used at startup to restore the persisted device; a missing one falls back to the
+    /// default with an audio-device-warning event.
+    pub fn restore_device(&self, app: AppHandle, name: Option<String>) -> Result<(), String> {
+        self.send(AudioCommand::SetDevice {
+            name,
+            app,
+            result_tx: None,
+        })
+    }
+}
+
+/// Starts playing the audio file at the given path and returns immediately.
+/// Decode success or failure arrives later as an audio-play-result event; only a missing
+/// file is reported synchronously.
+#[tauri::command]
+pub fn play_audio(app: AppHandle, path: String, state: State<AudioState>) -> Result<(), String> {
+    if !std::path::Path::new(&path).is_file() {
+        return Err("File not found.".to_string());
+    }
+    state.ensure_output()?;
+    state.send(AudioCommand::Play {
+        path,
+        app,
+        start_at: None,
+    })
+}
+
+/// Reloads the track saved on the last pause/stop and resumes at the saved position.
+/// Errors if nothing was saved or the file has since been moved or deleted.
+#[tauri::command]
+pub fn resume_last_track(
+    app: AppHandle,
+    state: State<AudioState>,
+    persistence: State<PersistenceState>,
+) -> Result<ResumedTrack, String> {
+    let path = persistence
+        .get(LAST_SELECTED_TRACK_KEY)?
+        .filter(|p| !p.is_empty())
+        .ok_or_else(|| "No track to resume.".to_string())?;
+    if !std::path::Path::new(&path).is_file() {
+        return Err(format!("The last track was moved or deleted: {}", path));
+    }
+    state.ensure_output()?;
+    let position_secs = persistence
+        .get(LAST_TRACK_POSITION_KEY)?
+        .and_then(|v| v.parse::<f64>().ok())
+        .filter(|s| s.is_finite() && *s >= 0.0)
+        .unwrap_or(0.0);
+    state.send(AudioCommand::Play {
+        path: path.clone(),
+        app,
+        start_at: Some(Duration::from_secs_f64(position_secs)),
+    })?;
+    Ok(ResumedTrack {
+        path,
+        position_secs,
+    })
+}
+
+/// Checks that every queued path is a file before it is handed to the audio thread.
+fn check_queue_paths(paths: &[String]) -> Result<(), String> {
+    match paths.iter().find(|p| !std::path::Path::new(p).is_file()) {
+        Some(missing) => Err(format!("File not found: {}", missing)),
+        None => Ok(()),
+    }
+}
+
+/// Replaces whatever is playing with paths, played back-to-back from the first one.
+/// Each track that starts is announced with an audio-queue-advanced event.
+#[tauri::command]
+pub fn set_queue(
+    app: AppHandle,
+    paths: Vec<String>,
+    state: State<AudioState>,
+) -> Result<(), String> {
+    if paths.is_empty() {
+        return Err("The queue is empty.".to_string());
     }
+    check_queue_paths(&paths)?;
+    state.ensure_output()?;
+    state.send(AudioCommand::SetQueue { paths, app })
 }
 
-/// Plays the audio file at the given path. Returns when decode succeeds or fails so the UI can show errors.
+/// Appends paths to the end of the queue without interrupting the current track.
+/// When nothing is playing, the first of them starts right away.
 #[tauri::command]
-pub fn play_audio(path: String, state: State<AudioState>) -> Result<(), String> {
+pub fn enqueue(app: AppHandle, paths: Vec<String>, state: State<AudioState>) -> Result<(), String> {
+    check_queue_paths(&paths)?;
+    state.ensure_output()?;
+    state.send(AudioCommand::Enqueue { paths, app })
+}
+
+/// Starts the next track of the queue now. Errors when the current track is the last one.
+#[tauri::command]
+pub async fn skip_next(app: AppHandle, state: State<'_, AudioState>) -> Result<(), String> {
     let (result_tx, result_rx) = mpsc::channel();
-    state
-        .tx
-        .send(AudioCommand::Play {
-            path,
-            result_tx: Some(result_tx),
-        })
-        .map_err(|e| e.to_string())?;
-    result_rx
-        .recv_timeout(Duration::from_secs(10))
-        .map_err(|_| "Playback start timed out.".to_string())?
+    state.send(AudioCommand::SkipNext { app, result_tx })?;
+    tauri::async_runtime::spawn_blocking(move || {
+        result_rx
+            .recv_timeout(Duration::from_secs(2))
+            .map_err(|_| "Audio thread did not respond.".to_string())?
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Whether the audio output is open and on which device, or the error that prevented it.
+#[tauri::command]
+pub fn get_audio_device_status(state: State<AudioState>) -> AudioDeviceStatus {
+    state.device_status()
 }
 
-/// Stops current audio playback.
+/// Stops current audio playback and clears the queue.
 #[tauri::command]
 pub fn stop_audio(state: State<AudioState>) -> Result<(), String> {
-    state.tx.send(AudioCommand::Stop).map_err(|e| e.to_string())
+    state.send(AudioCommand::Stop)
 }
 
 /// Pauses or resumes playback.
 #[tauri::command]
 pub fn pause_audio(state: State<AudioState>) -> Result<(), String> {
-    state
-        .tx
-        .send(AudioCommand::Pause)
-        .map_err(|e| e.to_string())
+    state.send(AudioCommand::Pause)
+}
+
+/// Sets the playback speed (clamped to 0.5–3.0) for the current and following tracks and persists it.
+/// rodio changes speed by resampling, so pitch shifts with speed; that is acceptable for podcasts/audiobooks.
+/// Returns the speed actually applied.
+#[tauri::command]
+pub fn set_playback_speed(
+    speed: f32,
+    state: State<AudioState>,
+    persistence: State<PersistenceState>,
+) -> Result<f32, String> {
+    let applied = state.apply_speed(speed)?;
+    persistence.set(PLAYBACK_SPEED_KEY, &applied.to_string())?;
+    Ok(applied)
+}
+
+/// Returns the current playback speed (1.0 = normal).
+#[tauri::command]
+pub fn get_playback_speed(state: State<AudioState>) -> Result<f32, String> {
+    state.speed.lock().map(|s| *s).map_err(|e| e.to_string())
+}
+
+/// Sets the volume (clamped to 0.0–1.0) for the current and following tracks and persists it.
+/// Returns the volume actually applied.
+#[tauri::command]
+pub fn set_volume(
+    level: f32,
+    state: State<AudioState>,
+    persistence: State<PersistenceState>,
+) -> Result<f32, String> {
+    let applied = state.apply_volume(level)?;
+    persistence.set(PLAYBACK_VOLUME_KEY, &applied.to_string())?;
+    Ok(applied)
+}
+
+/// Returns the current volume (0.0–1.0).
+#[tauri::command]
+pub fn get_volume(state: State<AudioState>) -> Result<f32, String> {
+    state.volume.lock().map(|v| *v).map_err(|e| e.to_string())
+}
+
+/// Returns whether a track is playing or paused, the position within it and its duration,
+/// in seconds. Polled by the frontend to drive the seek bar.
+#[tauri::command]
+pub async fn get_playback_status(state: State<'_, AudioState>) -> Result<PlaybackStatus, String> {
+    let (result_tx, result_rx) = mpsc::channel();
+    state.send(AudioCommand::Status { result_tx })?;
+    tauri::async_runtime::spawn_blocking(move || {
+        result_rx
+            .recv_timeout(Duration::from_secs(2))
+            .map_err(|_| "Audio thread did not respond.".to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Loops playback between start_secs and end_secs of the current track until cleared or the
+/// track changes. Requires 0 <= start < end, with end within the track duration when known.
+#[tauri::command]
+pub async fn set_loop_region(
+    start_secs: f64,
+    end_secs: f64,
+    state: State<'_, AudioState>,
+) -> Result<(), String> {
+    if !start_secs.is_finite() || !end_secs.is_finite() || start_secs < 0.0 {
+        return Err("Invalid loop region.".to_string());
+    }
+    if start_secs >= end_secs {
+        return Err("Loop start must be before loop end.".to_string());
+    }
+    let (result_tx, result_rx) = mpsc::channel();
+    state.send(AudioCommand::SetLoop {
+        region: Some((start_secs, end_secs)),
+        result_tx: Some(result_tx),
+    })?;
+    tauri::async_runtime::spawn_blocking(move || {
+        result_rx
+            .recv_timeout(Duration::from_secs(2))
+            .map_err(|_| "Audio thread did not respond.".to_string())?
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Removes the A-B loop; playback continues normally to the end of the track.
+#[tauri::command]
+pub fn clear_loop_region(state: State<AudioState>) -> Result<(), String> {
+    state.send(AudioCommand::SetLoop {
+        region: None,
+        result_tx: None,
+    })
+}
+
+/// Names of the available audio output devices, as accepted by set_audio_device.
+#[tauri::command]
+pub async fn list_audio_devices() -> Result<Vec<String>, String> {
+    tauri::async_runtime::spawn_blocking(|| {
+        let devices = rodio::cpal::default_host()
+            .output_devices()
+            .map_err(|e| e.to_string())?;
+        let mut names: Vec<String> = devices.filter_map(|d| d.name().ok()).collect();
+        names.dedup();
+        Ok(names)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Plays through the named output device from now on and persists the choice; None or an
+/// empty name goes back to the system default. The current track continues at the same
+/// position, speed and volume. Errors if the device is not in list_audio_devices.
+#[tauri::command]
+pub async fn set_audio_device(
+    app: AppHandle,
+    name: Option<String>,
+    state: State<'_, AudioState>,
+    persistence: State<'_, PersistenceState>,
+) -> Result<(), String> {
+    let name = name.filter(|n| !n.trim().is_empty());
+    if let Some(requested) = name.clone() {
+        let available = list_audio_devices().await?;
+        if !available.contains(&requested) {
+            return Err(format!("Audio device not found: {}", requested));
+        }
+    }
+    switch_device(app, name.clone(), &state).await?;
+    persistence.set(AUDIO_DEVICE_KEY, name.as_deref().unwrap_or(""))
+}
+
+/// Has the audio thread reopen the output on name (None: system default) and waits for it.
+async fn switch_device(
+    app: AppHandle,
+    name: Option<String>,
+    state: &AudioState,
+) -> Result<(), String> {
+    let (result_tx, result_rx) = mpsc::channel();
+    state.send(AudioCommand::SetDevice {
+        name,
+        app,
+        result_tx: Some(result_tx),
+    })?;
+    tauri::async_runtime::spawn_blocking(move || {
+        result_rx
+            .recv_timeout(Duration::from_secs(5))
+            .map_err(|_| "Audio thread did not respond.".to_string())?
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Drops and reopens the output stream, e.g. after headphones were plugged in or the default
+/// device changed. With a device_name it behaves like set_audio_device; without one it reopens
+/// the saved device, or the current system default when none is saved or the saved one is not
+/// connected. The current track continues where it was.
+#[tauri::command]
+pub async fn reinit_audio_device(
+    app: AppHandle,
+    device_name: Option<String>,
+    state: State<'_, AudioState>,
+    persistence: State<'_, PersistenceState>,
+) -> Result<(), String> {
+    if let Some(name) = device_name.filter(|n| !n.trim().is_empty()) {
+        return set_audio_device(app, Some(name), state, persistence).await;
+    }
+    // A saved device that is unplugged falls back to the system default; the choice stays
+    // saved so a later reinit picks it up again once it is back.
+    let saved = persistence
+        .get(AUDIO_DEVICE_KEY)?
+        .filter(|n| !n.trim().is_empty());
+    let name = match saved {
+        Some(saved) if list_audio_devices().await?.contains(&saved) => Some(saved),
+        _ => None,
+    };
+    switch_device(app, name, &state).await
 }
@@ -0,0 +1,148 @@
+// Author: Viorel LUPU
+// Date: 2026-10-14
+// Purpose: Static waveform overview for the player's seek bar. Decodes the track with the same
+// decoder dispatch as playback and reduces it to per-bucket peaks. Long tracks are sampled
+// (seek + short window per bucket) instead of decoded end to end, and results are cached
+// per path+mtime so reloading a track is instant.
+
+use super::audio::open_decoder;
+use super::fs::normalize_path;
+use rodio::Source;
+use std::collections::VecDeque;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+
+/// Bucket count limits; the seek bar is never wider than a few thousand pixels.
+const MIN_BUCKETS: usize = 1;
+const MAX_BUCKETS: usize = 4096;
+
+/// Tracks up to this length are decoded in full; longer ones are sampled per bucket.
+const FULL_DECODE_LIMIT: Duration = Duration::from_secs(10 * 60);
+
+/// Audio read after each seek when sampling a long track.
+const SAMPLE_WINDOW: Duration = Duration::from_millis(100);
+
+/// Number of waveforms kept in memory (oldest dropped first).
+const CACHE_CAPACITY: usize = 32;
+
+/// Cache key: canonical path, modification time, and bucket count.
+type WaveformKey = (PathBuf, SystemTime, usize);
+
+static WAVEFORM_CACHE: Mutex<VecDeque<(WaveformKey, Vec<f32>)>> = Mutex::new(VecDeque::new());
+
+/// Scales peaks so the loudest bucket is 1.0; silent tracks stay all zeros.
+fn normalize_peaks(mut peaks: Vec<f32>) -> Vec<f32> {
+    let max = peaks.iter().copied().fold(0.0f32, f32::max);
+    if max > 0.0 {
+        for p in peaks.iter_mut() {
+            *p = (*p / max).clamp(0.0, 1.0);
+        }
+    }
+    peaks
+}
+
+/// Decodes every sample. Peaks are first collected per fixed chunk (the total length may be
+/// unknown up front), then merged into buckets.
+fn full_decode_peaks(path: &str, buckets: usize) -> Result<Vec<f32>, String> {
+    const CHUNK: usize = 1024;
+    let source = open_decoder(path)?.convert_samples::<f32>();
+    let mut chunks: Vec<f32> = Vec::new();
+    let mut peak = 0.0f32;
+    let mut count = 0usize;
+    for sample in source {
+        peak = peak.max(sample.abs());
+        count += 1;
+        if count == CHUNK {
+            chunks.push(peak);
+            peak = 0.0;
+            count = 0;
+        }
+    }
+    if count > 0 {
+        chunks.push(peak);
+    }
+    if chunks.is_empty() {
+        return Ok(vec![0.0; buckets]);
+    }
+    let peaks = (0..buckets)
+        .map(|i| {
+            let start = i * chunks.len() / buckets;
+            let end = ((i + 1) * chunks.len() / buckets).max(start + 1);
+            chunks[start..end.min(chunks.len())]
+                .iter()
+                .copied()
+                .fold(0.0f32, f32::max)
+        })
+        .collect();
+    Ok(peaks)
+}
+
+/// Seeks to the start of each bucket and takes the peak of a short window there.
+/// Returns None if the decoder can't seek, so the caller can fall back to a full decode.
+fn sampled_peaks(
+    path: &str,
+    buckets: usize,
+    duration: Duration,
+) -> Result<Option<Vec<f32>>, String> {
+    let mut source = open_decoder(path)?.convert_samples::<f32>();
+    let window =
+        (source.sample_rate() as f64 * source.channels() as f64 * SAMPLE_WINDOW.as_secs_f64())
+            .max(1.0) as usize;
+    let mut peaks = Vec::with_capacity(buckets);
+    for i in 0..buckets {
+        let pos = duration.mul_f64(i as f64 / buckets as f64);
+        if source.try_seek(pos).is_err() {
+            return Ok(None);
+        }
+        let peak = source
+            .by_ref()
+            .take(window)
+            .fold(0.0f32, |acc, s| acc.max(s.abs()));
+        peaks.push(peak);
+    }
+    Ok(Some(peaks))
+}
+
+/// Peaks for path, sampled when the track is long and the decoder supports seeking.
+fn compute_waveform(path: &str, buckets: usize) -> Result<Vec<f32>, String> {
+    let duration = open_decoder(path)?.total_duration();
+    if let Some(d) = duration.filter(|d| *d > FULL_DECODE_LIMIT) {
+        if let Some(peaks) = sampled_peaks(path, buckets, d)? {
+            return Ok(normalize_peaks(peaks));
+        }
+    }
+    Ok(normalize_peaks(full_decode_peaks(path, buckets)?))
+}
+
+/// Returns buckets peak amplitudes (0.0..=1.0, loudest bucket = 1.0) covering the whole track.
+/// Results are cached by path and modification time, so an edited file is recomputed.
+#[tauri::command]
+pub async fn get_audio_waveform(path: String, buckets: usize) -> Result<Vec<f32>, String> {
+    let buckets = buckets.clamp(MIN_BUCKETS, MAX_BUCKETS);
+    let path = normalize_path(&path)?;
+    let mtime = std::fs::metadata(&path)
+        .and_then(|m| m.modified())
+        .map_err(|e| e.to_string())?;
+    let key: WaveformKey = (path, mtime, buckets);
+    if let Some((_, peaks)) = WAVEFORM_CACHE
+        .lock()
+        .map_err(|e| e.to_string())?
+        .iter()
+        .find(|(k, _)| *k == key)
+    {
+        return Ok(peaks.clone());
+    }
+    let path_str = key.0.to_string_lossy().into_owned();
+    let peaks = tauri::async_runtime::spawn_blocking(move || compute_waveform(&path_str, buckets))
+        .await
+        .map_err(|e| e.to_string())??;
+    let mut cache = WAVEFORM_CACHE.lock().map_err(|e| e.to_string())?;
+    // Entries for an older version of the same file can never hit again.
+    cache.retain(|(k, _)| k.0 != key.0 || k.1 == key.1);
+    if cache.len() >= CACHE_CAPACITY {
+        cache.pop_front();
+    }
+    cache.push_back((key, peaks.clone()));
+    Ok(peaks)
+}
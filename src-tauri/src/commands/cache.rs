@@ -0,0 +1,212 @@
+// Author: Viorel LUPU
+// Date: 2026-10-14
+// Purpose: One place for on-disk caches (image and video thumbnails). The cache lives in
+// Tauri's app cache dir unless the user relocates it with set_cache_dir; clear_all_caches
+// empties every cache subfolder. Only the subfolders listed here are ever deleted, so pointing
+// the cache at an existing folder can't remove the user's own files.
+
+use super::fs::normalize_path;
+use super::persistence::{PersistenceState, CACHE_DIR_KEY};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
+use tauri::{AppHandle, Manager};
+
+/// Subfolder for cached video thumbnails (see get_video_thumbnail_data_url).
+pub const VIDEO_THUMBNAIL_CACHE: &str = "video-thumbnails";
+
+/// Cache subfolders (image and video thumbnails); clear_all_caches empties exactly these.
+const CACHE_SUBDIRS: [&str; 2] = ["image-thumbnails", VIDEO_THUMBNAIL_CACHE];
+
+/// Size cap per cache subfolder; the least recently used files are removed beyond it.
+const MAX_CACHE_SUBDIR_BYTES: u64 = 256 * 1024 * 1024;
+
+/// Estimated size of each cache subfolder written this session, so write_cached only scans a
+/// folder when the estimate goes over the cap. Replaced files are counted twice; the scan that
+/// this triggers early corrects the estimate.
+static CACHE_SIZES: Mutex<Vec<(PathBuf, u64)>> = Mutex::new(Vec::new());
+
+/// Resolved cache root: the persisted override if set, else Tauri's app cache dir, else a
+/// cache folder in the app data dir.
+pub fn cache_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    if let Ok(Some(custom)) = app.state::<PersistenceState>().get(CACHE_DIR_KEY) {
+        if !custom.trim().is_empty() {
+            return Ok(PathBuf::from(custom));
+        }
+    }
+    app.path()
+        .app_cache_dir()
+        .or_else(|_| app.path().app_data_dir().map(|d| d.join("cache")))
+        .map_err(|e| e.to_string())
+}
+
+/// The cache subfolder name inside the cache root, created if needed.
+pub fn cache_subdir(app: &AppHandle, name: &str) -> Result<PathBuf, String> {
+    let dir = cache_dir(app)?.join(name);
+    std::fs::create_dir_all(&dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+    Ok(dir)
+}
+
+/// 64-bit FNV-1a over parts, for cache file names. Unlike DefaultHasher the value is the same
+/// across runs and Rust versions, so cached files stay valid after an update.
+pub fn stable_hash(parts: &[&[u8]]) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for part in parts {
+        for &byte in *part {
+            hash ^= byte as u64;
+            hash = hash.wrapping_mul(0x0100_0000_01b3);
+        }
+        // Separator so ("ab", "c") and ("a", "bc") differ.
+        hash ^= 0xff;
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    hash
+}
+
+/// Contents of dir/name, or None on a miss. A hit bumps the file's modification time, which
+/// is what the LRU eviction orders by.
+pub fn read_cached(dir: &Path, name: &str) -> Option<Vec<u8>> {
+    let path = dir.join(name);
+    let bytes = std::fs::read(&path).ok()?;
+    if let Ok(file) = std::fs::File::options().write(true).open(&path) {
+        let _ = file.set_modified(SystemTime::now());
+    }
+    Some(bytes)
+}
+
+/// Stores bytes as dir/name (via a temp file, so readers never see half a file), then trims
+/// the folder to MAX_CACHE_SUBDIR_BYTES once its estimated size passes the cap. Failures only
+/// cost a future cache miss.
+pub fn write_cached(dir: &Path, name: &str, bytes: &[u8]) {
+    let tmp = dir.join(format!("{}.tmp", name));
+    if std::fs::write(&tmp, bytes).is_err() || std::fs::rename(&tmp, dir.join(name)).is_err() {
+        let _ = std::fs::remove_file(&tmp);
+        return;
+    }
+    let Ok(mut sizes) = CACHE_SIZES.lock() else {
+        return;
+    };
+    match sizes.iter_mut().find(|(d, _)| d == dir) {
+        Some((_, size)) => {
+            *size += bytes.len() as u64;
+            if *size > MAX_CACHE_SUBDIR_BYTES {
+                *size = evict_least_recently_used(dir, MAX_CACHE_SUBDIR_BYTES);
+            }
+        }
+        // First write to this folder this session: one scan gives the starting size.
+        None => {
+            let size = evict_least_recently_used(dir, MAX_CACHE_SUBDIR_BYTES);
+            sizes.push((dir.to_path_buf(), size));
+        }
+    }
+}
+
+/// Deletes the oldest files (by modification time) in dir until it holds at most max_bytes,
+/// and returns the size left.
+fn evict_least_recently_used(dir: &Path, max_bytes: u64) -> u64 {
+    let Ok(read) = std::fs::read_dir(dir) else {
+        return 0;
+    };
+    let mut files: Vec<(SystemTime, u64, PathBuf)> = read
+        .flatten()
+        .filter_map(|e| {
+            let meta = e.metadata().ok().filter(|m| m.is_file())?;
+            Some((meta.modified().ok()?, meta.len(), e.path()))
+        })
+        .collect();
+    let mut total: u64 = files.iter().map(|(_, len, _)| len).sum();
+    if total <= max_bytes {
+        return total;
+    }
+    files.sort_by_key(|(modified, _, _)| *modified);
+    for (_, len, path) in files {
+        if total <= max_bytes {
+            break;
+        }
+        if std::fs::remove_file(&path).is_ok() {
+            total -= len;
+        }
+    }
+    total
+}
+
+/// Deletes the cache subfolder name under root and returns the bytes freed.
+fn clear_subdir(root: &Path, name: &str) -> Result<u64, String> {
+    let dir = root.join(name);
+    if !dir.is_dir() {
+        return Ok(0);
+    }
+    let size = dir_size(&dir);
+    std::fs::remove_dir_all(&dir).map_err(|e| format!("{}: {}", name, e))?;
+    if let Ok(mut sizes) = CACHE_SIZES.lock() {
+        sizes.retain(|(d, _)| *d != dir);
+    }
+    Ok(size)
+}
+
+/// Total size of files under dir (best effort; unreadable entries are skipped).
+fn dir_size(dir: &Path) -> u64 {
+    let mut total = 0;
+    let mut stack = vec![dir.to_path_buf()];
+    while let Some(d) = stack.pop() {
+        let read = match std::fs::read_dir(&d) {
+            Ok(r) => r,
+            Err(_) => continue,
+        };
+        for entry in read.flatten() {
+            match entry.metadata() {
+                Ok(m) if m.is_dir() => stack.push(entry.path()),
+                Ok(m) => total += m.len(),
+                Err(_) => {}
+            }
+        }
+    }
+    total
+}
+
+/// Returns the current cache directory.
+#[tauri::command]
+pub fn get_cache_dir(app: AppHandle) -> Result<String, String> {
+    Ok(cache_dir(&app)?.to_string_lossy().into_owned())
+}
+
+/// Moves the on-disk caches to path (must be an existing, writable folder) and returns it.
+/// Files cached in the previous location are left there; clear them first if needed.
+#[tauri::command]
+pub fn set_cache_dir(app: AppHandle, path: String) -> Result<String, String> {
+    let dir = normalize_path(&path)?;
+    if !dir.is_dir() {
+        return Err("Path is not a directory.".to_string());
+    }
+    let probe = dir.join(".v-see-write-test");
+    std::fs::write(&probe, b"").map_err(|e| format!("Folder is not writable: {}", e))?;
+    let _ = std::fs::remove_file(&probe);
+    let dir_str = dir.to_string_lossy().into_owned();
+    app.state::<PersistenceState>()
+        .set(CACHE_DIR_KEY, &dir_str)?;
+    Ok(dir_str)
+}
+
+/// Deletes all cached thumbnails and returns the number of bytes freed.
+#[tauri::command]
+pub async fn clear_all_caches(app: AppHandle) -> Result<u64, String> {
+    let root = cache_dir(&app)?;
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut freed = 0;
+        for name in CACHE_SUBDIRS {
+            freed += clear_subdir(&root, name)?;
+        }
+        Ok(freed)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Deletes the cached video thumbnails and returns the number of bytes freed.
+#[tauri::command]
+pub async fn clear_thumbnail_cache(app: AppHandle) -> Result<u64, String> {
+    let root = cache_dir(&app)?;
+    tauri::async_runtime::spawn_blocking(move || clear_subdir(&root, VIDEO_THUMBNAIL_CACHE))
+        .await
+        .map_err(|e| e.to_string())?
+}
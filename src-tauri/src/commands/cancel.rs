@@ -0,0 +1,78 @@
+// Author: Viorel LUPU
+// Date: 2026-10-14
+// Purpose: Cooperative cancellation for long-running commands (e.g. get_directory_stats).
+// The frontend passes an operation id when starting the work and calls cancel_operation
+// with the same id to stop it; the worker checks its token and returns a partial result.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use tauri::State;
+
+type CancelFlags = Arc<Mutex<HashMap<String, Arc<AtomicBool>>>>;
+
+/// Purpose: Registry of running operations (operation id -> cancel flag).
+/// Usage: Managed by Tauri; long-running commands call register and poll the returned token.
+/// Reason for choice: AtomicBool flags are cheap to check in tight loops and need no channel.
+#[derive(Default)]
+pub struct CancellationState {
+    flags: CancelFlags,
+}
+
+/// Cancel flag for one running operation. Unregisters itself when dropped, so finished
+/// operations don't accumulate. Without an id it can never be cancelled.
+pub struct CancelToken {
+    id: Option<String>,
+    flag: Arc<AtomicBool>,
+    flags: CancelFlags,
+}
+
+impl CancelToken {
+    pub fn is_cancelled(&self) -> bool {
+        self.flag.load(Ordering::Relaxed)
+    }
+}
+
+impl Drop for CancelToken {
+    fn drop(&mut self) {
+        if let Some(id) = &self.id {
+            if let Ok(mut map) = self.flags.lock() {
+                // Only remove our own flag; a newer operation may have reused the id.
+                if map.get(id).is_some_and(|f| Arc::ptr_eq(f, &self.flag)) {
+                    map.remove(id);
+                }
+            }
+        }
+    }
+}
+
+impl CancellationState {
+    /// Registers an operation under id (replacing any older one with the same id).
+    pub fn register(&self, id: Option<String>) -> CancelToken {
+        let flag = Arc::new(AtomicBool::new(false));
+        if let Some(id) = &id {
+            if let Ok(mut map) = self.flags.lock() {
+                map.insert(id.clone(), Arc::clone(&flag));
+            }
+        }
+        CancelToken {
+            id,
+            flag,
+            flags: Arc::clone(&self.flags),
+        }
+    }
+}
+
+/// Requests cancellation of the operation started with id. Returns false if no such
+/// operation is running (already finished or never started).
+#[tauri::command]
+pub fn cancel_operation(id: String, state: State<CancellationState>) -> Result<bool, String> {
+    let map = state.flags.lock().map_err(|e| e.to_string())?;
+    match map.get(&id) {
+        Some(flag) => {
+            flag.store(true, Ordering::Relaxed);
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
@@ -0,0 +1,13 @@
+// Author: Viorel LUPU
+// Date: 2026-10-14
+// Purpose: Clipboard access for the frontend (e.g. copy the current file path from the viewer).
+// Goes through Tauri's clipboard-manager plugin so no JS clipboard permission is needed.
+
+use tauri::AppHandle;
+use tauri_plugin_clipboard_manager::ClipboardExt;
+
+/// Replaces the system clipboard contents with text.
+#[tauri::command]
+pub fn copy_text_to_clipboard(app: AppHandle, text: String) -> Result<(), String> {
+    app.clipboard().write_text(text).map_err(|e| e.to_string())
+}
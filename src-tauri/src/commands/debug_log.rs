@@ -2,67 +2,127 @@
 // Date: 2026-02-17
 // Purpose: Debug logging to a file. Frontend console.log/error and backend messages
 // are written to a log file for debugging. Echoes frontend logs and errors.
+// The file is rotated by size (v-see.log -> v-see.1.log -> ... v-see.3.log) so it stays small.
 
+use serde::{Deserialize, Serialize};
 use std::fs::OpenOptions;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::sync::Mutex;
 
-fn format_timestamp() -> String {
-    let d = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap_or_default();
-    let secs = d.as_secs();
-    let millis = d.subsec_millis();
-    let (date, time) = {
-        const SECS_PER_DAY: u64 = 86400;
-        let days_since_epoch = secs / SECS_PER_DAY;
-        let time_secs = secs % SECS_PER_DAY;
-        let hour = time_secs / 3600;
-        let min = (time_secs % 3600) / 60;
-        let sec = time_secs % 60;
-        let (y, m, d) = days_to_ymd(days_since_epoch);
-        (
-            format!("{:04}-{:02}-{:02}", y, m, d),
-            format!("{:02}:{:02}:{:02}.{:03}", hour, min, sec, millis),
-        )
-    };
-    format!("{} {}", date, time)
-}
-
-#[allow(clippy::many_single_char_names)]
-fn days_to_ymd(days: u64) -> (u64, u64, u64) {
-    let (mut y, mut m, mut d) = (1970u64, 1u64, 1u64);
-    let mut n = days + 1;
-    let is_leap = |year: u64| year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
-    let month_days = |year: u64, month: u64| {
-        let feb = if is_leap(year) { 29 } else { 28 };
-        match month {
-            1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
-            4 | 6 | 9 | 11 => 30,
-            2 => feb,
-            _ => 0,
+/// Line format of the log file.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+pub enum LogFormat {
+    /// "YYYY-MM-DD HH:MM:SS.mmm +HH:MM [LEVEL] message"
+    #[default]
+    Plain,
+    /// {"ts":"YYYY-MM-DD HH:MM:SS.mmm +HH:MM","level":"LEVEL","msg":"message","source":"viewer"}
+    /// per line; source is left out when the caller gave none.
+    Json,
+}
+
+/// Current format for new log lines; Plain until set_log_format is called or
+/// LOG_FORMAT_ENV is set.
+static LOG_FORMAT: Mutex<LogFormat> = Mutex::new(LogFormat::Plain);
+
+/// Severity of a log line, lowest first; lines below the minimum level are dropped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum LogLevel {
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl LogLevel {
+    /// Parses a normalized level name ("DEBUG", "INFO", "WARN", "ERROR").
+    fn parse(level: &str) -> Option<Self> {
+        match level {
+            "DEBUG" => Some(LogLevel::Debug),
+            "INFO" => Some(LogLevel::Info),
+            "WARN" => Some(LogLevel::Warn),
+            "ERROR" => Some(LogLevel::Error),
+            _ => None,
         }
+    }
+}
+
+/// Lowest level written to the log: DEBUG in debug builds, INFO in release. Changed with
+/// set_log_level.
+static MIN_LOG_LEVEL: Mutex<LogLevel> = Mutex::new(if cfg!(debug_assertions) {
+    LogLevel::Debug
+} else {
+    LogLevel::Info
+});
+
+/// Maps frontend console level names to log levels (log -> INFO, warning -> WARN, ...).
+/// Unknown names are kept as given.
+fn normalize_level(level: &str) -> &str {
+    match level {
+        "log" | "info" => "INFO",
+        "warn" | "warning" => "WARN",
+        "error" => "ERROR",
+        "debug" => "DEBUG",
+        _ => level,
+    }
+}
+
+/// False when level is below the minimum level. Levels with other names are always written.
+fn level_enabled(level: &str) -> bool {
+    let Some(level) = LogLevel::parse(level) else {
+        return true;
     };
-    while n > 0 {
-        let dim = month_days(y, m);
-        if n > dim {
-            n -= dim;
-            m += 1;
-            if m > 12 {
-                m = 1;
-                y += 1;
-            }
-        } else {
-            d = n;
-            n = 0;
-        }
+    let min = MIN_LOG_LEVEL.lock().map(|l| *l).unwrap_or(LogLevel::Debug);
+    level >= min
+}
+
+/// Environment variable that selects the log format at startup ("json" or "plain").
+const LOG_FORMAT_ENV: &str = "VSEE_LOG_FORMAT";
+
+/// Applies LOG_FORMAT_ENV if set; called once from setup. Unknown values keep Plain.
+pub fn apply_log_format_env() {
+    let Ok(value) = std::env::var(LOG_FORMAT_ENV) else {
+        return;
+    };
+    let format = match value.trim().to_ascii_lowercase().as_str() {
+        "json" => LogFormat::Json,
+        _ => LogFormat::Plain,
+    };
+    if let Ok(mut current) = LOG_FORMAT.lock() {
+        *current = format;
     }
-    (y, m, d)
 }
+
+/// One JSON log line; serde_json escapes quotes, newlines, and control characters in msg.
+#[derive(Serialize)]
+struct JsonLogLine<'a> {
+    ts: &'a str,
+    level: &'a str,
+    msg: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    source: Option<&'a str>,
+}
+
+/// Local time with its UTC offset, e.g. "2026-02-17 14:03:22.123 +02:00", so log lines match
+/// the clock the user saw on screen.
+fn format_timestamp() -> String {
+    chrono::Local::now()
+        .format("%Y-%m-%d %H:%M:%S%.3f %:z")
+        .to_string()
+}
+
 use std::io::Write;
 use std::path::PathBuf;
+use tauri::{AppHandle, Manager};
 
-/// Log file path: APPDATA/V-See/logs/v-see.log (Windows) or HOME/.v-see/logs/v-see.log (Unix).
-fn log_dir() -> Option<PathBuf> {
+/// Log directory: logs/ inside Tauri's app data dir; falls back to the legacy
+/// APPDATA/V-See/logs (Windows) or HOME/.v-see/logs (Unix) if the resolver fails.
+fn log_dir(app: &AppHandle) -> Option<PathBuf> {
+    match app.path().app_data_dir() {
+        Ok(dir) => Some(dir.join("logs")),
+        Err(_) => legacy_log_dir(),
+    }
+}
+
+fn legacy_log_dir() -> Option<PathBuf> {
     #[cfg(target_os = "windows")]
     {
         std::env::var_os("APPDATA").map(PathBuf::from).map(|p| p.join("V-See").join("logs"))
@@ -75,23 +135,95 @@ fn log_dir() -> Option<PathBuf> {
     }
 }
 
-fn log_path() -> Option<PathBuf> {
-    log_dir().map(|d| d.join("v-see.log"))
+/// Checks that the log directory can be created and written to (a probe file is created and
+/// removed again) and returns it.
+pub fn check_log_dir_writable(app: &AppHandle) -> Result<PathBuf, String> {
+    let dir = log_dir(app).ok_or_else(|| "Could not determine log directory".to_string())?;
+    std::fs::create_dir_all(&dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+    let probe = dir.join(".write-test");
+    std::fs::write(&probe, b"").map_err(|e| format!("{}: {}", dir.display(), e))?;
+    let _ = std::fs::remove_file(&probe);
+    Ok(dir)
+}
+
+fn log_path(app: &AppHandle) -> Option<PathBuf> {
+    log_dir(app).map(|d| d.join("v-see.log"))
+}
+
+/// The active log is rotated once it grows past this size.
+const LOG_ROTATE_BYTES: u64 = 5 * 1024 * 1024;
+
+/// Rotated logs kept next to the active one (v-see.1.log is the most recent).
+const LOG_ROTATED_KEEP: u32 = 3;
+
+/// Serializes rotation and appends, so two writers can't rotate the same file twice.
+static LOG_WRITE_LOCK: Mutex<()> = Mutex::new(());
+
+/// Path of the n-th rotated log next to path (v-see.log -> v-see.n.log).
+fn rotated_log_path(path: &std::path::Path, n: u32) -> PathBuf {
+    path.with_file_name(format!("v-see.{}.log", n))
+}
+
+/// Shifts v-see.log to v-see.1.log (and older ones up, dropping the last) when it is over
+/// LOG_ROTATE_BYTES. Errors are ignored: logging continues in the current file.
+fn rotate_if_needed(path: &std::path::Path) {
+    let size = std::fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+    if size <= LOG_ROTATE_BYTES {
+        return;
+    }
+    let _ = std::fs::remove_file(rotated_log_path(path, LOG_ROTATED_KEEP));
+    for n in (1..LOG_ROTATED_KEEP).rev() {
+        let _ = std::fs::rename(rotated_log_path(path, n), rotated_log_path(path, n + 1));
+    }
+    let _ = std::fs::rename(path, rotated_log_path(path, 1));
+}
+
+/// Formats one log line (with trailing newline) in the current LogFormat. In plain text the
+/// source, when given, follows the level: "... [INFO] [viewer] message".
+fn format_log_line(level: &str, message: &str, source: Option<&str>) -> String {
+    let ts = format_timestamp();
+    let level = level.to_uppercase();
+    let plain = || match source {
+        Some(src) => format!("{} [{}] [{}] {}\n", ts, level, src, message),
+        None => format!("{} [{}] {}\n", ts, level, message),
+    };
+    let format = LOG_FORMAT.lock().map(|f| *f).unwrap_or_default();
+    match format {
+        LogFormat::Plain => plain(),
+        LogFormat::Json => {
+            let line = JsonLogLine {
+                ts: &ts,
+                level: &level,
+                msg: message,
+                source,
+            };
+            match serde_json::to_string(&line) {
+                Ok(json) => json + "\n",
+                Err(_) => plain(),
+            }
+        }
+    }
 }
 
-/// Writes a single line to the log file. Creates the log directory if needed.
-/// Format: "YYYY-MM-DD HH:MM:SS [LEVEL] message"
-fn write_log_line(level: &str, message: &str) -> Result<(), String> {
-    let path = log_path().ok_or_else(|| "Could not determine log directory".to_string())?;
+/// Writes a single line to the log file at path. Creates the log directory if needed.
+/// Format: "YYYY-MM-DD HH:MM:SS.mmm +HH:MM [LEVEL] message", or one JSON object per line
+/// (see LogFormat).
+fn write_log_line(
+    path: &std::path::Path,
+    level: &str,
+    message: &str,
+    source: Option<&str>,
+) -> Result<(), String> {
     if let Some(parent) = path.parent() {
         std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
     }
-    let ts = format_timestamp();
-    let line = format!("{} [{}] {}\n", ts, level.to_uppercase(), message);
+    let line = format_log_line(level, message, source);
+    let _guard = LOG_WRITE_LOCK.lock();
+    rotate_if_needed(path);
     let mut f = OpenOptions::new()
         .create(true)
         .append(true)
-        .open(&path)
+        .open(path)
         .map_err(|e| format!("{}: {}", path.display(), e))?;
     f.write_all(line.as_bytes())
         .map_err(|e| e.to_string())?;
@@ -100,24 +232,137 @@ fn write_log_line(level: &str, message: &str) -> Result<(), String> {
 }
 
 /// Tauri command: append a message to the debug log file and echo to stderr (terminal).
-/// Called by the frontend for console.log/warn/error and unhandled errors.
+/// Called by the frontend for console.log/warn/error and unhandled errors. source optionally
+/// tags where the message came from (e.g. "viewer", "audio").
 #[tauri::command]
-pub fn debug_log(level: String, message: String) -> Result<(), String> {
-    let level = level.as_str();
-    let level = match level {
-        "log" | "info" => "INFO",
-        "warn" | "warning" => "WARN",
-        "error" => "ERROR",
-        "debug" => "DEBUG",
-        _ => level,
-    };
+pub fn debug_log(
+    app: AppHandle,
+    level: String,
+    message: String,
+    source: Option<String>,
+) -> Result<(), String> {
+    let path = log_path(&app).ok_or_else(|| "Could not determine log directory".to_string())?;
+    log_message(&path, &level, &message, source.as_deref())
+}
+
+/// debug_log for the log file at path: drops levels below the minimum, echoes to stderr and
+/// appends the line.
+fn log_message(
+    path: &std::path::Path,
+    level: &str,
+    message: &str,
+    source: Option<&str>,
+) -> Result<(), String> {
+    let level = normalize_level(level);
+    if !level_enabled(level) {
+        return Ok(());
+    }
     // Echo to terminal when running e.g. `tauri dev`
-    eprintln!("[{}] {}", level, message);
-    write_log_line(level, &message)
+    let source = source.map(str::trim).filter(|s| !s.is_empty());
+    match source {
+        Some(src) => eprintln!("[{}] [{}] {}", level, src, message),
+        None => eprintln!("[{}] {}", level, message),
+    }
+    write_log_line(path, level, message, source)
 }
 
 /// Returns the path to the log file for display in UI or help.
 #[tauri::command]
-pub fn get_debug_log_path() -> Option<String> {
-    log_path().map(|p| p.to_string_lossy().into_owned())
+pub fn get_debug_log_path(app: AppHandle) -> Option<String> {
+    log_path(&app).map(|p| p.to_string_lossy().into_owned())
+}
+
+/// Switches the log file between plain text and JSON lines (applies to subsequent lines).
+#[tauri::command]
+pub fn set_log_format(format: LogFormat) -> Result<(), String> {
+    let mut current = LOG_FORMAT.lock().map_err(|e| e.to_string())?;
+    *current = format;
+    Ok(())
+}
+
+/// Sets the lowest level that is written ("debug", "info", "warn" or "error"; frontend names
+/// such as "log" are accepted too). Applies to subsequent lines.
+#[tauri::command]
+pub fn set_log_level(level: String) -> Result<(), String> {
+    let name = level.trim().to_ascii_lowercase();
+    let parsed = LogLevel::parse(normalize_level(&name))
+        .ok_or_else(|| format!("Unknown log level: {}", level))?;
+    let mut current = MIN_LOG_LEVEL.lock().map_err(|e| e.to_string())?;
+    *current = parsed;
+    Ok(())
+}
+
+/// Upper bound for get_recent_log_lines so the log viewer never pulls megabytes over IPC.
+const MAX_RECENT_LOG_LINES: usize = 5000;
+
+/// Size of each block read backwards from the end of the log.
+const LOG_TAIL_CHUNK: u64 = 64 * 1024;
+
+/// Reads the last count lines of the file at path by reading blocks from the end, so a large
+/// log is not loaded whole. Lines are returned oldest first.
+fn read_last_lines(path: &std::path::Path, count: usize) -> std::io::Result<Vec<String>> {
+    use std::io::{Read, Seek, SeekFrom};
+    let mut f = std::fs::File::open(path)?;
+    let len = f.metadata()?.len();
+    let mut pos = len;
+    let mut tail: Vec<u8> = Vec::new();
+    // A trailing newline ends the last line; it doesn't start an empty one.
+    while pos > 0 && tail.iter().filter(|&&b| b == b'\n').count() <= count {
+        let start = pos.saturating_sub(LOG_TAIL_CHUNK);
+        let mut block = vec![0u8; (pos - start) as usize];
+        f.seek(SeekFrom::Start(start))?;
+        f.read_exact(&mut block)?;
+        block.extend_from_slice(&tail);
+        tail = block;
+        pos = start;
+    }
+    let text = String::from_utf8_lossy(&tail);
+    let mut lines: Vec<String> = text
+        .lines()
+        .map(|l| l.trim_end_matches('\r').to_string())
+        .collect();
+    // When reading stopped mid-file the first line may be cut; enough whole lines follow it.
+    if pos > 0 && !lines.is_empty() {
+        lines.remove(0);
+    }
+    let skip = lines.len().saturating_sub(count);
+    Ok(lines.split_off(skip))
+}
+
+/// Returns up to count of the newest log lines (capped at 5000), oldest first, for the in-app
+/// log viewer. A log file that doesn't exist yet gives an empty list.
+#[tauri::command]
+pub async fn get_recent_log_lines(app: AppHandle, count: usize) -> Result<Vec<String>, String> {
+    let path = log_path(&app).ok_or_else(|| "Could not determine log directory".to_string())?;
+    let count = count.min(MAX_RECENT_LOG_LINES);
+    if count == 0 {
+        return Ok(Vec::new());
+    }
+    tauri::async_runtime::spawn_blocking(move || match read_last_lines(&path, count) {
+        Ok(lines) => Ok(lines),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(format!("{}: {}", path.display(), e)),
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn debug_is_dropped_at_info_and_error_is_written() {
+        let dir = std::env::temp_dir().join(format!("v-see-log-level-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let path = dir.join("v-see.log");
+        set_log_level("info".to_string()).unwrap();
+        log_message(&path, "debug", "hidden detail", None).unwrap();
+        log_message(&path, "error", "visible failure", Some("viewer")).unwrap();
+        let text = std::fs::read_to_string(&path).unwrap();
+        assert!(!text.contains("hidden detail"));
+        assert!(text.contains("[ERROR] [viewer] visible failure"));
+        assert_eq!(text.lines().count(), 1);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
 }
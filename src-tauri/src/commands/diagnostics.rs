@@ -0,0 +1,120 @@
+// Author: Viorel LUPU
+// Date: 2026-10-14
+// Purpose: One health check for bug reports. run_diagnostics runs the individual checks
+// (audio output, state.db, log folder, ffmpeg/ffprobe, app data folder) and reports each as
+// ok + message, so the "Copy diagnostics" button can paste the lot into an issue.
+
+use super::audio::find_output_device;
+use super::debug_log::check_log_dir_writable;
+use super::persistence::{PersistenceState, AUDIO_DEVICE_KEY};
+use super::video_thumb::external_tools;
+use rodio::cpal::traits::DeviceTrait;
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+
+/// Outcome of one diagnostic check; message says what was found or what went wrong.
+#[derive(Debug, Serialize)]
+pub struct DiagnosticCheck {
+    pub ok: bool,
+    pub message: String,
+}
+
+impl DiagnosticCheck {
+    fn from_result(result: Result<String, String>) -> Self {
+        match result {
+            Ok(message) => DiagnosticCheck { ok: true, message },
+            Err(message) => DiagnosticCheck { ok: false, message },
+        }
+    }
+}
+
+/// Purpose: Result of run_diagnostics.
+/// Usage: Shown in the About/Help dialog and copied as JSON into bug reports.
+/// Reason for choice: One named field per subsystem (rather than a list) keeps the copied
+/// report stable and easy to read across versions.
+#[derive(Debug, Serialize)]
+pub struct Diagnostics {
+    pub audio_device: DiagnosticCheck,
+    pub persistence: DiagnosticCheck,
+    pub log_directory: DiagnosticCheck,
+    pub ffmpeg: DiagnosticCheck,
+    pub ffprobe: DiagnosticCheck,
+    pub app_data_dir: DiagnosticCheck,
+}
+
+/// The output device playback would use, noting when the saved choice is missing.
+fn check_audio_device(persistence: &PersistenceState) -> Result<String, String> {
+    let saved = persistence
+        .get(AUDIO_DEVICE_KEY)
+        .ok()
+        .flatten()
+        .filter(|n| !n.is_empty());
+    let (device, fell_back) = find_output_device(saved.as_deref())
+        .ok_or_else(|| "No audio output device found.".to_string())?;
+    let name = device.name().unwrap_or_else(|_| "(unnamed)".to_string());
+    match saved {
+        Some(saved) if fell_back => Ok(format!(
+            "{} (saved device \"{}\" not found, using the default)",
+            name, saved
+        )),
+        _ => Ok(name),
+    }
+}
+
+fn check_persistence(persistence: &PersistenceState) -> Result<String, String> {
+    let path = persistence.db_path()?;
+    let path = path.to_string_lossy().into_owned();
+    persistence
+        .check_writable()
+        .map(|_| format!("{} (writable)", path))
+        .map_err(|e| format!("{}: {}", path, e))
+}
+
+fn check_tool(name: &str, present: bool, version: Option<String>) -> Result<String, String> {
+    if !present {
+        return Err(format!(
+            "{} not found ({}_PATH, bundled copy or PATH).",
+            name,
+            name.to_uppercase()
+        ));
+    }
+    Ok(match version {
+        Some(v) => format!("{} {}", name, v),
+        None => format!("{} (unknown version)", name),
+    })
+}
+
+/// Runs every check and reports each subsystem's status; never fails as a whole, since a
+/// broken subsystem is exactly what the report should show.
+#[tauri::command]
+pub async fn run_diagnostics(app: AppHandle) -> Result<Diagnostics, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let persistence = app.state::<PersistenceState>();
+        let tools = external_tools();
+        Diagnostics {
+            audio_device: DiagnosticCheck::from_result(check_audio_device(&persistence)),
+            persistence: DiagnosticCheck::from_result(check_persistence(&persistence)),
+            log_directory: DiagnosticCheck::from_result(
+                check_log_dir_writable(&app).map(|d| format!("{} (writable)", d.display())),
+            ),
+            ffmpeg: DiagnosticCheck::from_result(check_tool(
+                "ffmpeg",
+                tools.ffmpeg,
+                tools.ffmpeg_version,
+            )),
+            ffprobe: DiagnosticCheck::from_result(check_tool(
+                "ffprobe",
+                tools.ffprobe,
+                tools.ffprobe_version,
+            )),
+            app_data_dir: DiagnosticCheck::from_result(
+                app.path()
+                    .app_data_dir()
+                    .map(|d| d.to_string_lossy().into_owned())
+                    .map_err(|e| e.to_string()),
+            ),
+        }
+    })
+    .await
+    .map_err(|e| e.to_string())
+}
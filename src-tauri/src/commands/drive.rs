@@ -0,0 +1,97 @@
+// Author: Viorel LUPU
+// Date: 2026-10-14
+// Purpose: "Safe eject" support for removable drives. flush_drive commits pending writes
+// (e.g. from copy/move) on a USB stick or card before the user pulls it.
+
+/// Drive letter ("E") from a root such as "E:", "E:\" or "E:/".
+fn drive_letter(drive_root: &str) -> Option<char> {
+    let trimmed = drive_root.trim().trim_end_matches(['\\', '/']);
+    let mut chars = trimmed.chars();
+    let letter = chars.next().filter(|c| c.is_ascii_alphabetic())?;
+    match (chars.next(), chars.next()) {
+        (Some(':'), None) => Some(letter.to_ascii_uppercase()),
+        _ => None,
+    }
+}
+
+/// Opens \\.\X: and calls FlushFileBuffers on the volume handle.
+#[cfg(target_os = "windows")]
+fn flush_volume(letter: char) -> Result<(), String> {
+    use windows_sys::Win32::Foundation::{
+        CloseHandle, GetLastError, ERROR_ACCESS_DENIED, GENERIC_READ, GENERIC_WRITE,
+        INVALID_HANDLE_VALUE,
+    };
+    use windows_sys::Win32::Storage::FileSystem::{
+        CreateFileW, FlushFileBuffers, GetDriveTypeW, FILE_SHARE_READ, FILE_SHARE_WRITE,
+        OPEN_EXISTING,
+    };
+    /// GetDriveTypeW result for USB sticks and card readers.
+    const DRIVE_REMOVABLE: u32 = 2;
+
+    let wide = |s: String| {
+        s.encode_utf16()
+            .chain(std::iter::once(0))
+            .collect::<Vec<u16>>()
+    };
+    let root = wide(format!("{}:\\", letter));
+    // SAFETY: root is a NUL-terminated UTF-16 string that outlives the call.
+    if unsafe { GetDriveTypeW(root.as_ptr()) } != DRIVE_REMOVABLE {
+        return Err(format!("{}: is not a removable drive.", letter));
+    }
+    let volume = wide(format!("\\\\.\\{}:", letter));
+    // SAFETY: volume is NUL-terminated; the returned handle is closed below.
+    let handle = unsafe {
+        CreateFileW(
+            volume.as_ptr(),
+            GENERIC_READ | GENERIC_WRITE,
+            FILE_SHARE_READ | FILE_SHARE_WRITE,
+            std::ptr::null(),
+            OPEN_EXISTING,
+            0,
+            std::ptr::null_mut(),
+        )
+    };
+    if handle == INVALID_HANDLE_VALUE {
+        // SAFETY: reads the calling thread's last-error value.
+        return Err(match unsafe { GetLastError() } {
+            ERROR_ACCESS_DENIED => {
+                "Access denied (flushing a drive requires administrator rights).".to_string()
+            }
+            _ => format!(
+                "Drive {}: could not be opened (it may have been removed).",
+                letter
+            ),
+        });
+    }
+    // SAFETY: handle is a valid volume handle opened above.
+    let flushed = unsafe { FlushFileBuffers(handle) } != 0;
+    // SAFETY: reads the last error before CloseHandle can overwrite it.
+    let error = if flushed {
+        0
+    } else {
+        unsafe { GetLastError() }
+    };
+    // SAFETY: handle is valid and not used after this.
+    unsafe { CloseHandle(handle) };
+    if flushed {
+        Ok(())
+    } else {
+        Err(format!("Flush failed on {}: (error {}).", letter, error))
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn flush_volume(_letter: char) -> Result<(), String> {
+    Err("Flushing drives is only supported on Windows.".to_string())
+}
+
+/// Commits pending writes on a removable drive (e.g. "E:\") so it can be unplugged safely.
+/// Errors if the drive is not removable, is gone, or the volume can't be opened.
+#[tauri::command]
+pub async fn flush_drive(drive_root: String) -> Result<(), String> {
+    let letter =
+        drive_letter(&drive_root).ok_or_else(|| "Not a drive root (e.g. E:\\).".to_string())?;
+    tauri::async_runtime::spawn_blocking(move || flush_volume(letter))
+        .await
+        .map_err(|e| e.to_string())?
+}
@@ -0,0 +1,303 @@
+// Author: Viorel LUPU
+// Date: 2026-10-14
+// Purpose: "Find duplicates" tool. Walks the images under a folder, computes a perceptual hash
+// for each, and groups images whose hashes are within a small Hamming distance, so resized or
+// re-encoded copies are found as well as exact ones. compare_files helps pick which copy to keep.
+
+use super::cancel::{CancelToken, CancellationState};
+use super::formats::image_mime;
+use super::fs::{is_hidden_entry, normalize_path, MAX_STATS_NODES};
+use image_hasher::{HashAlg, HasherConfig, ImageHash};
+use serde::Serialize;
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// Max images hashed per scan; the rest of the tree is ignored (result marked truncated).
+const MAX_DUPLICATE_FILES: usize = 5_000;
+
+/// Files larger than this are skipped rather than decoded.
+const MAX_DUPLICATE_FILE_SIZE: u64 = 50 * 1024 * 1024;
+
+/// Hashes at most this many bits apart are treated as the same picture (64-bit hash).
+const MAX_HASH_DISTANCE: u32 = 5;
+
+/// Emit a duplicate-scan-progress event every this many hashed images.
+const DUPLICATE_PROGRESS_EVERY: usize = 25;
+
+/// Result of find_duplicate_images. Each group holds two or more paths of similar images.
+/// `skipped` counts images that were too large or could not be decoded.
+#[derive(Debug, Serialize)]
+pub struct DuplicateScanResult {
+    pub groups: Vec<Vec<String>>,
+    pub scanned: usize,
+    pub skipped: usize,
+    pub truncated: bool,
+    pub cancelled: bool,
+}
+
+/// Progress payload for duplicate-scan-progress.
+#[derive(Debug, Clone, Serialize)]
+struct DuplicateScanProgress {
+    root: String,
+    hashed: usize,
+    total: usize,
+}
+
+/// Image files under root (recursive, hidden entries and unreadable folders skipped).
+/// Returns the paths and whether MAX_DUPLICATE_FILES or MAX_STATS_NODES visited entries was
+/// hit. Stops early, with the files found so far, when cancel is set.
+fn collect_images(root: &Path, cancel: &CancelToken) -> (Vec<PathBuf>, bool) {
+    let mut files = Vec::new();
+    let mut visited: u64 = 0;
+    let mut stack = vec![root.to_path_buf()];
+    while let Some(dir) = stack.pop() {
+        let read = match std::fs::read_dir(&dir) {
+            Ok(r) => r,
+            Err(_) => continue,
+        };
+        for entry in read.flatten() {
+            if cancel.is_cancelled() {
+                return (files, false);
+            }
+            visited += 1;
+            if visited > MAX_STATS_NODES {
+                return (files, true);
+            }
+            let meta = match entry.metadata() {
+                Ok(m) => m,
+                Err(_) => continue,
+            };
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if is_hidden_entry(&name, &meta) {
+                continue;
+            }
+            let path = entry.path();
+            if meta.is_dir() {
+                stack.push(path);
+                continue;
+            }
+            let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("").to_lowercase();
+            // SVG is vector (no pixels to hash).
+            if ext == "svg" || image_mime(&ext).is_none() {
+                continue;
+            }
+            if files.len() >= MAX_DUPLICATE_FILES {
+                return (files, true);
+            }
+            files.push(path);
+        }
+    }
+    (files, false)
+}
+
+fn hash_file(hasher: &image_hasher::Hasher, path: &Path) -> Option<ImageHash> {
+    let size = std::fs::metadata(path).ok()?.len();
+    if size > MAX_DUPLICATE_FILE_SIZE {
+        return None;
+    }
+    let img = image::ImageReader::open(path)
+        .ok()?
+        .with_guessed_format()
+        .ok()?
+        .decode()
+        .ok()?;
+    Some(hasher.hash_image(&img))
+}
+
+/// Union-find root with path halving.
+fn find_root(parent: &mut [usize], mut i: usize) -> usize {
+    while parent[i] != i {
+        parent[i] = parent[parent[i]];
+        i = parent[i];
+    }
+    i
+}
+
+/// Groups hashes within MAX_HASH_DISTANCE of each other (transitively); singletons are dropped.
+fn group_similar(hashed: &[(PathBuf, ImageHash)]) -> Vec<Vec<String>> {
+    let mut parent: Vec<usize> = (0..hashed.len()).collect();
+    for i in 0..hashed.len() {
+        for j in (i + 1)..hashed.len() {
+            if hashed[i].1.dist(&hashed[j].1) <= MAX_HASH_DISTANCE {
+                let (a, b) = (find_root(&mut parent, i), find_root(&mut parent, j));
+                if a != b {
+                    parent[b] = a;
+                }
+            }
+        }
+    }
+    let mut groups: std::collections::HashMap<usize, Vec<String>> = Default::default();
+    for (i, (path, _)) in hashed.iter().enumerate() {
+        let root = find_root(&mut parent, i);
+        groups
+            .entry(root)
+            .or_default()
+            .push(path.to_string_lossy().into_owned());
+    }
+    let mut groups: Vec<Vec<String>> = groups.into_values().filter(|g| g.len() > 1).collect();
+    for g in &mut groups {
+        g.sort_by_key(|p| p.to_lowercase());
+    }
+    groups.sort_by_key(|g| g[0].to_lowercase());
+    groups
+}
+
+/// Finds groups of visually similar images under root (perceptual hash, recursive).
+/// Runs on a background thread and emits duplicate-scan-progress while hashing.
+/// Pass operation_id to be able to stop it with cancel_operation (groups found so far are returned).
+#[tauri::command]
+pub async fn find_duplicate_images(
+    app: AppHandle,
+    root: String,
+    operation_id: Option<String>,
+) -> Result<DuplicateScanResult, String> {
+    let root = normalize_path(&root)?;
+    if !root.is_dir() {
+        return Err("Path is not a directory.".to_string());
+    }
+    let cancel = app.state::<CancellationState>().register(operation_id);
+    tauri::async_runtime::spawn_blocking(move || {
+        let (files, truncated) = collect_images(&root, &cancel);
+        let hasher = HasherConfig::new().hash_alg(HashAlg::Gradient).to_hasher();
+        let root_str = root.to_string_lossy().into_owned();
+        let mut hashed = Vec::with_capacity(files.len());
+        let mut skipped = 0;
+        let mut cancelled = cancel.is_cancelled();
+        for (i, path) in files.iter().enumerate() {
+            if cancel.is_cancelled() {
+                cancelled = true;
+                break;
+            }
+            match hash_file(&hasher, path) {
+                Some(hash) => hashed.push((path.clone(), hash)),
+                None => skipped += 1,
+            }
+            if (i + 1) % DUPLICATE_PROGRESS_EVERY == 0 {
+                let _ = app.emit(
+                    "duplicate-scan-progress",
+                    DuplicateScanProgress {
+                        root: root_str.clone(),
+                        hashed: i + 1,
+                        total: files.len(),
+                    },
+                );
+            }
+        }
+        DuplicateScanResult {
+            groups: group_similar(&hashed),
+            scanned: hashed.len() + skipped,
+            skipped,
+            truncated,
+            cancelled,
+        }
+    })
+    .await
+    .map_err(|e| e.to_string())
+}
+
+/// Size, modification time, and (for raster images) dimensions of one compared file.
+#[derive(Debug, Serialize)]
+pub struct ComparedFile {
+    pub path: String,
+    pub size: u64,
+    /// Milliseconds since the Unix epoch, if the file system reports it.
+    pub modified_ms: Option<u64>,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+}
+
+/// Result of compare_files. `identical` is true only if the contents match byte for byte.
+#[derive(Debug, Serialize)]
+pub struct FileComparison {
+    pub a: ComparedFile,
+    pub b: ComparedFile,
+    pub identical: bool,
+}
+
+fn describe_file(path: &Path) -> Result<ComparedFile, String> {
+    let meta = std::fs::metadata(path).map_err(|e| e.to_string())?;
+    if !meta.is_file() {
+        return Err(format!("Not a file: {}", path.display()));
+    }
+    let modified_ms = meta
+        .modified()
+        .ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_millis() as u64);
+    let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("").to_lowercase();
+    // Header only; SVG has no pixel size.
+    let dims = if ext != "svg" && image_mime(&ext).is_some() {
+        image::ImageReader::open(path)
+            .ok()
+            .and_then(|r| r.with_guessed_format().ok())
+            .and_then(|r| r.into_dimensions().ok())
+    } else {
+        None
+    };
+    Ok(ComparedFile {
+        path: path.to_string_lossy().into_owned(),
+        size: meta.len(),
+        modified_ms,
+        width: dims.map(|d| d.0),
+        height: dims.map(|d| d.1),
+    })
+}
+
+/// Streams both files in fixed-size chunks and stops at the first differing chunk.
+fn same_contents(a: &Path, b: &Path) -> Result<bool, String> {
+    const CHUNK: usize = 64 * 1024;
+    let mut ra = BufReader::new(File::open(a).map_err(|e| e.to_string())?);
+    let mut rb = BufReader::new(File::open(b).map_err(|e| e.to_string())?);
+    let mut buf_a = vec![0u8; CHUNK];
+    let mut buf_b = vec![0u8; CHUNK];
+    loop {
+        let n = read_full(&mut ra, &mut buf_a)?;
+        let m = read_full(&mut rb, &mut buf_b)?;
+        if n != m || buf_a[..n] != buf_b[..m] {
+            return Ok(false);
+        }
+        if n == 0 {
+            return Ok(true);
+        }
+    }
+}
+
+/// Fills buf as far as possible (short only at end of file), so chunks line up between files.
+fn read_full(reader: &mut impl Read, buf: &mut [u8]) -> Result<usize, String> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e.to_string()),
+        }
+    }
+    Ok(filled)
+}
+
+/// Compares two files for a "keep larger / keep newer" decision: size, modification time,
+/// image dimensions, and whether the bytes are identical (skipped when the sizes differ).
+#[tauri::command]
+pub async fn compare_files(a: String, b: String) -> Result<FileComparison, String> {
+    let (a, b) = (normalize_path(&a)?, normalize_path(&b)?);
+    tauri::async_runtime::spawn_blocking(move || {
+        let (info_a, info_b) = (describe_file(&a)?, describe_file(&b)?);
+        // Paths are kept as given, so compare the resolved files for "same file twice".
+        let same_file = match (std::fs::canonicalize(&a), std::fs::canonicalize(&b)) {
+            (Ok(ra), Ok(rb)) => ra == rb,
+            _ => a == b,
+        };
+        let identical = same_file || (info_a.size == info_b.size && same_contents(&a, &b)?);
+        Ok(FileComparison {
+            a: info_a,
+            b: info_b,
+            identical,
+        })
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
@@ -0,0 +1,142 @@
+// Author: Viorel LUPU
+// Date: 2026-10-14
+// Purpose: Open media in another program. Users can pick an external image and video app
+// (stored in the persistence store); when none is set the file opens with the OS default
+// handler, the same as double-clicking it in Explorer.
+
+use super::fs::normalize_path;
+use super::persistence::{PersistenceState, EXTERNAL_IMAGE_APP_KEY, EXTERNAL_VIDEO_APP_KEY};
+use serde::Deserialize;
+use std::path::Path;
+use std::process::Command;
+use tauri::State;
+
+/// Which external app setting applies to a file.
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MediaKind {
+    Image,
+    Video,
+}
+
+impl MediaKind {
+    fn key(self) -> &'static str {
+        match self {
+            MediaKind::Image => EXTERNAL_IMAGE_APP_KEY,
+            MediaKind::Video => EXTERNAL_VIDEO_APP_KEY,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            MediaKind::Image => "image",
+            MediaKind::Video => "video",
+        }
+    }
+}
+
+/// Opens path with the OS default handler (ShellExecuteW "open" on Windows).
+#[cfg(target_os = "windows")]
+pub fn open_with_default(path: &Path) -> Result<(), String> {
+    use windows_sys::Win32::UI::Shell::ShellExecuteW;
+    use windows_sys::Win32::UI::WindowsAndMessaging::SW_SHOWNORMAL;
+
+    let wide = |s: &str| {
+        s.encode_utf16()
+            .chain(std::iter::once(0))
+            .collect::<Vec<u16>>()
+    };
+    let verb = wide("open");
+    let file = wide(&path.to_string_lossy());
+    // SAFETY: verb and file are NUL-terminated and outlive the call; null parent window,
+    // parameters and directory are allowed.
+    let result = unsafe {
+        ShellExecuteW(
+            std::ptr::null_mut(),
+            verb.as_ptr(),
+            file.as_ptr(),
+            std::ptr::null(),
+            std::ptr::null(),
+            SW_SHOWNORMAL,
+        )
+    };
+    // Values up to 32 are error codes (e.g. 31 = no app associated with the file type).
+    match result as isize {
+        code if code > 32 => Ok(()),
+        31 => Err("No app is associated with this file type.".to_string()),
+        code => Err(format!("Could not open the file (error {}).", code)),
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn open_with_default(path: &Path) -> Result<(), String> {
+    let opener = if cfg!(target_os = "macos") {
+        "open"
+    } else {
+        "xdg-open"
+    };
+    Command::new(opener)
+        .arg(path)
+        .spawn()
+        .map(|_| ())
+        .map_err(|e| format!("Could not run {}: {}", opener, e))
+}
+
+/// Checks that app_path names an existing file, so a bad setting is caught when it is saved.
+fn validate_app_path(app_path: &str) -> Result<(), String> {
+    let p = Path::new(app_path);
+    if !p.is_absolute() || !p.is_file() {
+        return Err(format!("Program not found: {}", app_path));
+    }
+    Ok(())
+}
+
+/// Returns the configured program for kind, or None when the OS default is used.
+#[tauri::command]
+pub fn get_external_app(
+    kind: MediaKind,
+    state: State<PersistenceState>,
+) -> Result<Option<String>, String> {
+    Ok(state.get(kind.key())?.filter(|v| !v.is_empty()))
+}
+
+/// Sets the program used for kind. None or an empty string goes back to the OS default.
+#[tauri::command]
+pub fn set_external_app(
+    kind: MediaKind,
+    app_path: Option<String>,
+    state: State<PersistenceState>,
+) -> Result<(), String> {
+    let app_path = app_path.map(|p| p.trim().to_string()).unwrap_or_default();
+    if !app_path.is_empty() {
+        validate_app_path(&app_path)?;
+    }
+    state.set(kind.key(), &app_path)
+}
+
+/// Opens path in the program configured for kind, passing the path as the only argument,
+/// or with the OS default handler when none is set. Errors if the configured program is gone.
+#[tauri::command]
+pub fn open_in_external_app(
+    path: String,
+    kind: MediaKind,
+    state: State<PersistenceState>,
+) -> Result<(), String> {
+    let path = normalize_path(&path)?;
+    let app_path = state.get(kind.key())?.filter(|v| !v.is_empty());
+    let Some(app_path) = app_path else {
+        return open_with_default(&path);
+    };
+    validate_app_path(&app_path).map_err(|_| {
+        format!(
+            "The external {} app ({}) was not found. Choose another one or clear the setting.",
+            kind.label(),
+            app_path
+        )
+    })?;
+    Command::new(&app_path)
+        .arg(&path)
+        .spawn()
+        .map(|_| ())
+        .map_err(|e| format!("Could not start {}: {}", app_path, e))
+}
@@ -0,0 +1,166 @@
+// Author: Viorel LUPU
+// Date: 2026-10-14
+// Purpose: Find the ffmpeg / ffprobe binaries. Order: FFMPEG_PATH / FFPROBE_PATH env var,
+// then a copy bundled with the app (Tauri resource dir or next to the executable), then PATH,
+// so video thumbnails work for users who never installed ffmpeg globally. The result is
+// cached, since every thumbnail and probe asks and a PATH search stats a file per folder.
+
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::sync::{OnceLock, RwLock};
+use tauri::{AppHandle, Manager};
+
+/// Tauri resource dir, recorded at startup (helpers that run ffmpeg have no AppHandle).
+static RESOURCE_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+/// ffmpeg and ffprobe as last located; None until first use or after
+/// invalidate_ffmpeg_locations.
+static LOCATED: RwLock<Option<LocatedTools>> = RwLock::new(None);
+
+#[derive(Clone)]
+struct LocatedTools {
+    ffmpeg: Option<(PathBuf, ToolSource)>,
+    ffprobe: Option<(PathBuf, ToolSource)>,
+}
+
+impl LocatedTools {
+    /// False once a binary found earlier has been removed, so the search runs again.
+    fn still_present(&self) -> bool {
+        [&self.ffmpeg, &self.ffprobe]
+            .iter()
+            .all(|tool| tool.as_ref().is_none_or(|(path, _)| path.is_file()))
+    }
+}
+
+/// Where a tool was found.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ToolSource {
+    EnvVar,
+    Bundled,
+    Path,
+}
+
+/// Purpose: Result of get_ffmpeg_status.
+/// Usage: Settings/help shows where ffmpeg came from, or how to install it when not found.
+/// Reason for choice: Source is reported (not just found) so "works in the terminal but
+/// not in the app" reports can be told apart from a missing install.
+#[derive(Debug, Serialize)]
+pub struct FfmpegStatus {
+    pub ffmpeg_found: bool,
+    pub ffmpeg_source: Option<ToolSource>,
+    pub ffmpeg_path: Option<String>,
+    pub ffprobe_found: bool,
+    pub ffprobe_source: Option<ToolSource>,
+    pub ffprobe_path: Option<String>,
+}
+
+/// Records the app's resource dir so bundled binaries can be found; called once from setup.
+pub fn init_ffmpeg_locations(app: &AppHandle) {
+    if let Ok(dir) = app.path().resource_dir() {
+        let _ = RESOURCE_DIR.set(dir);
+    }
+    invalidate_ffmpeg_locations();
+}
+
+/// Drops the cached locations so the next lookup searches again, e.g. when the user asks
+/// for the status after installing ffmpeg.
+pub fn invalidate_ffmpeg_locations() {
+    if let Ok(mut located) = LOCATED.write() {
+        *located = None;
+    }
+}
+
+/// Both tools from the cache, searching first when it is empty or stale.
+fn located_tools() -> LocatedTools {
+    if let Ok(located) = LOCATED.read() {
+        if let Some(tools) = located.as_ref().filter(|t| t.still_present()) {
+            return tools.clone();
+        }
+    }
+    let tools = LocatedTools {
+        ffmpeg: locate("ffmpeg", "FFMPEG_PATH"),
+        ffprobe: locate("ffprobe", "FFPROBE_PATH"),
+    };
+    if let Ok(mut located) = LOCATED.write() {
+        *located = Some(tools.clone());
+    }
+    tools
+}
+
+/// program with the platform's executable suffix (ffmpeg.exe on Windows).
+fn executable_name(program: &str) -> String {
+    format!("{}{}", program, std::env::consts::EXE_SUFFIX)
+}
+
+/// program inside one of dirs, if it exists there.
+fn find_in(dirs: impl IntoIterator<Item = PathBuf>, program: &str) -> Option<PathBuf> {
+    let name = executable_name(program);
+    dirs.into_iter()
+        .map(|dir| dir.join(&name))
+        .find(|candidate| candidate.is_file())
+}
+
+/// Folders a bundled copy may be in: the resource dir (and its bin/), then the executable's
+/// own folder.
+fn bundled_dirs() -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    if let Some(dir) = RESOURCE_DIR.get() {
+        dirs.push(dir.clone());
+        dirs.push(dir.join("bin"));
+    }
+    if let Some(dir) = std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(Path::to_path_buf))
+    {
+        dirs.push(dir);
+    }
+    dirs
+}
+
+/// Resolves program via env_var (a path to the binary), the bundled folders, then PATH.
+fn locate(program: &str, env_var: &str) -> Option<(PathBuf, ToolSource)> {
+    if let Some(path) = std::env::var_os(env_var).map(PathBuf::from) {
+        if path.is_file() {
+            return Some((path, ToolSource::EnvVar));
+        }
+    }
+    if let Some(path) = find_in(bundled_dirs(), program) {
+        return Some((path, ToolSource::Bundled));
+    }
+    let path_var = std::env::var_os("PATH")?;
+    find_in(std::env::split_paths(&path_var), program).map(|p| (p, ToolSource::Path))
+}
+
+/// Path to the ffmpeg binary to run, or None if it can't be found anywhere.
+pub fn resolve_ffmpeg() -> Option<PathBuf> {
+    located_tools().ffmpeg.map(|(path, _)| path)
+}
+
+/// Path to the ffprobe binary to run, or None if it can't be found anywhere.
+pub fn resolve_ffprobe() -> Option<PathBuf> {
+    located_tools().ffprobe.map(|(path, _)| path)
+}
+
+/// Paths to ffmpeg and ffprobe from a single lookup, or None for a tool that can't be found.
+pub fn resolve_ffmpeg_tools() -> (Option<PathBuf>, Option<PathBuf>) {
+    let LocatedTools { ffmpeg, ffprobe } = located_tools();
+    (ffmpeg.map(|(path, _)| path), ffprobe.map(|(path, _)| path))
+}
+
+/// Reports whether ffmpeg and ffprobe were found and where (env var, bundled, or PATH).
+/// Searches afresh, so a copy installed while the app runs is picked up from here on.
+#[tauri::command]
+pub fn get_ffmpeg_status() -> FfmpegStatus {
+    invalidate_ffmpeg_locations();
+    let LocatedTools { ffmpeg, ffprobe } = located_tools();
+    let path_string = |p: &PathBuf| p.to_string_lossy().into_owned();
+    FfmpegStatus {
+        ffmpeg_found: ffmpeg.is_some(),
+        ffmpeg_source: ffmpeg.as_ref().map(|(_, s)| *s),
+        ffmpeg_path: ffmpeg.as_ref().map(|(p, _)| path_string(p)),
+        ffprobe_found: ffprobe.is_some(),
+        ffprobe_source: ffprobe.as_ref().map(|(_, s)| *s),
+        ffprobe_path: ffprobe.as_ref().map(|(p, _)| path_string(p)),
+    }
+}
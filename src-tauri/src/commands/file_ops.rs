@@ -0,0 +1,596 @@
+// Author: Viorel LUPU
+// Date: 2026-10-14
+// Purpose: File management for the content pane: send entries to the Recycle Bin, move them,
+// copy them to another folder, rename them, or create a new folder. Batch commands run the
+// single-item helpers for each path and report a result per path, so one locked or missing
+// file doesn't abort the rest of the selection. Copies are chunked so large files report progress and can be cancelled.
+
+use super::cancel::{CancelToken, CancellationState};
+use super::fs::{absolute_path, friendly_error, normalize_path};
+use serde::Serialize;
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager};
+
+/// Purpose: Outcome for one path of a batch delete or move.
+/// Usage: Returned in input order; the UI lists the entries with ok == false.
+/// Reason for choice: Partial failures are normal (file in use, no permission), so errors are
+/// reported per path instead of failing the whole command.
+#[derive(Debug, Serialize)]
+pub struct PathOpResult {
+    pub path: String,
+    pub ok: bool,
+    pub error: Option<String>,
+}
+
+impl PathOpResult {
+    fn from_result(path: String, result: Result<(), String>) -> Self {
+        match result {
+            Ok(()) => PathOpResult {
+                path,
+                ok: true,
+                error: None,
+            },
+            Err(e) => PathOpResult {
+                path,
+                ok: false,
+                error: Some(e),
+            },
+        }
+    }
+}
+
+/// Moves path to the Recycle Bin via SHFileOperationW with FOF_ALLOWUNDO.
+#[cfg(target_os = "windows")]
+pub fn recycle_path(path: &Path) -> Result<(), String> {
+    use windows_sys::Win32::UI::Shell::{
+        SHFileOperationW, FOF_ALLOWUNDO, FOF_NOCONFIRMATION, FOF_NOERRORUI, FOF_SILENT,
+        FOF_WANTNUKEWARNING, FO_DELETE, SHFILEOPSTRUCTW,
+    };
+
+    // pFrom is a list of NUL-terminated names ending with an extra NUL.
+    let from: Vec<u16> = path
+        .as_os_str()
+        .to_string_lossy()
+        .encode_utf16()
+        .chain([0, 0])
+        .collect();
+    // FOF_WANTNUKEWARNING: ask before deleting permanently when the drive has no Recycle Bin
+    // (network shares, some USB sticks) instead of silently skipping the bin.
+    let flags =
+        FOF_ALLOWUNDO | FOF_NOCONFIRMATION | FOF_NOERRORUI | FOF_SILENT | FOF_WANTNUKEWARNING;
+    let mut op = SHFILEOPSTRUCTW {
+        wFunc: FO_DELETE,
+        pFrom: from.as_ptr(),
+        fFlags: flags as u16,
+        ..Default::default()
+    };
+    // SAFETY: op is fully initialised and from outlives the call.
+    let code = unsafe { SHFileOperationW(&mut op) };
+    if code != 0 {
+        return Err(format!("Could not move to Recycle Bin (error {}).", code));
+    }
+    if op.fAnyOperationsAborted != 0 {
+        return Err("Cancelled.".to_string());
+    }
+    Ok(())
+}
+
+/// Other platforms have no Recycle Bin here: the shell API above is used instead of the
+/// trash crate, and refusing is safer than deleting permanently.
+#[cfg(not(target_os = "windows"))]
+pub fn recycle_path(_path: &Path) -> Result<(), String> {
+    Err("Recycle Bin is only supported on Windows.".to_string())
+}
+
+/// Buffer size for the chunked copy.
+const COPY_CHUNK_SIZE: usize = 1024 * 1024;
+
+/// Minimum time between file-copy-progress events (~4 per second).
+const COPY_PROGRESS_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Payload of file-copy-progress: bytes copied so far for the copy or move of path.
+#[derive(Debug, Clone, Serialize)]
+struct FileCopyProgress {
+    path: String,
+    bytes_done: u64,
+    bytes_total: u64,
+}
+
+/// Why a copy stopped early.
+enum CopyFailure {
+    Cancelled,
+    Io(std::io::Error),
+}
+
+impl From<std::io::Error> for CopyFailure {
+    fn from(e: std::io::Error) -> Self {
+        CopyFailure::Io(e)
+    }
+}
+
+/// Byte counter for one copy (file or folder tree); emits throttled progress events and
+/// carries the cancel token checked between chunks.
+struct CopyProgress<'a> {
+    app: &'a AppHandle,
+    cancel: &'a CancelToken,
+    path: String,
+    done: u64,
+    total: u64,
+    last_emit: Option<Instant>,
+}
+
+impl CopyProgress<'_> {
+    fn advance(&mut self, bytes: u64) {
+        self.done += bytes;
+        let due = self
+            .last_emit
+            .is_none_or(|t| t.elapsed() >= COPY_PROGRESS_INTERVAL);
+        if due || self.done == self.total {
+            self.last_emit = Some(Instant::now());
+            let payload = FileCopyProgress {
+                path: self.path.clone(),
+                bytes_done: self.done,
+                bytes_total: self.total,
+            };
+            let _ = self.app.emit("file-copy-progress", payload);
+        }
+    }
+}
+
+/// Total size of a file, or of all files under a folder. Links are not followed (they count
+/// as nothing), so a junction pointing at an ancestor can't make the walk loop.
+fn tree_size(path: &Path) -> u64 {
+    match std::fs::symlink_metadata(path) {
+        Ok(meta) if meta.file_type().is_symlink() => 0,
+        Ok(meta) if meta.is_dir() => std::fs::read_dir(path)
+            .map(|read| read.flatten().map(|e| tree_size(&e.path())).sum())
+            .unwrap_or(0),
+        Ok(meta) => meta.len(),
+        Err(_) => 0,
+    }
+}
+
+/// Copies one file in COPY_CHUNK_SIZE pieces, reporting progress and checking the cancel
+/// token after every chunk.
+fn copy_file_chunked(
+    src: &Path,
+    dest: &Path,
+    progress: &mut CopyProgress,
+) -> Result<(), CopyFailure> {
+    let mut reader = File::open(src)?;
+    let mut writer = File::create(dest)?;
+    let mut buf = vec![0u8; COPY_CHUNK_SIZE];
+    loop {
+        if progress.cancel.is_cancelled() {
+            return Err(CopyFailure::Cancelled);
+        }
+        let n = match reader.read(&mut buf) {
+            Ok(0) => break,
+            Ok(n) => n,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e.into()),
+        };
+        writer.write_all(&buf[..n])?;
+        progress.advance(n as u64);
+    }
+    // Make sure the data is on the device (e.g. a USB stick) before the source is removed.
+    writer.sync_all()?;
+    if let Ok(perms) = std::fs::metadata(src).map(|m| m.permissions()) {
+        let _ = std::fs::set_permissions(dest, perms);
+    }
+    Ok(())
+}
+
+/// Recreates the link src at dest, pointing at the same target. On Windows this needs the
+/// right to create symlinks (Developer Mode or admin); without it the copy fails rather than
+/// silently dropping the link.
+fn copy_link(src: &Path, dest: &Path) -> std::io::Result<()> {
+    let target = std::fs::read_link(src)?;
+    #[cfg(target_os = "windows")]
+    {
+        if std::fs::metadata(src).is_ok_and(|m| m.is_dir()) {
+            std::os::windows::fs::symlink_dir(&target, dest)
+        } else {
+            std::os::windows::fs::symlink_file(&target, dest)
+        }
+    }
+    #[cfg(not(target_os = "windows"))]
+    {
+        std::os::unix::fs::symlink(&target, dest)
+    }
+}
+
+/// Copies a file or folder tree to dest (which must not exist). Links are copied as links,
+/// never followed, so a link to an ancestor can't recurse forever or fill the disk.
+fn copy_tree(src: &Path, dest: &Path, progress: &mut CopyProgress) -> Result<(), CopyFailure> {
+    let meta = std::fs::symlink_metadata(src)?;
+    if meta.file_type().is_symlink() {
+        copy_link(src, dest)?;
+        Ok(())
+    } else if meta.is_dir() {
+        std::fs::create_dir(dest)?;
+        for entry in std::fs::read_dir(src)? {
+            let entry = entry?;
+            copy_tree(&entry.path(), &dest.join(entry.file_name()), progress)?;
+        }
+        Ok(())
+    } else {
+        copy_file_chunked(src, dest, progress)
+    }
+}
+
+/// Deletes path for good. A link (symlink, Windows directory link or junction) is removed
+/// itself; its target is never touched.
+fn remove_entry(path: &Path) -> std::io::Result<()> {
+    let meta = std::fs::symlink_metadata(path)?;
+    if meta.file_type().is_symlink() {
+        // Windows directory links and junctions are removed like empty folders.
+        return std::fs::remove_file(path).or_else(|_| std::fs::remove_dir(path));
+    }
+    if meta.is_dir() {
+        std::fs::remove_dir_all(path)
+    } else {
+        std::fs::remove_file(path)
+    }
+}
+
+/// Destination for putting src into dest_dir under the same name; errors if that would
+/// overwrite something or put a folder inside itself.
+fn destination_in(src: &Path, dest_dir: &Path) -> Result<PathBuf, String> {
+    let name = src
+        .file_name()
+        .ok_or_else(|| "Cannot copy or move a drive root.".to_string())?;
+    // Paths are not resolved by normalize_path, so also check on the real folders. src's own
+    // last component is not resolved: a link is moved as a link, not as its target.
+    let real = |p: &Path| std::fs::canonicalize(p).unwrap_or_else(|_| p.to_path_buf());
+    let real_src = src.parent().map(|parent| real(parent).join(name));
+    if dest_dir.starts_with(src) || real_src.is_some_and(|s| real(dest_dir).starts_with(s)) {
+        return Err("Cannot put a folder inside itself.".to_string());
+    }
+    let dest = dest_dir.join(name);
+    if std::fs::symlink_metadata(&dest).is_ok() {
+        return Err(format!(
+            "\"{}\" already exists in the destination.",
+            name.to_string_lossy()
+        ));
+    }
+    Ok(dest)
+}
+
+/// Copies src into dest_dir with file-copy-progress events and returns the new path.
+/// On error or cancellation the partial copy is deleted and the source is left untouched.
+pub fn copy_path_into(
+    src: &Path,
+    dest_dir: &Path,
+    app: &AppHandle,
+    cancel: &CancelToken,
+) -> Result<PathBuf, String> {
+    let dest = destination_in(src, dest_dir)?;
+    let mut progress = CopyProgress {
+        app,
+        cancel,
+        path: src.to_string_lossy().into_owned(),
+        done: 0,
+        total: tree_size(src),
+        last_emit: None,
+    };
+    if let Err(failure) = copy_tree(src, &dest, &mut progress) {
+        let _ = remove_entry(&dest);
+        return Err(match failure {
+            CopyFailure::Cancelled => "Cancelled.".to_string(),
+            CopyFailure::Io(e) => friendly_error(&e),
+        });
+    }
+    Ok(dest)
+}
+
+/// Moves src into dest_dir under the same name and returns the new path. A plain rename when
+/// both are on one volume; across volumes (e.g. to a USB stick) a progress-reporting copy,
+/// then the source is deleted.
+pub fn move_path(
+    src: &Path,
+    dest_dir: &Path,
+    app: &AppHandle,
+    cancel: &CancelToken,
+) -> Result<PathBuf, String> {
+    let dest = destination_in(src, dest_dir)?;
+    match std::fs::rename(src, &dest) {
+        Ok(()) => Ok(dest),
+        Err(e) if e.kind() == std::io::ErrorKind::CrossesDevices => {
+            let dest = copy_path_into(src, dest_dir, app, cancel)?;
+            remove_entry(src).map_err(|e| {
+                format!(
+                    "Copied, but the original could not be removed: {}",
+                    friendly_error(&e)
+                )
+            })?;
+            Ok(dest)
+        }
+        Err(e) => Err(friendly_error(&e)),
+    }
+}
+
+/// Copies a file or folder into dest_dir and returns the new path. Emits file-copy-progress
+/// { path, bytes_done, bytes_total } a few times per second; pass operation_id to be able to
+/// stop it with cancel_operation (the partial copy is deleted).
+#[tauri::command]
+pub async fn copy_path(
+    app: AppHandle,
+    path: String,
+    dest_dir: String,
+    operation_id: Option<String>,
+) -> Result<String, String> {
+    let src = normalize_path(&path)?;
+    let dest_dir = normalize_path(&dest_dir)?;
+    if !dest_dir.is_dir() {
+        return Err("Destination is not a folder.".to_string());
+    }
+    let cancel = app.state::<CancellationState>().register(operation_id);
+    tauri::async_runtime::spawn_blocking(move || {
+        copy_path_into(&src, &dest_dir, &app, &cancel)
+            .map(|dest| dest.to_string_lossy().into_owned())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Sends each path to the Recycle Bin (links and junctions themselves, never their targets).
+/// Returns one result per path, in input order.
+#[tauri::command]
+pub async fn delete_paths_to_recycle_bin(paths: Vec<String>) -> Result<Vec<PathOpResult>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        paths
+            .into_iter()
+            .map(|path| {
+                let result = normalize_path(&path).and_then(|p| recycle_path(&p));
+                PathOpResult::from_result(path, result)
+            })
+            .collect()
+    })
+    .await
+    .map_err(|e| e.to_string())
+}
+
+/// Moves each path into dest_dir; a link is moved as a link. Returns one result per path, in
+/// input order; an invalid destination fails the whole call since no move could succeed.
+/// Moves across volumes emit file-copy-progress; cancelling operation_id stops the current
+/// copy and skips the rest.
+#[tauri::command]
+pub async fn move_paths(
+    app: AppHandle,
+    paths: Vec<String>,
+    dest_dir: String,
+    operation_id: Option<String>,
+) -> Result<Vec<PathOpResult>, String> {
+    let dest_dir = normalize_path(&dest_dir)?;
+    if !dest_dir.is_dir() {
+        return Err("Destination is not a folder.".to_string());
+    }
+    let cancel = app.state::<CancellationState>().register(operation_id);
+    tauri::async_runtime::spawn_blocking(move || {
+        paths
+            .into_iter()
+            .map(|path| {
+                let result = if cancel.is_cancelled() {
+                    Err("Cancelled.".to_string())
+                } else {
+                    normalize_path(&path)
+                        .and_then(|p| move_path(&p, &dest_dir, &app, &cancel).map(|_| ()))
+                };
+                PathOpResult::from_result(path, result)
+            })
+            .collect()
+    })
+    .await
+    .map_err(|e| e.to_string())
+}
+
+/// Characters Windows does not allow in file or folder names.
+const INVALID_NAME_CHARS: &[char] = &['<', '>', ':', '"', '/', '\\', '|', '?', '*'];
+
+/// Checks a single file or folder name typed by the user (no path separators).
+fn check_entry_name(name: &str) -> Result<(), String> {
+    let trimmed = name.trim();
+    if trimmed.is_empty() || trimmed == "." || trimmed == ".." {
+        return Err("Enter a name.".to_string());
+    }
+    if name
+        .chars()
+        .any(|c| INVALID_NAME_CHARS.contains(&c) || c.is_control())
+    {
+        return Err("Names cannot contain < > : \" / \\ | ? or *.".to_string());
+    }
+    if name.ends_with('.') || name.ends_with(' ') {
+        return Err("Names cannot end with a dot or a space.".to_string());
+    }
+    Ok(())
+}
+
+/// True when a and b name the same existing entry, e.g. a case-only rename on Windows. Only
+/// the folders are resolved: two links to one target are different entries.
+fn same_entry(a: &Path, b: &Path) -> bool {
+    let parent = |p: &Path| p.parent().and_then(|d| d.canonicalize().ok());
+    let (Some(name_a), Some(name_b)) = (a.file_name(), b.file_name()) else {
+        return false;
+    };
+    // Windows file names are case-insensitive; elsewhere "a" and "A" are two entries.
+    let same_name = name_a == name_b
+        || (cfg!(windows)
+            && name_a.to_string_lossy().to_lowercase() == name_b.to_string_lossy().to_lowercase());
+    same_name && parent(a).is_some() && parent(a) == parent(b)
+}
+
+/// Renames (or moves within one volume) from to to. An existing to is only replaced when
+/// overwrite is set; a case-only change of the same name is always allowed.
+fn rename_entry(from: &Path, to: &Path, overwrite: bool) -> Result<(), String> {
+    if std::fs::symlink_metadata(from).is_err() {
+        return Err("Path not found.".to_string());
+    }
+    if let Some(name) = to.file_name() {
+        check_entry_name(&name.to_string_lossy())?;
+    }
+    if std::fs::symlink_metadata(to).is_ok() && !overwrite && !same_entry(from, to) {
+        return Err(format!(
+            "\"{}\" already exists.",
+            to.file_name().unwrap_or_default().to_string_lossy()
+        ));
+    }
+    std::fs::rename(from, to).map_err(|e| {
+        if e.kind() == std::io::ErrorKind::CrossesDevices {
+            "Cannot rename to another drive; move it instead.".to_string()
+        } else {
+            friendly_error(&e)
+        }
+    })
+}
+
+/// Target of a rename: a bare name stays in from's folder, anything else must be an absolute
+/// path (a relative one would resolve against the app's working directory).
+fn rename_target(from: &Path, to: &str) -> Result<PathBuf, String> {
+    let to = to.trim();
+    if !to.contains(['/', '\\']) && !Path::new(to).is_absolute() {
+        check_entry_name(to)?;
+        let parent = from
+            .parent()
+            .ok_or_else(|| "Cannot rename a drive root.".to_string())?;
+        return Ok(parent.join(to));
+    }
+    absolute_path(to)
+}
+
+/// Renames a file or folder (a link is renamed itself, not its target). to is a new name in
+/// the same folder or an absolute path. Fails if to already exists unless overwrite is true.
+#[tauri::command]
+pub async fn rename_path(from: String, to: String, overwrite: Option<bool>) -> Result<(), String> {
+    let from = normalize_path(&from)?;
+    let to = rename_target(&from, &to)?;
+    tauri::async_runtime::spawn_blocking(move || {
+        rename_entry(&from, &to, overwrite.unwrap_or(false))
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Sends one file or folder to the Recycle Bin; a link or junction is removed, not its target.
+/// Windows only (see recycle_path); elsewhere it returns an error and nothing is deleted.
+#[tauri::command]
+pub async fn delete_to_trash(path: String) -> Result<(), String> {
+    let path = normalize_path(&path)?;
+    tauri::async_runtime::spawn_blocking(move || recycle_path(&path))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+/// Creates the folder name inside parent. Fails if something with that name already exists.
+#[tauri::command]
+pub async fn create_directory(parent: String, name: String) -> Result<(), String> {
+    let parent = normalize_path(&parent)?;
+    check_entry_name(&name)?;
+    if !parent.is_dir() {
+        return Err("Parent is not a folder.".to_string());
+    }
+    tauri::async_runtime::spawn_blocking(move || {
+        std::fs::create_dir(parent.join(&name)).map_err(|e| {
+            if e.kind() == std::io::ErrorKind::AlreadyExists {
+                format!("\"{}\" already exists.", name)
+            } else {
+                friendly_error(&e)
+            }
+        })
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Fresh empty folder under the system temp dir, unique per test and process.
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("v-see-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn rename_onto_existing_name_needs_overwrite() {
+        let dir = temp_dir("rename-collision");
+        let (a, b) = (dir.join("a.txt"), dir.join("b.txt"));
+        std::fs::write(&a, "new").unwrap();
+        std::fs::write(&b, "old").unwrap();
+        let err = rename_entry(&a, &b, false).unwrap_err();
+        assert_eq!(err, "\"b.txt\" already exists.");
+        assert_eq!(std::fs::read_to_string(&b).unwrap(), "old");
+        rename_entry(&a, &b, true).unwrap();
+        assert!(!a.exists());
+        assert_eq!(std::fs::read_to_string(&b).unwrap(), "new");
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn case_only_rename_is_allowed() {
+        let dir = temp_dir("rename-case");
+        let from = dir.join("photo.jpg");
+        std::fs::write(&from, "x").unwrap();
+        let to = rename_target(&from, "Photo.jpg").unwrap();
+        rename_entry(&from, &to, false).unwrap();
+        let names: Vec<String> = std::fs::read_dir(&dir)
+            .unwrap()
+            .flatten()
+            .map(|e| e.file_name().to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(names, ["Photo.jpg"]);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn rename_of_missing_source_fails() {
+        let dir = temp_dir("rename-missing");
+        let err = rename_entry(&dir.join("gone.txt"), &dir.join("new.txt"), false).unwrap_err();
+        assert_eq!(err, "Path not found.");
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn invalid_names_are_rejected() {
+        let dir = temp_dir("rename-invalid");
+        let from = dir.join("a.txt");
+        std::fs::write(&from, "x").unwrap();
+        let bad_names = ["", "  ", "..", "a?b", "a*b", "a:b", "a\u{1}b", "trailing."];
+        for bad in bad_names {
+            assert!(rename_target(&from, bad)
+                .and_then(|to| rename_entry(&from, &to, false))
+                .is_err());
+        }
+        assert_eq!(
+            rename_target(&from, "relative/b.txt").unwrap_err(),
+            "Path must be absolute."
+        );
+        assert!(from.exists());
+        let err = tauri::async_runtime::block_on(create_directory(
+            dir.to_string_lossy().into_owned(),
+            "bad|name".to_string(),
+        ))
+        .unwrap_err();
+        assert!(err.starts_with("Names cannot contain"), "{}", err);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn create_directory_refuses_existing_name() {
+        let dir = temp_dir("mkdir-existing");
+        let parent = dir.to_string_lossy().into_owned();
+        let create = |name: &str| {
+            tauri::async_runtime::block_on(create_directory(parent.clone(), name.to_string()))
+        };
+        create("Albums").unwrap();
+        assert!(dir.join("Albums").is_dir());
+        assert_eq!(create("Albums").unwrap_err(), "\"Albums\" already exists.");
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
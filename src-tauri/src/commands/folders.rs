@@ -0,0 +1,116 @@
+// Author: Viorel LUPU
+// Date: 2026-10-14
+// Purpose: Recent folders (MRU) and favorite folders, each kept in the persistence store as a
+// JSON array of paths. Recents are most recent first and power the "Recent" menu; favorites
+// are pinned by the user and listed with an accessibility flag.
+
+use super::fs::is_readable_dir;
+use super::persistence::{PersistenceState, FAVORITE_FOLDERS_KEY, RECENT_FOLDERS_KEY};
+use serde::Serialize;
+use std::path::Path;
+use tauri::State;
+
+/// Max folders kept in the recent list; older entries drop off the end.
+const MAX_RECENT_FOLDERS: usize = 20;
+
+/// Moves path to the front of the recent folders list (adding it if new).
+#[tauri::command]
+pub fn push_recent_folder(path: String, state: State<PersistenceState>) -> Result<(), String> {
+    let path = path.trim().to_string();
+    if path.is_empty() {
+        return Ok(());
+    }
+    let mut list = state.get_string_list(RECENT_FOLDERS_KEY)?;
+    list.retain(|p| p != &path);
+    list.insert(0, path);
+    list.truncate(MAX_RECENT_FOLDERS);
+    state.set_string_list(RECENT_FOLDERS_KEY, &list)
+}
+
+/// Returns up to limit recent folders, most recent first, skipping folders that no longer exist.
+#[tauri::command]
+pub fn get_recent_folders(
+    limit: usize,
+    state: State<PersistenceState>,
+) -> Result<Vec<String>, String> {
+    Ok(state
+        .get_string_list(RECENT_FOLDERS_KEY)?
+        .into_iter()
+        .filter(|p| Path::new(p).is_dir())
+        .take(limit)
+        .collect())
+}
+
+/// Comparison key for de-duplicating folders: trailing separators dropped (except at a root);
+/// on Windows also separator- and case-insensitive (C:/Photos == c:\photos\).
+fn folder_key(path: &str) -> String {
+    #[cfg(target_os = "windows")]
+    let path = path.replace('/', "\\").to_lowercase();
+    #[cfg(not(target_os = "windows"))]
+    let path = path.to_string();
+    let trimmed = path.trim_end_matches(['\\', '/']);
+    if trimmed.is_empty() || trimmed.ends_with(':') {
+        path
+    } else {
+        trimmed.to_string()
+    }
+}
+
+/// Purpose: One favorite folder as returned by list_favorite_folders.
+/// Usage: UI lists favorites and greys out entries with accessible == false.
+/// Reason for choice: Accessibility is checked on each list so reconnected drives light up again.
+#[derive(Debug, Serialize)]
+pub struct FavoriteFolder {
+    pub path: String,
+    pub name: String,
+    pub accessible: bool,
+}
+
+/// Pins path as a favorite (no-op if an equivalent path is already pinned).
+#[tauri::command]
+pub fn add_favorite_folder(path: String, state: State<PersistenceState>) -> Result<(), String> {
+    let path = path.trim().to_string();
+    if path.is_empty() {
+        return Ok(());
+    }
+    let mut list = state.get_string_list(FAVORITE_FOLDERS_KEY)?;
+    let key = folder_key(&path);
+    if list.iter().any(|p| folder_key(p) == key) {
+        return Ok(());
+    }
+    list.push(path);
+    state.set_string_list(FAVORITE_FOLDERS_KEY, &list)
+}
+
+/// Unpins every favorite equivalent to path.
+#[tauri::command]
+pub fn remove_favorite_folder(path: String, state: State<PersistenceState>) -> Result<(), String> {
+    let mut list = state.get_string_list(FAVORITE_FOLDERS_KEY)?;
+    let key = folder_key(path.trim());
+    list.retain(|p| folder_key(p) != key);
+    state.set_string_list(FAVORITE_FOLDERS_KEY, &list)
+}
+
+/// Returns favorites in the order added, each flagged with whether it can be read right now.
+#[tauri::command]
+pub fn list_favorite_folders(
+    state: State<PersistenceState>,
+) -> Result<Vec<FavoriteFolder>, String> {
+    Ok(state
+        .get_string_list(FAVORITE_FOLDERS_KEY)?
+        .into_iter()
+        .map(|path| {
+            let p = Path::new(&path);
+            let name = p
+                .file_name()
+                .and_then(|n| n.to_str())
+                .map(String::from)
+                .unwrap_or_else(|| path.clone());
+            FavoriteFolder {
+                accessible: is_readable_dir(p),
+                name,
+                path,
+            }
+        })
+        .collect())
+}
@@ -0,0 +1,102 @@
+// Author: Viorel LUPU
+// Date: 2026-10-14
+// Purpose: Single table of supported image, audio, and video formats (extension, MIME, decoder).
+// fs.rs and audio.rs look formats up here, and get_supported_formats exposes the same lists
+// to the frontend so its capability checks stay in sync with the backend.
+
+use serde::Serialize;
+
+/// Which rodio decoder try_play uses for an audio extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AudioDecoder {
+    Mp3,
+    Wav,
+    Flac,
+    Vorbis,
+    /// rodio's format-probing Decoder::new.
+    Generic,
+    /// Playable in the webview via data URL, but not by the native player.
+    Unsupported,
+}
+
+/// Images the preview can display: (extension, MIME type).
+pub const IMAGE_FORMATS: &[(&str, &str)] = &[
+    ("jpg", "image/jpeg"),
+    ("jpeg", "image/jpeg"),
+    ("png", "image/png"),
+    ("gif", "image/gif"),
+    ("webp", "image/webp"),
+    ("bmp", "image/bmp"),
+    ("tiff", "image/tiff"),
+    ("tif", "image/tiff"),
+    ("ico", "image/x-icon"),
+    ("svg", "image/svg+xml"),
+];
+
+/// Audio files listed by the player: (extension, MIME type, native decoder).
+pub const AUDIO_FORMATS: &[(&str, &str, AudioDecoder)] = &[
+    ("mp3", "audio/mpeg", AudioDecoder::Mp3),
+    ("wav", "audio/wav", AudioDecoder::Wav),
+    ("ogg", "audio/ogg", AudioDecoder::Vorbis),
+    ("m4a", "audio/mp4", AudioDecoder::Unsupported),
+    ("aac", "audio/aac", AudioDecoder::Unsupported),
+    ("flac", "audio/flac", AudioDecoder::Flac),
+    ("wma", "audio/x-ms-wma", AudioDecoder::Generic),
+    ("opus", "audio/opus", AudioDecoder::Generic),
+    ("webm", "audio/webm", AudioDecoder::Generic),
+];
+
+/// Videos shown in the grid and played by the webview.
+pub const VIDEO_EXTENSIONS: &[&str] = &["mp4", "mov", "avi", "webm", "mkv", "m4v", "wmv"];
+
+/// Files the text preview pane offers to open with read_text_file.
+pub const TEXT_EXTENSIONS: &[&str] = &[
+    "txt", "md", "log", "csv", "json", "xml", "ini", "cfg", "yaml", "yml", "toml", "html", "css",
+    "js", "ts", "rs", "py", "c", "cpp", "h", "cs", "java", "sh", "bat", "ps1",
+];
+
+/// MIME type for an image extension (lowercase), if supported.
+pub fn image_mime(ext: &str) -> Option<&'static str> {
+    IMAGE_FORMATS.iter().find(|(e, _)| *e == ext).map(|(_, m)| *m)
+}
+
+/// MIME type for an audio extension (lowercase), if supported.
+pub fn audio_mime(ext: &str) -> Option<&'static str> {
+    AUDIO_FORMATS.iter().find(|(e, _, _)| *e == ext).map(|(_, m, _)| *m)
+}
+
+/// Native decoder for an audio extension; unknown extensions are probed generically.
+pub fn audio_decoder(ext: &str) -> AudioDecoder {
+    AUDIO_FORMATS
+        .iter()
+        .find(|(e, _, _)| *e == ext)
+        .map(|(_, _, d)| *d)
+        .unwrap_or(AudioDecoder::Generic)
+}
+
+/// Result of get_supported_formats: lowercase extensions without the dot.
+/// `audio` is what the webview player accepts; `native_audio` is what play_audio can decode.
+#[derive(Debug, Serialize)]
+pub struct SupportedFormats {
+    pub image: Vec<&'static str>,
+    pub audio: Vec<&'static str>,
+    pub native_audio: Vec<&'static str>,
+    pub video: Vec<&'static str>,
+    pub text: Vec<&'static str>,
+}
+
+/// Returns the image, audio, video, and text extensions the backend supports.
+#[tauri::command]
+pub fn get_supported_formats() -> SupportedFormats {
+    SupportedFormats {
+        image: IMAGE_FORMATS.iter().map(|(e, _)| *e).collect(),
+        audio: AUDIO_FORMATS.iter().map(|(e, _, _)| *e).collect(),
+        native_audio: AUDIO_FORMATS
+            .iter()
+            .filter(|(_, _, d)| *d != AudioDecoder::Unsupported)
+            .map(|(e, _, _)| *e)
+            .collect(),
+        video: VIDEO_EXTENSIONS.to_vec(),
+        text: TEXT_EXTENSIONS.to_vec(),
+    }
+}
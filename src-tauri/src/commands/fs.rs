@@ -2,18 +2,117 @@
 // Date: 2026-02-17
 // Purpose: Tauri commands for file system operations (list directories, drive roots).
 // Used by the folder tree to lazy-load children. Handles errors for disconnected drives.
+// Recursive walks read DirEntry metadata, which never follows links, so a junction to an
+// ancestor can't loop; only scan_media_recursive enters linked folders, on request and with a
+// visited-folder guard. (file_ops' tree_size/copy_tree use symlink_metadata for the same reason.)
 
+use super::cancel::{CancelToken, CancellationState};
+use super::formats::{audio_decoder, audio_mime, image_mime, AudioDecoder, VIDEO_EXTENSIONS};
+use super::heif_decode::heif_to_jpeg;
+use super::svg_render::{rasterize_svg, DEFAULT_SVG_RASTER_EDGE};
 use base64::{engine::general_purpose::STANDARD, Engine};
+use image::metadata::Orientation;
+use image::ImageDecoder;
 use serde::Serialize;
-use std::path::PathBuf;
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::io::{BufRead, Seek};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, UNIX_EPOCH};
+use tauri::{AppHandle, Emitter, Manager};
 
 /// Single entry returned from list_directory (file or directory).
 /// Frontend uses this for the tree; only directories are shown as expandable.
-#[derive(Debug, Serialize)]
+/// The media counts are set for directories when list_directory is called with include_counts.
+/// size is 0 for directories; modified is Unix millis, None where the platform can't tell.
+/// is_symlink marks symlinks and junctions; is_dir, size and modified describe their target.
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct DirEntry {
     pub name: String,
     pub path: String,
     pub is_dir: bool,
+    pub is_symlink: bool,
+    pub size: u64,
+    pub modified: Option<u64>,
+    pub image_count: Option<u32>,
+    pub video_count: Option<u32>,
+    pub audio_count: Option<u32>,
+}
+
+/// Image, video and audio files directly inside a folder.
+#[derive(Debug, Clone, Copy, Default)]
+struct MediaCounts {
+    images: u32,
+    videos: u32,
+    audio: u32,
+}
+
+/// Media category of a file, by extension, as counted and filtered by the folder scans.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ScanKind {
+    Image,
+    Video,
+    Audio,
+}
+
+impl ScanKind {
+    /// Parses a kind name as used by scan_media_recursive ("image", "video", "audio").
+    fn parse(value: &str) -> Result<Self, String> {
+        match value.trim().to_lowercase().as_str() {
+            "image" => Ok(ScanKind::Image),
+            "video" => Ok(ScanKind::Video),
+            "audio" => Ok(ScanKind::Audio),
+            other => Err(format!(
+                "Unknown media kind \"{}\" (use image, video or audio).",
+                other
+            )),
+        }
+    }
+
+    /// Kind of the file named name, using the same extension sets as get_supported_formats.
+    fn of_name(name: &str) -> Option<Self> {
+        let ext = Path::new(name)
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or("")
+            .to_lowercase();
+        if image_mime(&ext).is_some() {
+            Some(ScanKind::Image)
+        } else if VIDEO_EXTENSIONS.contains(&ext.as_str()) {
+            Some(ScanKind::Video)
+        } else if audio_mime(&ext).is_some() {
+            Some(ScanKind::Audio)
+        } else {
+            None
+        }
+    }
+}
+
+/// Counts the media files directly inside dir (one level, not recursive), using the same
+/// extension sets as get_supported_formats. An unreadable folder (e.g. drive unplugged while
+/// counting) counts as zero instead of failing the listing.
+fn count_media(dir: &Path, show_hidden: bool) -> MediaCounts {
+    let mut counts = MediaCounts::default();
+    let Ok(read) = std::fs::read_dir(dir) else {
+        return counts;
+    };
+    for entry in read.flatten() {
+        let Ok(meta) = entry.metadata() else {
+            continue;
+        };
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if meta.is_dir() || (!show_hidden && is_hidden_entry(&name, &meta)) {
+            continue;
+        }
+        match ScanKind::of_name(&name) {
+            Some(ScanKind::Image) => counts.images += 1,
+            Some(ScanKind::Video) => counts.videos += 1,
+            Some(ScanKind::Audio) => counts.audio += 1,
+            None => {}
+        }
+    }
+    counts
 }
 
 /// Result of list_directory. Returns entries or an error message for the UI.
@@ -25,7 +124,7 @@ pub struct ListDirResult {
 }
 
 /// Maps OS errors to short, user-friendly messages (e.g. disconnected drive).
-fn friendly_error(e: &std::io::Error) -> String {
+pub fn friendly_error(e: &std::io::Error) -> String {
     let msg = e.to_string();
     let lower = msg.to_lowercase();
     if lower.contains("not ready") || lower.contains("device is not ready") {
@@ -34,6 +133,9 @@ fn friendly_error(e: &std::io::Error) -> String {
     if lower.contains("access is denied") || lower.contains("permission denied") {
         return "Access denied.".to_string();
     }
+    if lower.contains("not enough space") || lower.contains("no space left") {
+        return "Not enough free space on the destination drive.".to_string();
+    }
     if lower.contains("path not found") || lower.contains("no such file") || lower.contains("the system cannot find") {
         return "Path not found (drive may have been disconnected).".to_string();
     }
@@ -43,36 +145,193 @@ fn friendly_error(e: &std::io::Error) -> String {
     msg
 }
 
-/// Lists direct children of the given path (directories only for folder tree).
-/// Sorted by name (case-insensitive). Returns error if path is invalid or inaccessible.
-/// Defensive: external drives can be disconnected at any time; errors are returned cleanly.
-#[tauri::command]
-pub fn list_directory(path: String) -> ListDirResult {
-    let path_buf = PathBuf::from(&path);
-    if !path_buf.is_dir() {
-        return ListDirResult {
-            ok: false,
-            entries: None,
-            error: Some("Path is not a directory.".to_string()),
+/// Makes a frontend path absolute without touching the file system: empty and relative
+/// paths are rejected (so nothing resolves against the app's working directory) and `.`/`..`
+/// are resolved lexically. Links, mapped and subst drives are kept as typed, so returned and
+/// emitted paths match what the UI navigated to. UNC (\\server\share) and long-path (\\?\)
+/// inputs are accepted on Windows. The path does not have to exist.
+pub fn absolute_path(path: &str) -> Result<PathBuf, String> {
+    let trimmed = path.trim();
+    if trimmed.is_empty() {
+        return Err("Path is empty.".to_string());
+    }
+    let p = Path::new(trimmed);
+    if !p.is_absolute() {
+        return Err("Path must be absolute.".to_string());
+    }
+    let abs = std::path::absolute(p).map_err(|e| friendly_error(&e))?;
+    let mut clean = PathBuf::new();
+    for comp in abs.components() {
+        match comp {
+            std::path::Component::CurDir => {}
+            std::path::Component::ParentDir => {
+                clean.pop();
+            }
+            other => clean.push(other),
+        }
+    }
+    Ok(clean)
+}
+
+/// Validates a path coming from the frontend (see absolute_path) and checks that it exists.
+/// A link counts as existing even when broken, and is not resolved: operations act on the
+/// link itself. Errors use the same wording as listings. Use std::fs::canonicalize only where
+/// two paths must be compared for identity.
+pub fn normalize_path(path: &str) -> Result<PathBuf, String> {
+    let p = absolute_path(path)?;
+    std::fs::symlink_metadata(&p).map_err(|e| friendly_error(&e))?;
+    Ok(p)
+}
+
+/// Windows file attribute bits for hidden and system entries.
+#[cfg(target_os = "windows")]
+const FILE_ATTRIBUTE_HIDDEN: u32 = 0x2;
+#[cfg(target_os = "windows")]
+const FILE_ATTRIBUTE_SYSTEM: u32 = 0x4;
+
+/// True if the entry should be hidden by default: HIDDEN or SYSTEM attribute on Windows,
+/// dotfiles elsewhere. Based on metadata so it applies the same way to any listing.
+#[cfg(target_os = "windows")]
+pub fn is_hidden_entry(_name: &str, meta: &std::fs::Metadata) -> bool {
+    use std::os::windows::fs::MetadataExt;
+    meta.file_attributes() & (FILE_ATTRIBUTE_HIDDEN | FILE_ATTRIBUTE_SYSTEM) != 0
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn is_hidden_entry(name: &str, _meta: &std::fs::Metadata) -> bool {
+    name.starts_with('.')
+}
+
+/// Takes the run of ASCII digits at the front of chars.
+fn take_digits(chars: &mut std::iter::Peekable<std::str::Chars>) -> String {
+    let mut run = String::new();
+    while let Some(c) = chars.next_if(|c| c.is_ascii_digit()) {
+        run.push(c);
+    }
+    run
+}
+
+/// Explorer-style name order: case-insensitive, with digit runs compared by numeric value
+/// ("img2" < "img10"). Equal values with different zero padding put the shorter run first.
+pub fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let (mut ai, mut bi) = (a.chars().peekable(), b.chars().peekable());
+    loop {
+        let (x, y) = match (ai.peek(), bi.peek()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(&x), Some(&y)) => (x, y),
+        };
+        let ord = if x.is_ascii_digit() && y.is_ascii_digit() {
+            let (na, nb) = (take_digits(&mut ai), take_digits(&mut bi));
+            let (ta, tb) = (na.trim_start_matches('0'), nb.trim_start_matches('0'));
+            // Compare by length first so arbitrarily long runs never overflow.
+            ta.len()
+                .cmp(&tb.len())
+                .then_with(|| ta.cmp(tb))
+                .then_with(|| na.len().cmp(&nb.len()))
+        } else {
+            ai.next();
+            bi.next();
+            x.to_lowercase().cmp(y.to_lowercase())
         };
+        if ord != Ordering::Equal {
+            return ord;
+        }
     }
-    let read = match std::fs::read_dir(&path_buf) {
-        Ok(r) => r,
-        Err(e) => {
-            return ListDirResult {
-                ok: false,
-                entries: None,
-                error: Some(friendly_error(&e)),
-            };
+}
+
+/// Order of list_directory results.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DirSort {
+    /// Case-insensitive lexicographic ("img10" before "img2").
+    Name,
+    /// Explorer-style, see natural_cmp.
+    Natural,
+    /// Newest first.
+    Modified,
+    /// Folders first (natural order), then files largest first.
+    Size,
+}
+
+impl DirSort {
+    /// Parses the sort parameter of list_directory.
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value.trim().to_lowercase().as_str() {
+            "name" => Ok(DirSort::Name),
+            "natural" => Ok(DirSort::Natural),
+            "modified" => Ok(DirSort::Modified),
+            "size" => Ok(DirSort::Size),
+            other => Err(format!(
+                "Unknown sort \"{}\" (use name, natural, modified or size).",
+                other
+            )),
         }
-    };
+    }
+}
+
+/// Unix millis of the entry's last modification, if available.
+fn modified_millis(meta: &std::fs::Metadata) -> Option<u64> {
+    meta.modified()
+        .ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_millis() as u64)
+}
+
+/// Creation time as Unix millis, None where the platform or file system doesn't record it.
+fn created_millis(meta: &std::fs::Metadata) -> Option<u64> {
+    meta.created()
+        .ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_millis() as u64)
+}
+
+/// Natural order with the exact name as tie-break, so names differing only in case keep a
+/// fixed order.
+fn natural_name_cmp(a: &DirEntry, b: &DirEntry) -> Ordering {
+    natural_cmp(&a.name, &b.name).then_with(|| a.name.cmp(&b.name))
+}
+
+/// Sorts entries in place; ties in the modified and size orders fall back to natural order.
+fn sort_entries(entries: &mut [DirEntry], sort: DirSort) {
+    match sort {
+        DirSort::Name => entries.sort_by_key(|a| a.name.to_lowercase()),
+        DirSort::Natural => entries.sort_by(natural_name_cmp),
+        DirSort::Modified => entries.sort_by(|a, b| {
+            b.modified
+                .cmp(&a.modified)
+                .then_with(|| natural_name_cmp(a, b))
+        }),
+        DirSort::Size => entries.sort_by(|a, b| {
+            b.is_dir
+                .cmp(&a.is_dir)
+                .then_with(|| b.size.cmp(&a.size))
+                .then_with(|| natural_name_cmp(a, b))
+        }),
+    }
+}
+
+/// For a symlink or junction, the metadata of its target (so a linked folder lists as a folder)
+/// and true; a broken link keeps its own metadata. Other entries are returned unchanged.
+/// DirEntry::metadata never follows links, which is what keeps the recursive walks below from
+/// looping through a link to an ancestor unless they opt in.
+fn follow_link(path: &Path, meta: std::fs::Metadata) -> (std::fs::Metadata, bool) {
+    if !meta.file_type().is_symlink() {
+        return (meta, false);
+    }
+    (std::fs::metadata(path).unwrap_or(meta), true)
+}
+
+/// Reads the direct children of a directory (unsorted, no media counts), skipping hidden/system
+/// entries unless show_hidden. Entries whose metadata can't be read are left out.
+fn read_entries(path: &Path, show_hidden: bool) -> Result<Vec<DirEntry>, String> {
+    let read = std::fs::read_dir(path).map_err(|e| friendly_error(&e))?;
     let mut entries: Vec<DirEntry> = Vec::new();
     for entry in read.flatten() {
         let meta = match entry.metadata() {
             Ok(m) => m,
             Err(_) => continue,
         };
-        let is_dir = meta.is_dir();
         let path_buf = entry.path();
         let path_str = path_buf.to_string_lossy().into_owned();
         let name = path_buf
@@ -80,20 +339,164 @@ pub fn list_directory(path: String) -> ListDirResult {
             .and_then(|n| n.to_str())
             .unwrap_or("")
             .to_string();
+        if !show_hidden && is_hidden_entry(&name, &meta) {
+            continue;
+        }
+        let (meta, is_symlink) = follow_link(&path_buf, meta);
+        let is_dir = meta.is_dir();
         entries.push(DirEntry {
             name,
             path: path_str,
             is_dir,
+            is_symlink,
+            size: if is_dir { 0 } else { meta.len() },
+            modified: modified_millis(&meta),
+            ..Default::default()
         });
     }
-    entries.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
-    ListDirResult {
-        ok: true,
-        entries: Some(entries),
-        error: None,
+    Ok(entries)
+}
+
+/// Fills image_count / video_count / audio_count for every directory in entries.
+fn add_media_counts(entries: &mut [DirEntry], show_hidden: bool) {
+    for entry in entries.iter_mut().filter(|e| e.is_dir) {
+        let counts = count_media(Path::new(&entry.path), show_hidden);
+        entry.image_count = Some(counts.images);
+        entry.video_count = Some(counts.videos);
+        entry.audio_count = Some(counts.audio);
+    }
+}
+
+/// Parses an optional sort parameter; None means name order.
+fn resolve_sort(sort: Option<&str>) -> Result<DirSort, String> {
+    sort.map_or(Ok(DirSort::Name), DirSort::parse)
+}
+
+/// Validates path and returns its children in the requested order.
+fn sorted_entries(path: &str, show_hidden: bool, sort: DirSort) -> Result<Vec<DirEntry>, String> {
+    let path_buf = normalize_path(path)?;
+    if !path_buf.is_dir() {
+        return Err("Path is not a directory.".to_string());
+    }
+    let mut entries = read_entries(&path_buf, show_hidden)?;
+    sort_entries(&mut entries, sort);
+    Ok(entries)
+}
+
+/// Compiles list_directory's filter into a case-insensitive file-name matcher. A pattern
+/// without wildcards matches names containing it ("vacation" = "*vacation*"); blank means no
+/// filter.
+fn name_filter(filter: Option<&str>) -> Result<Option<globset::GlobMatcher>, String> {
+    let pattern = match filter.map(str::trim) {
+        Some(p) if !p.is_empty() => p,
+        _ => return Ok(None),
+    };
+    let pattern = if pattern.contains(['*', '?', '[', '{']) {
+        pattern.to_string()
+    } else {
+        format!("*{}*", pattern)
+    };
+    let matcher = globset::GlobBuilder::new(&pattern)
+        .case_insensitive(true)
+        .literal_separator(true)
+        .build()
+        .map_err(|e| format!("Invalid filter: {}", e))?
+        .compile_matcher();
+    Ok(Some(matcher))
+}
+
+/// Lists direct children of the given path (directories only for folder tree).
+/// sort is "name" (case-insensitive), "natural" (numbered names by value, like Explorer),
+/// "modified" (newest first) or "size" (folders first, then largest files first); default
+/// "name". Returns error if path is invalid or inaccessible, or sort is unknown.
+/// Hidden/system entries are skipped unless show_hidden is true (default false, like Explorer).
+/// include_counts (default false) fills image_count / video_count / audio_count for each
+/// subfolder from its direct children; this reads every subfolder, so only the tree asks for it.
+/// filter keeps only files whose name matches a glob (`*`, `?`; case-insensitive, e.g. "*.cr2",
+/// "IMG_??.jpg") or contains the text when it has no wildcards. Folders are always kept so the
+/// tree still works. Empty or absent = no filter; an invalid pattern is reported in error.
+/// Defensive: external drives can be disconnected at any time; errors are returned cleanly.
+#[tauri::command]
+pub async fn list_directory(
+    path: String,
+    show_hidden: Option<bool>,
+    include_counts: Option<bool>,
+    sort: Option<String>,
+    filter: Option<String>,
+) -> ListDirResult {
+    let show_hidden = show_hidden.unwrap_or(false);
+    let listed = tauri::async_runtime::spawn_blocking(move || {
+        let matcher = name_filter(filter.as_deref())?;
+        let order = resolve_sort(sort.as_deref())?;
+        let mut entries = sorted_entries(&path, show_hidden, order)?;
+        if let Some(matcher) = matcher {
+            entries.retain(|e| e.is_dir || matcher.is_match(&e.name));
+        }
+        if include_counts.unwrap_or(false) {
+            add_media_counts(&mut entries, show_hidden);
+        }
+        Ok::<_, String>(entries)
+    })
+    .await
+    .map_err(|e| e.to_string())
+    .and_then(|listed| listed);
+    match listed {
+        Ok(entries) => ListDirResult {
+            ok: true,
+            entries: Some(entries),
+            error: None,
+        },
+        Err(e) => ListDirResult {
+            ok: false,
+            entries: None,
+            error: Some(e),
+        },
     }
 }
 
+/// Result of list_directory_page: one slice of the sorted listing plus the full entry count.
+#[derive(Debug, Serialize)]
+pub struct DirPage {
+    pub entries: Vec<DirEntry>,
+    pub total: usize,
+    pub has_more: bool,
+}
+
+/// Lists limit entries of path starting at offset, so huge folders can be rendered lazily.
+/// The whole folder is read and sorted (same sort values as list_directory, default
+/// name) before slicing, so pages stay consistent across calls. An offset past the end
+/// gives an empty page with has_more false; total is always the real number of entries.
+/// include_counts fills the media counts for the folders on this page only.
+#[tauri::command]
+pub async fn list_directory_page(
+    path: String,
+    offset: usize,
+    limit: usize,
+    sort: Option<String>,
+    show_hidden: Option<bool>,
+    include_counts: Option<bool>,
+) -> Result<DirPage, String> {
+    let show_hidden = show_hidden.unwrap_or(false);
+    let order = resolve_sort(sort.as_deref())?;
+    tauri::async_runtime::spawn_blocking(move || {
+        let entries = sorted_entries(&path, show_hidden, order)?;
+        let total = entries.len();
+        let start = offset.min(total);
+        let end = start.saturating_add(limit).min(total);
+        let mut page: Vec<DirEntry> = entries.into_iter().skip(start).take(end - start).collect();
+        if include_counts.unwrap_or(false) {
+            add_media_counts(&mut page, show_hidden);
+        }
+        Ok(DirPage {
+            entries: page,
+            total,
+            has_more: end < total,
+        })
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
 /// Returns currently accessible drive roots (e.g. C:\, D:\, E:\) for the folder tree.
 /// Only includes drives that can be read (avoids showing disconnected/external that are "not ready").
 /// Call again (e.g. Refresh) to pick up newly connected external devices.
@@ -106,14 +509,13 @@ pub fn get_folder_roots() -> ListDirResult {
             let root = format!("{}:\\", letter as char);
             let path_buf = PathBuf::from(&root);
             // Only include drives we can actually read (defensive: skip disconnected/external not ready)
-            if path_buf.is_dir() {
-                if std::fs::read_dir(&path_buf).is_ok() {
-                    entries.push(DirEntry {
-                        name: root.clone(),
-                        path: root,
-                        is_dir: true,
-                    });
-                }
+            if is_readable_dir(&path_buf) {
+                entries.push(DirEntry {
+                    name: root.clone(),
+                    path: root,
+                    is_dir: true,
+                    ..Default::default()
+                });
             }
         }
         ListDirResult {
@@ -135,6 +537,7 @@ pub fn get_folder_roots() -> ListDirResult {
                     name,
                     path: path_str,
                     is_dir: true,
+                    ..Default::default()
                 }]
             })
             .unwrap_or_default();
@@ -146,10 +549,60 @@ pub fn get_folder_roots() -> ListDirResult {
     }
 }
 
+/// True if path is a directory whose entries can be listed (false for disconnected drives,
+/// unready removable media, or folders without read permission).
+pub fn is_readable_dir(path: &Path) -> bool {
+    path.is_dir() && std::fs::read_dir(path).is_ok()
+}
+
+/// How long path_accessible waits for the metadata read before treating the path as gone.
+const PATH_CHECK_TIMEOUT: Duration = Duration::from_millis(1500);
+
+/// Metadata reads still running, by path; the value turns Some once the read returns.
+/// Callers asking about a path already being checked wait on the same read.
+type PathCheck = tokio::sync::watch::Receiver<Option<bool>>;
+static PENDING_PATH_CHECKS: Mutex<Vec<(PathBuf, PathCheck)>> = Mutex::new(Vec::new());
+
+/// The in-flight check for path, starting one if there is none. The read runs on its own
+/// thread because a hung network share can block it indefinitely; deduplicating keeps repeated
+/// polls of the same dead drive down to one parked thread.
+fn path_check(path: PathBuf) -> Option<PathCheck> {
+    let mut pending = PENDING_PATH_CHECKS.lock().ok()?;
+    if let Some((_, rx)) = pending.iter().find(|(p, _)| *p == path) {
+        return Some(rx.clone());
+    }
+    let (tx, rx) = tokio::sync::watch::channel(None);
+    pending.push((path.clone(), rx.clone()));
+    std::thread::spawn(move || {
+        let ok = std::fs::metadata(&path).is_ok();
+        if let Ok(mut pending) = PENDING_PATH_CHECKS.lock() {
+            pending.retain(|(p, _)| *p != path);
+        }
+        let _ = tx.send(Some(ok));
+    });
+    Some(rx)
+}
+
+/// True if path can still be reached (single metadata read, no listing). A hung network
+/// share yields false after PATH_CHECK_TIMEOUT instead of blocking; the frontend uses this to
+/// grey out tree nodes on disconnected drives.
+#[tauri::command]
+pub async fn path_accessible(path: String) -> bool {
+    let p = PathBuf::from(path.trim());
+    if !p.is_absolute() {
+        return false;
+    }
+    let Some(mut rx) = path_check(p) else {
+        return false;
+    };
+    let done = tokio::time::timeout(PATH_CHECK_TIMEOUT, rx.wait_for(Option::is_some)).await;
+    matches!(done, Ok(Ok(ok)) if *ok == Some(true))
+}
+
 /// Returns the parent path of the given path, or None if at root.
 #[tauri::command]
 pub fn get_parent_path(path: String) -> Option<String> {
-    let p = PathBuf::from(&path);
+    let p = normalize_path(&path).ok()?;
     let parent = p.parent()?;
     let parent_str = parent.to_string_lossy().into_owned();
     if parent_str.is_empty() || parent == p {
@@ -158,73 +611,1017 @@ pub fn get_parent_path(path: String) -> Option<String> {
     Some(parent_str)
 }
 
+/// Result of get_file_info. Times are Unix millis like DirEntry; extension is lowercase without
+/// the dot; mime_guess is None for folders and unrecognised files.
+#[derive(Debug, Serialize)]
+pub struct FileInfo {
+    pub name: String,
+    pub path: String,
+    pub is_dir: bool,
+    pub size: u64,
+    pub modified: Option<u64>,
+    pub created: Option<u64>,
+    pub readonly: bool,
+    pub extension: Option<String>,
+    pub mime_guess: Option<String>,
+}
+
+/// MIME type from the extension tables in formats.rs, falling back to sniffing the content
+/// (covers video and files with a wrong extension).
+fn guess_mime(path: &Path, ext: &str) -> Option<String> {
+    image_mime(ext)
+        .or_else(|| audio_mime(ext))
+        .map(str::to_string)
+        .or_else(|| {
+            infer::get_from_path(path)
+                .ok()
+                .flatten()
+                .map(|t| t.mime_type().to_string())
+        })
+}
+
+/// Metadata for a single file or folder, e.g. a path dropped onto the window. A disconnected
+/// drive or missing path returns the friendly_error message.
+#[tauri::command]
+pub async fn get_file_info(path: String) -> Result<FileInfo, String> {
+    let path_buf = normalize_path(&path)?;
+    tauri::async_runtime::spawn_blocking(move || {
+        let meta = std::fs::metadata(&path_buf).map_err(|e| friendly_error(&e))?;
+        let is_dir = meta.is_dir();
+        let extension = if is_dir {
+            None
+        } else {
+            path_buf
+                .extension()
+                .map(|e| e.to_string_lossy().to_lowercase())
+        };
+        let mime_guess = if is_dir {
+            None
+        } else {
+            guess_mime(&path_buf, extension.as_deref().unwrap_or(""))
+        };
+        Ok(FileInfo {
+            name: path_buf
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_else(|| path_buf.to_string_lossy().into_owned()),
+            path: path_buf.to_string_lossy().into_owned(),
+            is_dir,
+            size: if is_dir { 0 } else { meta.len() },
+            modified: modified_millis(&meta),
+            created: created_millis(&meta),
+            readonly: meta.permissions().readonly(),
+            extension,
+            mime_guess,
+        })
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
 /// Max size (bytes) for read_file_as_data_url to avoid loading huge files.
 const MAX_DATA_URL_SIZE: u64 = 8 * 1024 * 1024;
 
 /// Max size (bytes) for read_file_as_audio_url (audio playback via data URL when asset protocol fails).
 const MAX_AUDIO_DATA_URL_SIZE: u64 = 32 * 1024 * 1024;
 
+/// Max size (bytes) of a HEIC/HEIF file read_file_as_data_url decodes; the 8MB limit then
+/// applies to the JPEG it produces.
+const MAX_HEIF_INPUT_SIZE: u64 = 64 * 1024 * 1024;
+
+/// HEIC/HEIF are decoded to JPEG (heic feature); PDFs are refused even when the extension
+/// looks fine.
+const HEIF_MIMES: [&str; 2] = ["image/heif", "image/heic"];
+const HEIF_NOT_SUPPORTED: &str = "HEIC/HEIF is not supported";
+const PDF_MIME: &str = "application/pdf";
+const SVG_MIME: &str = "image/svg+xml";
+
+/// Picks the data URL MIME type: a sniffed image type wins (wrong or missing extension),
+/// then the extension table, then any other sniffed type.
+fn preview_mime(sniffed: Option<infer::Type>, ext: &str) -> &'static str {
+    let sniffed_image = sniffed
+        .filter(|t| t.matcher_type() == infer::MatcherType::Image)
+        .map(|t| t.mime_type());
+    sniffed_image
+        .or_else(|| image_mime(ext))
+        .or_else(|| sniffed.map(|t| t.mime_type()))
+        .unwrap_or("application/octet-stream")
+}
+
+/// Brightness offset range (fraction of full scale; 0 = unchanged) and gamma range (1 = unchanged).
+const MIN_BRIGHTNESS: f32 = -1.0;
+const MAX_BRIGHTNESS: f32 = 1.0;
+const MIN_GAMMA: f32 = 0.2;
+const MAX_GAMMA: f32 = 5.0;
+
+/// JPEG quality for re-encoded previews, adjusted or downscaled (high, since this is the
+/// full-size view).
+const PREVIEW_JPEG_QUALITY: u8 = 90;
+
+/// Decodes the image, applies gamma then a brightness offset to the colour channels, and
+/// re-encodes it (PNG if it has transparency, else JPEG). Returns (bytes, mime).
+fn adjust_image(
+    bytes: &[u8],
+    brightness: f32,
+    gamma: f32,
+) -> Result<(Vec<u8>, &'static str), String> {
+    let img = image::load_from_memory(bytes).map_err(|e| e.to_string())?;
+    // Lookup table: gamma > 1 lifts shadows, brightness shifts everything up or down.
+    let mut lut = [0u8; 256];
+    for (i, out) in lut.iter_mut().enumerate() {
+        let v = (i as f32 / 255.0).powf(1.0 / gamma) + brightness;
+        *out = (v.clamp(0.0, 1.0) * 255.0).round() as u8;
+    }
+    let mut out = Vec::new();
+    if img.color().has_alpha() {
+        let mut rgba = img.into_rgba8();
+        for px in rgba.pixels_mut() {
+            for c in &mut px.0[..3] {
+                *c = lut[*c as usize];
+            }
+        }
+        rgba.write_to(&mut std::io::Cursor::new(&mut out), image::ImageFormat::Png)
+            .map_err(|e| e.to_string())?;
+        Ok((out, "image/png"))
+    } else {
+        let mut rgb = img.into_rgb8();
+        for c in rgb.iter_mut() {
+            *c = lut[*c as usize];
+        }
+        image::codecs::jpeg::JpegEncoder::new_with_quality(&mut out, PREVIEW_JPEG_QUALITY)
+            .encode_image(&rgb)
+            .map_err(|e| e.to_string())?;
+        Ok((out, "image/jpeg"))
+    }
+}
+
 /// Returns a data URL (e.g. data:image/jpeg;base64,...) for the file at path.
-/// Used as fallback when asset protocol fails for preview. Limited to 8MB.
+/// The MIME type comes from the file's first bytes when recognizable, else the extension,
+/// so misnamed files still render and a .jpg-named PDF is rejected.
+/// HEIC/HEIF (by extension or content) is decoded and returned as a JPEG data URL when built
+/// with the heic feature; otherwise, or if decoding fails, it errors as not supported.
+/// Optional brightness (-1..1, default 0) and gamma (0.2..5, default 1) re-encode the preview
+/// with the adjustment applied; this is non-destructive, the file on disk is never modified.
+/// SVGs are rasterized to a PNG whose longest edge is svg_max_edge (default 2048) so the
+/// webview never runs SVG markup; raw_svg = true returns the SVG itself for trusted sources.
+/// JPEG/TIFF with an EXIF orientation other than normal (phone portrait shots) are re-encoded
+/// upright; everything else is passed through byte for byte.
+/// Used as fallback when asset protocol fails for preview. Limited to 8MB, both the file and
+/// the returned image (the decoded HEIC, rasterized SVG or re-encoded JPEG). Decoding and
+/// rasterizing run on a background thread.
 #[tauri::command]
-pub fn read_file_as_data_url(path: String) -> Result<String, String> {
-    let p = PathBuf::from(&path);
+pub async fn read_file_as_data_url(
+    path: String,
+    brightness: Option<f32>,
+    gamma: Option<f32>,
+    raw_svg: Option<bool>,
+    svg_max_edge: Option<u32>,
+) -> Result<String, String> {
+    let p = normalize_path(&path)?;
+    tauri::async_runtime::spawn_blocking(move || {
+        file_data_url(&p, brightness, gamma, raw_svg, svg_max_edge)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Body of read_file_as_data_url for an already validated path.
+fn file_data_url(
+    p: &Path,
+    brightness: Option<f32>,
+    gamma: Option<f32>,
+    raw_svg: Option<bool>,
+    svg_max_edge: Option<u32>,
+) -> Result<String, String> {
     let ext = p.extension().and_then(|e| e.to_str()).unwrap_or("").to_lowercase();
-    if ext == "heic" || ext == "heif" {
-        return Err("HEIC/HEIF is not supported".to_string());
-    }
+    let heif_ext = ext == "heic" || ext == "heif";
     if ext == "pdf" {
         return Err("PDF cannot be displayed".to_string());
     }
-    let meta = std::fs::metadata(&p).map_err(|e| e.to_string())?;
+    let meta = std::fs::metadata(p).map_err(|e| e.to_string())?;
     if meta.is_dir() {
         return Err("Path is a directory".to_string());
     }
-    if meta.len() > MAX_DATA_URL_SIZE {
+    let max_size = if heif_ext { MAX_HEIF_INPUT_SIZE } else { MAX_DATA_URL_SIZE };
+    if meta.len() > max_size {
         return Err("File too large for preview".to_string());
     }
-    let bytes = std::fs::read(&p).map_err(|e| e.to_string())?;
-    let mime = match ext.as_str() {
-        "jpg" | "jpeg" => "image/jpeg",
-        "png" => "image/png",
-        "gif" => "image/gif",
-        "webp" => "image/webp",
-        "bmp" => "image/bmp",
-        "tiff" | "tif" => "image/tiff",
-        "ico" => "image/x-icon",
-        "svg" => "image/svg+xml",
-        _ => "application/octet-stream",
+    let mut bytes = std::fs::read(p).map_err(|e| e.to_string())?;
+    let sniffed = infer::get(&bytes);
+    if sniffed.map(|t| t.mime_type()) == Some(PDF_MIME) {
+        return Err("PDF cannot be displayed".to_string());
+    }
+    // A .heic name only counts when the content isn't recognisably another image format.
+    let is_heif = sniffed.is_some_and(|t| HEIF_MIMES.contains(&t.mime_type()))
+        || (heif_ext && sniffed.is_none_or(|t| t.matcher_type() != infer::MatcherType::Image));
+    let mut mime = if is_heif {
+        bytes = heif_to_jpeg(&bytes).map_err(|_| HEIF_NOT_SUPPORTED.to_string())?;
+        "image/jpeg"
+    } else {
+        preview_mime(sniffed, &ext)
     };
+    if mime == SVG_MIME && !raw_svg.unwrap_or(false) {
+        bytes = rasterize_svg(&bytes, svg_max_edge.unwrap_or(DEFAULT_SVG_RASTER_EDGE))?;
+        mime = "image/png";
+    }
+    if mime == "image/jpeg" || mime == "image/tiff" {
+        if let Some((upright, upright_mime)) = upright_preview(&bytes) {
+            bytes = upright;
+            mime = upright_mime;
+        }
+    }
+    let brightness = brightness
+        .filter(|b| b.is_finite())
+        .map_or(0.0, |b| b.clamp(MIN_BRIGHTNESS, MAX_BRIGHTNESS));
+    let gamma = gamma
+        .filter(|g| g.is_finite())
+        .map_or(1.0, |g| g.clamp(MIN_GAMMA, MAX_GAMMA));
+    if brightness != 0.0 || gamma != 1.0 {
+        (bytes, mime) = adjust_image(&bytes, brightness, gamma)?;
+    }
+    if bytes.len() as u64 > MAX_DATA_URL_SIZE {
+        return Err("File too large for preview".to_string());
+    }
     let b64 = STANDARD.encode(&bytes);
     Ok(format!("data:{};base64,{}", mime, b64))
 }
 
+/// Most bytes read_file_range returns per call (same budget as a data URL).
+const MAX_FILE_RANGE_LENGTH: u64 = MAX_DATA_URL_SIZE;
+
+/// Returns base64 of length bytes starting at offset in the file at path, so files too large
+/// for read_file_as_data_url (huge TIFFs) can be read piece by piece. The range must lie
+/// within the file and length may be at most 8 MB.
+#[tauri::command]
+pub async fn read_file_range(path: String, offset: u64, length: u64) -> Result<String, String> {
+    let p = normalize_path(&path)?;
+    if length > MAX_FILE_RANGE_LENGTH {
+        return Err(format!(
+            "At most {} MB can be read per call.",
+            MAX_FILE_RANGE_LENGTH / (1024 * 1024)
+        ));
+    }
+    tauri::async_runtime::spawn_blocking(move || {
+        use std::io::Read;
+        let mut f = std::fs::File::open(&p).map_err(|e| e.to_string())?;
+        let meta = f.metadata().map_err(|e| e.to_string())?;
+        if meta.is_dir() {
+            return Err("Path is a directory".to_string());
+        }
+        let end = offset
+            .checked_add(length)
+            .filter(|&end| end <= meta.len())
+            .ok_or_else(|| format!("Range is outside the file ({} bytes).", meta.len()))?;
+        f.seek(std::io::SeekFrom::Start(offset))
+            .map_err(|e| e.to_string())?;
+        let mut bytes = vec![0u8; (end - offset) as usize];
+        f.read_exact(&mut bytes).map_err(|e| e.to_string())?;
+        Ok(STANDARD.encode(&bytes))
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Longest preview edge for get_image_preview_data_url when the caller does not pass one,
+/// and the largest accepted.
+const DEFAULT_PREVIEW_MAX_DIMENSION: u32 = 2048;
+const MAX_PREVIEW_MAX_DIMENSION: u32 = 8192;
+
+/// True if the GIF at path has more than one frame (reads until the second frame only).
+fn is_animated_gif(path: &Path) -> bool {
+    use image::AnimationDecoder;
+    let Ok(file) = std::fs::File::open(path) else {
+        return false;
+    };
+    match image::codecs::gif::GifDecoder::new(std::io::BufReader::new(file)) {
+        Ok(decoder) => decoder.into_frames().take(2).count() > 1,
+        Err(_) => false,
+    }
+}
+
+/// Encodes a preview as JPEG, or PNG when it has transparency. Returns (bytes, mime).
+pub fn encode_preview(img: image::DynamicImage) -> Result<(Vec<u8>, &'static str), String> {
+    let mut out = Vec::new();
+    if img.color().has_alpha() {
+        img.write_to(&mut std::io::Cursor::new(&mut out), image::ImageFormat::Png)
+            .map_err(|e| e.to_string())?;
+        Ok((out, "image/png"))
+    } else {
+        image::codecs::jpeg::JpegEncoder::new_with_quality(&mut out, PREVIEW_JPEG_QUALITY)
+            .encode_image(&img.into_rgb8())
+            .map_err(|e| e.to_string())?;
+        Ok((out, "image/jpeg"))
+    }
+}
+
+/// Decodes an image and turns it upright according to its EXIF orientation (if any).
+fn decode_upright(
+    reader: image::ImageReader<impl BufRead + Seek>,
+) -> Result<image::DynamicImage, String> {
+    let mut decoder = reader
+        .with_guessed_format()
+        .map_err(|e| e.to_string())?
+        .into_decoder()
+        .map_err(|e| e.to_string())?;
+    let orientation = decoder.orientation().unwrap_or(Orientation::NoTransforms);
+    let mut img = image::DynamicImage::from_decoder(decoder).map_err(|e| e.to_string())?;
+    img.apply_orientation(orientation);
+    Ok(img)
+}
+
+/// For JPEG/TIFF bytes with an EXIF orientation other than normal, the image rotated/flipped
+/// upright and re-encoded (the new file has no EXIF, so it isn't turned twice). None when
+/// there is nothing to correct or the image can't be decoded, so the bytes pass through.
+fn upright_preview(bytes: &[u8]) -> Option<(Vec<u8>, &'static str)> {
+    let orientation = image::ImageReader::new(std::io::Cursor::new(bytes))
+        .with_guessed_format()
+        .ok()?
+        .into_decoder()
+        .ok()?
+        .orientation()
+        .ok()?;
+    if orientation == Orientation::NoTransforms {
+        return None;
+    }
+    let img = decode_upright(image::ImageReader::new(std::io::Cursor::new(bytes))).ok()?;
+    encode_preview(img).ok()
+}
+
+/// Decodes the image (upright per EXIF), scales it down so the longest side is at most
+/// max_dimension, and encodes it as JPEG (PNG when it has transparency). Returns (bytes, mime).
+fn downscaled_preview(
+    path: &Path,
+    max_dimension: u32,
+) -> Result<(Vec<u8>, &'static str), String> {
+    let img = decode_upright(image::ImageReader::open(path).map_err(|e| e.to_string())?)?;
+    let img = if img.width().max(img.height()) > max_dimension {
+        img.resize(max_dimension, max_dimension, image::imageops::FilterType::Triangle)
+    } else {
+        img
+    };
+    encode_preview(img)
+}
+
+/// Returns a preview data URL for images of any file size: the image is decoded, scaled so
+/// its longest side is at most max_dimension (default 2048) and re-encoded, which keeps the
+/// data URL small for 24MP+ photos. Animated GIFs, and files the decoder can't read (SVG,
+/// HEIC, ...), go through read_file_as_data_url unchanged, including its 8MB limit.
+#[tauri::command]
+pub async fn get_image_preview_data_url(
+    path: String,
+    max_dimension: Option<u32>,
+) -> Result<String, String> {
+    let p = normalize_path(&path)?;
+    if !p.is_file() {
+        return Err("Path is not a file.".to_string());
+    }
+    let max_dimension = max_dimension
+        .filter(|&d| d > 0)
+        .unwrap_or(DEFAULT_PREVIEW_MAX_DIMENSION)
+        .min(MAX_PREVIEW_MAX_DIMENSION);
+    tauri::async_runtime::spawn_blocking(move || {
+        let ext = p.extension().and_then(|e| e.to_str()).unwrap_or("").to_lowercase();
+        let passthrough = || file_data_url(&p, None, None, None, None);
+        if ext == "gif" && is_animated_gif(&p) {
+            return passthrough();
+        }
+        match downscaled_preview(&p, max_dimension) {
+            Ok((bytes, mime)) => Ok(format!("data:{};base64,{}", mime, STANDARD.encode(&bytes))),
+            Err(_) => passthrough(),
+        }
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Returned by read_file_as_audio_url with prefer_native when play_audio can decode the file,
+/// so the frontend plays it natively instead of loading a data URL.
+pub const NATIVE_PLAYBACK_SENTINEL: &str = "native";
+
 /// Returns a data URL for an audio file so the frontend can play it without relying on the asset protocol.
 /// Used when convertFileSrc fails ("no supported sources"). Limited to 32MB.
+/// With prefer_native, formats rodio decodes return NATIVE_PLAYBACK_SENTINEL instead.
+/// Reading and encoding run on a background thread so large FLACs don't block other commands.
 #[tauri::command]
-pub fn read_file_as_audio_url(path: String) -> Result<String, String> {
-    let p = PathBuf::from(&path);
+pub async fn read_file_as_audio_url(
+    path: String,
+    prefer_native: Option<bool>,
+) -> Result<String, String> {
+    let p = normalize_path(&path)?;
     let ext = p.extension().and_then(|e| e.to_str()).unwrap_or("").to_lowercase();
-    let meta = std::fs::metadata(&p).map_err(|e| e.to_string())?;
-    if meta.is_dir() {
-        return Err("Path is a directory.".to_string());
-    }
-    if meta.len() > MAX_AUDIO_DATA_URL_SIZE {
-        return Err("File too large for playback (max 32MB).".to_string());
-    }
-    let bytes = std::fs::read(&p).map_err(|e| e.to_string())?;
-    let mime = match ext.as_str() {
-        "mp3" => "audio/mpeg",
-        "wav" => "audio/wav",
-        "ogg" => "audio/ogg",
-        "m4a" => "audio/mp4",
-        "aac" => "audio/aac",
-        "flac" => "audio/flac",
-        "wma" => "audio/x-ms-wma",
-        "opus" => "audio/opus",
-        "webm" => "audio/webm",
-        _ => "application/octet-stream",
+    let native = audio_mime(&ext).is_some() && audio_decoder(&ext) != AudioDecoder::Unsupported;
+    if prefer_native.unwrap_or(false) && native {
+        return Ok(NATIVE_PLAYBACK_SENTINEL.to_string());
+    }
+    tauri::async_runtime::spawn_blocking(move || {
+        let meta = std::fs::metadata(&p).map_err(|e| e.to_string())?;
+        if meta.is_dir() {
+            return Err("Path is a directory.".to_string());
+        }
+        if meta.len() > MAX_AUDIO_DATA_URL_SIZE {
+            return Err(format!(
+                "File too large for playback ({:.1}MB, max {}MB).",
+                meta.len() as f64 / (1024.0 * 1024.0),
+                MAX_AUDIO_DATA_URL_SIZE / (1024 * 1024)
+            ));
+        }
+        let bytes = std::fs::read(&p).map_err(|e| e.to_string())?;
+        let mime = audio_mime(&ext).unwrap_or("application/octet-stream");
+        let b64 = STANDARD.encode(&bytes);
+        Ok(format!("data:{};base64,{}", mime, b64))
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Upper bound on entries visited by get_directory_stats (and the duplicate scan's walk) so a
+/// huge tree can't hang the scan.
+pub const MAX_STATS_NODES: u64 = 2_000_000;
+
+/// Emit a directory-stats-progress event every this many visited entries.
+const STATS_PROGRESS_EVERY: u64 = 5_000;
+
+/// Result of get_directory_stats. `truncated` is true when the node budget was hit;
+/// `cancelled` when cancel_operation stopped the scan (totals are partial in both cases).
+/// `skipped_dirs` counts subfolders that could not be read (last_error describes the latest).
+#[derive(Debug, Clone, Serialize)]
+pub struct DirectoryStats {
+    pub total_bytes: u64,
+    pub file_count: u64,
+    pub dir_count: u64,
+    pub skipped_dirs: u64,
+    pub truncated: bool,
+    pub cancelled: bool,
+    pub last_error: Option<String>,
+}
+
+/// Progress payload for directory-stats-progress (sent during large scans).
+#[derive(Debug, Clone, Serialize)]
+struct DirectoryStatsProgress {
+    path: String,
+    total_bytes: u64,
+    file_count: u64,
+    dir_count: u64,
+}
+
+/// Walks the tree under root summing file sizes and counting files/subfolders.
+/// Unreadable subfolders are skipped and counted; stops after MAX_STATS_NODES entries
+/// or as soon as the token is cancelled.
+fn compute_directory_stats(app: &AppHandle, root: &Path, cancel: &CancelToken) -> DirectoryStats {
+    let mut stats = DirectoryStats {
+        total_bytes: 0,
+        file_count: 0,
+        dir_count: 0,
+        skipped_dirs: 0,
+        truncated: false,
+        cancelled: false,
+        last_error: None,
     };
-    let b64 = STANDARD.encode(&bytes);
-    Ok(format!("data:{};base64,{}", mime, b64))
+    let root_str = root.to_string_lossy().into_owned();
+    let mut visited: u64 = 0;
+    let mut stack: Vec<PathBuf> = vec![root.to_path_buf()];
+    while let Some(dir) = stack.pop() {
+        let read = match std::fs::read_dir(&dir) {
+            Ok(r) => r,
+            Err(e) => {
+                stats.skipped_dirs += 1;
+                stats.last_error = Some(friendly_error(&e));
+                continue;
+            }
+        };
+        for entry in read.flatten() {
+            if cancel.is_cancelled() {
+                stats.cancelled = true;
+                return stats;
+            }
+            visited += 1;
+            if visited > MAX_STATS_NODES {
+                stats.truncated = true;
+                return stats;
+            }
+            let meta = match entry.metadata() {
+                Ok(m) => m,
+                Err(_) => continue,
+            };
+            if meta.is_dir() {
+                stats.dir_count += 1;
+                stack.push(entry.path());
+            } else {
+                stats.file_count += 1;
+                stats.total_bytes += meta.len();
+            }
+            if visited.is_multiple_of(STATS_PROGRESS_EVERY) {
+                let _ = app.emit(
+                    "directory-stats-progress",
+                    DirectoryStatsProgress {
+                        path: root_str.clone(),
+                        total_bytes: stats.total_bytes,
+                        file_count: stats.file_count,
+                        dir_count: stats.dir_count,
+                    },
+                );
+            }
+        }
+    }
+    stats
+}
+
+/// Computes the recursive size and file/folder count of a directory (e.g. before copying it).
+/// Runs on a background thread; emits directory-stats-progress events for large trees.
+/// Pass operation_id to be able to stop it with cancel_operation (partial totals are returned).
+#[tauri::command]
+pub async fn get_directory_stats(
+    app: AppHandle,
+    path: String,
+    operation_id: Option<String>,
+) -> Result<DirectoryStats, String> {
+    let path_buf = normalize_path(&path)?;
+    if !path_buf.is_dir() {
+        return Err("Path is not a directory.".to_string());
+    }
+    let cancel = app.state::<CancellationState>().register(operation_id);
+    tauri::async_runtime::spawn_blocking(move || compute_directory_stats(&app, &path_buf, &cancel))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Upper bound on paths returned by glob_files so a broad pattern can't flood the UI.
+const MAX_GLOB_RESULTS: usize = 10_000;
+
+/// Result of glob_files. `truncated` is true when MAX_GLOB_RESULTS or the node budget was hit
+/// (or the operation was cancelled); `skipped_dirs` counts subfolders that could not be read.
+#[derive(Debug, Serialize)]
+pub struct GlobFilesResult {
+    pub paths: Vec<String>,
+    pub truncated: bool,
+    pub skipped_dirs: u64,
+}
+
+/// Walks root (only its direct children unless recursive) collecting files that match.
+/// Patterns without a separator match the file name (*.jpg); others match the path relative
+/// to root (2024/**/*.jpg). Hidden entries and unreadable subfolders are skipped.
+fn collect_glob_matches(
+    root: &Path,
+    matcher: &globset::GlobMatcher,
+    match_relative: bool,
+    recursive: bool,
+    cancel: &CancelToken,
+) -> GlobFilesResult {
+    let mut result = GlobFilesResult {
+        paths: Vec::new(),
+        truncated: false,
+        skipped_dirs: 0,
+    };
+    let mut visited: u64 = 0;
+    let mut stack: Vec<PathBuf> = vec![root.to_path_buf()];
+    while let Some(dir) = stack.pop() {
+        let read = match std::fs::read_dir(&dir) {
+            Ok(r) => r,
+            Err(_) => {
+                result.skipped_dirs += 1;
+                continue;
+            }
+        };
+        for entry in read.flatten() {
+            visited += 1;
+            if visited > MAX_STATS_NODES || cancel.is_cancelled() {
+                result.truncated = true;
+                return result;
+            }
+            let meta = match entry.metadata() {
+                Ok(m) => m,
+                Err(_) => continue,
+            };
+            let path = entry.path();
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if is_hidden_entry(&name, &meta) {
+                continue;
+            }
+            if meta.is_dir() {
+                if recursive {
+                    stack.push(path);
+                }
+                continue;
+            }
+            let is_match = if match_relative {
+                path.strip_prefix(root).is_ok_and(|rel| matcher.is_match(rel))
+            } else {
+                matcher.is_match(&name)
+            };
+            if is_match {
+                if result.paths.len() >= MAX_GLOB_RESULTS {
+                    result.truncated = true;
+                    return result;
+                }
+                result.paths.push(path.to_string_lossy().into_owned());
+            }
+        }
+    }
+    result
+}
+
+/// Lists files under root matching a glob pattern (case-insensitive), e.g. "*.jpg" for a
+/// filtered slideshow. Returns absolute paths sorted by path, at most MAX_GLOB_RESULTS.
+/// Pass operation_id to be able to stop it with cancel_operation.
+#[tauri::command]
+pub async fn glob_files(
+    app: AppHandle,
+    root: String,
+    pattern: String,
+    recursive: bool,
+    operation_id: Option<String>,
+) -> Result<GlobFilesResult, String> {
+    let root = normalize_path(&root)?;
+    if !root.is_dir() {
+        return Err("Path is not a directory.".to_string());
+    }
+    let pattern = pattern.trim().to_string();
+    if pattern.is_empty() {
+        return Err("Pattern is empty.".to_string());
+    }
+    // globset compares against '/'-separated paths, so accept Windows-style separators too.
+    #[cfg(target_os = "windows")]
+    let pattern = pattern.replace('\\', "/");
+    let match_relative = pattern.contains('/');
+    let matcher = globset::GlobBuilder::new(&pattern)
+        .case_insensitive(true)
+        .literal_separator(true)
+        .build()
+        .map_err(|e| format!("Invalid pattern: {}", e))?
+        .compile_matcher();
+    let cancel = app.state::<CancellationState>().register(operation_id);
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut result = collect_glob_matches(&root, &matcher, match_relative, recursive, &cancel);
+        result.paths.sort_by_key(|p| p.to_lowercase());
+        result
+    })
+    .await
+    .map_err(|e| e.to_string())
+}
+
+/// Upper bound on paths returned by scan_media_recursive.
+const MAX_MEDIA_SCAN_RESULTS: usize = 100_000;
+
+/// Result of scan_media_recursive. `truncated` is true when MAX_MEDIA_SCAN_RESULTS or the node
+/// budget was hit (or the scan was cancelled); `skipped_dirs` counts unreadable subfolders.
+#[derive(Debug, Serialize)]
+pub struct MediaScanResult {
+    pub paths: Vec<String>,
+    pub truncated: bool,
+    pub skipped_dirs: u64,
+}
+
+/// Walks root down to max_depth folder levels (None = no limit) collecting files of the given
+/// kinds. Hidden entries are skipped; unreadable subfolders (e.g. on a drive that went away)
+/// are counted and skipped so the rest of the tree is still scanned. Linked folders are only
+/// entered with follow_symlinks, and then each real folder at most once, so a link back to an
+/// ancestor cannot loop.
+fn collect_media(
+    root: &Path,
+    kinds: &[ScanKind],
+    max_depth: Option<usize>,
+    follow_symlinks: bool,
+    cancel: &CancelToken,
+) -> MediaScanResult {
+    let mut result = MediaScanResult {
+        paths: Vec::new(),
+        truncated: false,
+        skipped_dirs: 0,
+    };
+    let mut visited: u64 = 0;
+    let mut seen: HashSet<PathBuf> = HashSet::new();
+    let mut stack: Vec<(PathBuf, usize)> = vec![(root.to_path_buf(), 0)];
+    while let Some((dir, depth)) = stack.pop() {
+        if follow_symlinks && !std::fs::canonicalize(&dir).is_ok_and(|real| seen.insert(real)) {
+            continue;
+        }
+        let read = match std::fs::read_dir(&dir) {
+            Ok(r) => r,
+            Err(_) => {
+                result.skipped_dirs += 1;
+                continue;
+            }
+        };
+        for entry in read.flatten() {
+            visited += 1;
+            if visited > MAX_STATS_NODES || cancel.is_cancelled() {
+                result.truncated = true;
+                return result;
+            }
+            let meta = match entry.metadata() {
+                Ok(m) => m,
+                Err(_) => continue,
+            };
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if is_hidden_entry(&name, &meta) {
+                continue;
+            }
+            let meta = if follow_symlinks {
+                follow_link(&entry.path(), meta).0
+            } else {
+                meta
+            };
+            if meta.is_dir() {
+                if max_depth.is_none_or(|max| depth < max) {
+                    stack.push((entry.path(), depth + 1));
+                }
+                continue;
+            }
+            if ScanKind::of_name(&name).is_some_and(|k| kinds.contains(&k)) {
+                if result.paths.len() >= MAX_MEDIA_SCAN_RESULTS {
+                    result.truncated = true;
+                    return result;
+                }
+                result.paths.push(entry.path().to_string_lossy().into_owned());
+            }
+        }
+    }
+    result
+}
+
+/// Collects every image/video/audio file (per kinds) under root for building a slideshow,
+/// in natural path order. max_depth limits how many folder levels are descended (0 = root
+/// only). At most 100,000 paths are returned, with truncated set when more exist. Symlinked
+/// folders and junctions are skipped unless follow_symlinks is true. Pass operation_id to be
+/// able to stop it with cancel_operation.
+#[tauri::command]
+pub async fn scan_media_recursive(
+    app: AppHandle,
+    root: String,
+    kinds: Vec<String>,
+    max_depth: Option<usize>,
+    follow_symlinks: Option<bool>,
+    operation_id: Option<String>,
+) -> Result<MediaScanResult, String> {
+    let root = normalize_path(&root)?;
+    if !root.is_dir() {
+        return Err("Path is not a directory.".to_string());
+    }
+    let kinds = kinds
+        .iter()
+        .map(|k| ScanKind::parse(k))
+        .collect::<Result<Vec<_>, _>>()?;
+    if kinds.is_empty() {
+        return Err("Choose at least one media kind.".to_string());
+    }
+    let cancel = app.state::<CancellationState>().register(operation_id);
+    tauri::async_runtime::spawn_blocking(move || {
+        let follow = follow_symlinks.unwrap_or(false);
+        let mut result = collect_media(&root, &kinds, max_depth, follow, &cancel);
+        result.paths.sort_by(|a, b| natural_cmp(a, b).then_with(|| a.cmp(b)));
+        result
+    })
+    .await
+    .map_err(|e| e.to_string())
+}
+
+/// Entries per dir-entry-batch event from list_directory_streaming.
+const DIR_BATCH_SIZE: usize = 500;
+
+/// Payload of dir-entry-batch: the next chunk of children of path (unsorted, in read order).
+#[derive(Debug, Clone, Serialize)]
+struct DirEntryBatch {
+    operation_id: String,
+    path: String,
+    entries: Vec<DirEntry>,
+}
+
+/// Payload of dir-list-done, the final event for an operation. `ok` is false with `error` set
+/// when the listing failed, including partway through (e.g. drive unplugged mid-enumeration).
+#[derive(Debug, Clone, Serialize)]
+struct DirListDone {
+    operation_id: String,
+    path: String,
+    ok: bool,
+    total: usize,
+    cancelled: bool,
+    error: Option<String>,
+}
+
+/// Reads path and emits dir-entry-batch events of DIR_BATCH_SIZE, then dir-list-done.
+fn stream_directory(
+    app: &AppHandle,
+    path: &Path,
+    operation_id: &str,
+    show_hidden: bool,
+    cancel: &CancelToken,
+) {
+    let path_str = path.to_string_lossy().into_owned();
+    let done = |total: usize, cancelled: bool, error: Option<String>| {
+        let _ = app.emit(
+            "dir-list-done",
+            DirListDone {
+                operation_id: operation_id.to_string(),
+                path: path_str.clone(),
+                ok: error.is_none(),
+                total,
+                cancelled,
+                error,
+            },
+        );
+    };
+    let send_batch = |entries: Vec<DirEntry>| {
+        if entries.is_empty() {
+            return;
+        }
+        let _ = app.emit(
+            "dir-entry-batch",
+            DirEntryBatch {
+                operation_id: operation_id.to_string(),
+                path: path_str.clone(),
+                entries,
+            },
+        );
+    };
+    let read = match std::fs::read_dir(path) {
+        Ok(r) => r,
+        Err(e) => return done(0, false, Some(friendly_error(&e))),
+    };
+    let mut batch: Vec<DirEntry> = Vec::with_capacity(DIR_BATCH_SIZE);
+    let mut total = 0;
+    for entry in read {
+        if cancel.is_cancelled() {
+            send_batch(batch);
+            return done(total, true, None);
+        }
+        let entry = match entry {
+            Ok(e) => e,
+            Err(e) => {
+                send_batch(batch);
+                return done(total, false, Some(friendly_error(&e)));
+            }
+        };
+        let meta = match entry.metadata() {
+            Ok(m) => m,
+            Err(_) => continue,
+        };
+        let entry_path = entry.path();
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if !show_hidden && is_hidden_entry(&name, &meta) {
+            continue;
+        }
+        let (meta, is_symlink) = follow_link(&entry_path, meta);
+        batch.push(DirEntry {
+            name,
+            path: entry_path.to_string_lossy().into_owned(),
+            is_dir: meta.is_dir(),
+            is_symlink,
+            size: if meta.is_dir() { 0 } else { meta.len() },
+            modified: modified_millis(&meta),
+            ..Default::default()
+        });
+        total += 1;
+        if batch.len() >= DIR_BATCH_SIZE {
+            send_batch(std::mem::replace(&mut batch, Vec::with_capacity(DIR_BATCH_SIZE)));
+        }
+    }
+    send_batch(batch);
+    done(total, false, None)
+}
+
+/// Lists path progressively for huge folders: returns at once, then emits dir-entry-batch
+/// events (DIR_BATCH_SIZE entries, unsorted) and a final dir-list-done, all tagged with
+/// operation_id. The listing can be stopped with cancel_operation(operation_id).
+/// Hidden/system entries are skipped unless show_hidden is true, like list_directory.
+#[tauri::command]
+pub fn list_directory_streaming(
+    app: AppHandle,
+    path: String,
+    operation_id: String,
+    show_hidden: Option<bool>,
+) -> Result<(), String> {
+    let path_buf = normalize_path(&path)?;
+    if !path_buf.is_dir() {
+        return Err("Path is not a directory.".to_string());
+    }
+    let show_hidden = show_hidden.unwrap_or(false);
+    let cancel = app
+        .state::<CancellationState>()
+        .register(Some(operation_id.clone()));
+    tauri::async_runtime::spawn_blocking(move || {
+        stream_directory(&app, &path_buf, &operation_id, show_hidden, &cancel)
+    });
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn named(names: &[&str]) -> Vec<DirEntry> {
+        names
+            .iter()
+            .map(|n| DirEntry {
+                name: n.to_string(),
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    fn names(entries: &[DirEntry]) -> Vec<&str> {
+        entries.iter().map(|e| e.name.as_str()).collect()
+    }
+
+    #[test]
+    fn natural_cmp_orders_digit_runs_by_value() {
+        assert_eq!(natural_cmp("img2", "img10"), Ordering::Less);
+        assert_eq!(natural_cmp("img10", "img9"), Ordering::Greater);
+        assert_eq!(natural_cmp("a1b10", "a1b2"), Ordering::Greater);
+        assert_eq!(natural_cmp("2024-01-05 3", "2024-01-05 12"), Ordering::Less);
+        assert_eq!(natural_cmp("img", "img1"), Ordering::Less);
+        // Runs far beyond u64 are compared without overflow.
+        assert_eq!(
+            natural_cmp("x99999999999999999999999", "x100000000000000000000000"),
+            Ordering::Less
+        );
+    }
+
+    #[test]
+    fn natural_cmp_puts_less_zero_padding_first() {
+        assert_eq!(natural_cmp("img7", "img007"), Ordering::Less);
+        assert_eq!(natural_cmp("img007", "img07"), Ordering::Greater);
+        assert_eq!(natural_cmp("img007", "img8"), Ordering::Less);
+        assert_eq!(natural_cmp("img010", "img9"), Ordering::Greater);
+    }
+
+    #[test]
+    fn natural_cmp_ignores_case_including_non_ascii() {
+        assert_eq!(natural_cmp("Photo1", "photo1"), Ordering::Equal);
+        assert_eq!(natural_cmp("apple", "Banana"), Ordering::Less);
+        assert_eq!(natural_cmp("Ärger", "ärger"), Ordering::Equal);
+        assert_eq!(natural_cmp("Ωmega2", "ωmega10"), Ordering::Less);
+        // Only ASCII digits form numeric runs; other digits compare as characters.
+        assert_eq!(natural_cmp("a\u{0662}", "a10"), Ordering::Greater);
+    }
+
+    #[test]
+    fn sort_entries_natural_breaks_ties_by_exact_name() {
+        let mut entries = named(&["b", "img10", "B", "img2", "IMG1"]);
+        sort_entries(&mut entries, DirSort::Natural);
+        assert_eq!(names(&entries), ["B", "b", "IMG1", "img2", "img10"]);
+    }
+
+    #[test]
+    fn sort_entries_name_is_lexicographic() {
+        let mut entries = named(&["img2", "IMG10", "img1"]);
+        sort_entries(&mut entries, DirSort::Name);
+        assert_eq!(names(&entries), ["img1", "IMG10", "img2"]);
+    }
+
+    #[test]
+    fn sort_entries_size_puts_folders_first() {
+        let mut entries = named(&["small", "dir10", "big", "dir2", "same"]);
+        for (entry, (is_dir, size)) in entries
+            .iter_mut()
+            .zip([(false, 1), (true, 0), (false, 9), (true, 0), (false, 1)])
+        {
+            entry.is_dir = is_dir;
+            entry.size = size;
+        }
+        sort_entries(&mut entries, DirSort::Size);
+        assert_eq!(names(&entries), ["dir2", "dir10", "big", "same", "small"]);
+    }
+
+    #[test]
+    fn resolve_sort_defaults_to_name() {
+        assert_eq!(resolve_sort(None), Ok(DirSort::Name));
+        assert_eq!(resolve_sort(Some("natural")), Ok(DirSort::Natural));
+        assert!(resolve_sort(Some("date")).is_err());
+    }
+
+    fn filter_matches(filter: &str, name: &str) -> bool {
+        name_filter(Some(filter)).unwrap().unwrap().is_match(name)
+    }
+
+    #[test]
+    fn name_filter_matches_extension_globs_ignoring_case() {
+        assert!(filter_matches("*.jpg", "beach.jpg"));
+        assert!(filter_matches("*.jpg", "BEACH.JPG"));
+        assert!(!filter_matches("*.jpg", "beach.jpeg"));
+        assert!(!filter_matches("*.jpg", "beach.jpg.txt"));
+    }
+
+    #[test]
+    fn name_filter_question_mark_is_one_character() {
+        assert!(filter_matches("IMG_??", "IMG_01"));
+        assert!(filter_matches("IMG_??", "img_ab"));
+        assert!(!filter_matches("IMG_??", "IMG_1"));
+        assert!(!filter_matches("IMG_??", "IMG_123"));
+        assert!(filter_matches("IMG_??.jpg", "IMG_07.jpg"));
+    }
+
+    #[test]
+    fn name_filter_without_wildcards_matches_substrings() {
+        assert!(filter_matches("vacation", "Summer Vacation 2024.jpg"));
+        assert!(filter_matches(" vacation ", "vacation.png"));
+        assert!(!filter_matches("vacation", "holiday.jpg"));
+    }
+
+    #[test]
+    fn name_filter_blank_is_no_filter_and_bad_globs_are_errors() {
+        assert!(name_filter(None).unwrap().is_none());
+        assert!(name_filter(Some("  ")).unwrap().is_none());
+        let err = name_filter(Some("[abc")).unwrap_err();
+        assert!(err.starts_with("Invalid filter"), "{}", err);
+    }
 }
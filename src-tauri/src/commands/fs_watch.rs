@@ -0,0 +1,203 @@
+// Author: Viorel LUPU
+// Date: 2026-10-14
+// Purpose: Folder watching so the tree and content pane refresh when files are added, removed
+// or renamed outside the app (e.g. a camera import). Changes are collected for a short quiet
+// period (at most WATCH_MAX_WAIT) and sent as one fs://changed event, so bulk copies don't
+// flood the UI.
+// A watched folder that disappears (drive unplugged) is reported once and then dropped.
+
+use super::fs::normalize_path;
+use notify::event::{EventKind, ModifyKind};
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager, State};
+
+/// Quiet time after the last change before the collected changes are emitted.
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Longest time changes are held back while events keep arriving without a quiet period.
+const WATCH_MAX_WAIT: Duration = Duration::from_secs(2);
+
+/// Purpose: Active folder watchers (normalized folder path -> watcher).
+/// Usage: Managed by Tauri; watch_directory inserts, unwatch_directory and app exit remove.
+/// Reason for choice: Dropping a notify watcher stops it and ends its debounce thread, so the
+/// map is the only thing that has to be cleaned up.
+#[derive(Default)]
+pub struct WatchState {
+    watchers: Mutex<HashMap<String, RecommendedWatcher>>,
+}
+
+impl WatchState {
+    /// Stops every watcher; called when the app exits.
+    pub fn clear(&self) {
+        if let Ok(mut map) = self.watchers.lock() {
+            map.clear();
+        }
+    }
+
+    fn remove(&self, key: &str) {
+        if let Ok(mut map) = self.watchers.lock() {
+            map.remove(key);
+        }
+    }
+}
+
+/// One changed entry in an fs://changed event. kind is "create", "modify", "remove",
+/// "rename" or "other".
+#[derive(Debug, Clone, Serialize)]
+pub struct FsChange {
+    pub path: String,
+    pub kind: &'static str,
+}
+
+/// Payload of fs://changed: the watched folder and what changed in it since the last
+/// event (one entry per path, latest kind wins).
+#[derive(Debug, Clone, Serialize)]
+pub struct DirectoryChanged {
+    pub dir: String,
+    pub changes: Vec<FsChange>,
+}
+
+/// Payload of directory-watch-error, emitted when a watched folder can no longer be watched.
+#[derive(Debug, Clone, Serialize)]
+pub struct DirectoryWatchError {
+    pub dir: String,
+    pub error: String,
+}
+
+fn change_kind(kind: &EventKind) -> &'static str {
+    match kind {
+        EventKind::Create(_) => "create",
+        EventKind::Remove(_) => "remove",
+        EventKind::Modify(ModifyKind::Name(_)) => "rename",
+        EventKind::Modify(_) => "modify",
+        _ => "other",
+    }
+}
+
+/// Adds the paths of one notify event to pending, keeping the order of first appearance.
+fn collect(pending: &mut Vec<FsChange>, event: notify::Event) {
+    let kind = change_kind(&event.kind);
+    for path in event.paths {
+        let path = path.to_string_lossy().into_owned();
+        match pending.iter_mut().find(|c| c.path == path) {
+            Some(change) => change.kind = kind,
+            None => pending.push(FsChange { path, kind }),
+        }
+    }
+}
+
+/// Debounce loop for one watcher. Ends when the watcher is dropped (its sender goes away)
+/// or the folder disappears.
+fn run_debounce(
+    app: AppHandle,
+    dir: PathBuf,
+    key: String,
+    rx: mpsc::Receiver<notify::Result<notify::Event>>,
+) {
+    let dir_string = dir.to_string_lossy().into_owned();
+    let mut pending: Vec<FsChange> = Vec::new();
+    let mut pending_since: Option<Instant> = None;
+    loop {
+        // Block until something happens; once changes are pending, wait for the quiet time but
+        // never past WATCH_MAX_WAIT from the first pending change.
+        let received = match pending_since {
+            None => rx.recv().map_err(|_| RecvTimeoutError::Disconnected),
+            Some(since) => match WATCH_MAX_WAIT.checked_sub(since.elapsed()) {
+                Some(left) if !left.is_zero() => rx.recv_timeout(WATCH_DEBOUNCE.min(left)),
+                _ => Err(RecvTimeoutError::Timeout),
+            },
+        };
+        match received {
+            Ok(Ok(event)) => {
+                collect(&mut pending, event);
+                if !pending.is_empty() {
+                    pending_since.get_or_insert_with(Instant::now);
+                }
+            }
+            Ok(Err(e)) => {
+                if !dir.is_dir() {
+                    report_lost(&app, &dir_string, &key, e.to_string());
+                    return;
+                }
+            }
+            Err(RecvTimeoutError::Timeout) => {
+                pending_since = None;
+                let payload = DirectoryChanged {
+                    dir: dir_string.clone(),
+                    changes: std::mem::take(&mut pending),
+                };
+                let _ = app.emit("fs://changed", payload);
+                if !dir.is_dir() {
+                    report_lost(&app, &dir_string, &key, "Folder was removed.".to_string());
+                    return;
+                }
+            }
+            Err(RecvTimeoutError::Disconnected) => return,
+        }
+    }
+}
+
+/// Emits directory-watch-error and drops the watcher of a folder that is gone.
+fn report_lost(app: &AppHandle, dir: &str, key: &str, detail: String) {
+    eprintln!("Folder watch stopped for {}: {}", dir, detail);
+    let payload = DirectoryWatchError {
+        dir: dir.to_string(),
+        error: "Folder is no longer available (drive may have been disconnected).".to_string(),
+    };
+    let _ = app.emit("directory-watch-error", payload);
+    app.state::<WatchState>().remove(key);
+}
+
+fn watch_key(path: &Path) -> String {
+    path.to_string_lossy().into_owned()
+}
+
+/// Starts watching the direct contents of path (not subfolders). Changes arrive as
+/// fs://changed { dir, changes: [{ path, kind }] } events, sent once 300 ms pass without
+/// further changes (or after 2 s while changes keep coming).
+/// Watching a folder that is already watched does nothing.
+#[tauri::command]
+pub fn watch_directory(
+    app: AppHandle,
+    path: String,
+    state: State<WatchState>,
+) -> Result<(), String> {
+    let dir = normalize_path(&path)?;
+    if !dir.is_dir() {
+        return Err("Path is not a folder.".to_string());
+    }
+    let key = watch_key(&dir);
+    let mut watchers = state.watchers.lock().map_err(|e| e.to_string())?;
+    if watchers.contains_key(&key) {
+        return Ok(());
+    }
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(move |res| {
+        let _ = tx.send(res);
+    })
+    .map_err(|e| e.to_string())?;
+    watcher
+        .watch(&dir, RecursiveMode::NonRecursive)
+        .map_err(|e| e.to_string())?;
+    let thread_key = key.clone();
+    std::thread::spawn(move || run_debounce(app, dir, thread_key, rx));
+    watchers.insert(key, watcher);
+    Ok(())
+}
+
+/// Stops watching path. Not an error if it was not being watched; a folder on a drive that
+/// is gone is matched by the path as given.
+#[tauri::command]
+pub fn unwatch_directory(path: String, state: State<WatchState>) -> Result<(), String> {
+    let key = normalize_path(&path)
+        .map(|dir| watch_key(&dir))
+        .unwrap_or_else(|_| path.trim().to_string());
+    state.remove(&key);
+    Ok(())
+}
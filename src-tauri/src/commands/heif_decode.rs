@@ -0,0 +1,46 @@
+// Author: Viorel LUPU
+// Date: 2026-10-14
+// Purpose: Decode HEIC/HEIF (the default iPhone photo format) to JPEG for previews, since the
+// webview can't show it. Needs libheif, so it is behind the "heic" cargo feature; without
+// the feature every call fails and callers keep their "not supported" message.
+
+/// JPEG quality for decoded HEIC previews (same as other re-encoded previews).
+#[cfg(feature = "heic")]
+const HEIF_JPEG_QUALITY: u8 = 90;
+
+/// Decodes the primary image of a HEIC/HEIF file (rotation and mirroring from the file are
+/// applied by libheif) and re-encodes it as JPEG.
+#[cfg(feature = "heic")]
+pub fn heif_to_jpeg(bytes: &[u8]) -> Result<Vec<u8>, String> {
+    use libheif_rs::{ColorSpace, HeifContext, LibHeif, RgbChroma};
+
+    let ctx = HeifContext::read_from_bytes(bytes).map_err(|e| format!("HEIF: {}", e))?;
+    let handle = ctx
+        .primary_image_handle()
+        .map_err(|e| format!("HEIF: {}", e))?;
+    let image = LibHeif::new()
+        .decode(&handle, ColorSpace::Rgb(RgbChroma::Rgb), None)
+        .map_err(|e| format!("HEIF: {}", e))?;
+    let plane = image
+        .planes()
+        .interleaved
+        .ok_or_else(|| "HEIF: no RGB plane".to_string())?;
+    // Rows are stride bytes apart; keep only the width * 3 pixel bytes of each.
+    let row_len = plane.width as usize * 3;
+    let mut rgb = Vec::with_capacity(row_len * plane.height as usize);
+    for row in plane.data.chunks(plane.stride).take(plane.height as usize) {
+        rgb.extend_from_slice(row.get(..row_len).ok_or("HEIF: short row")?);
+    }
+    let img = image::RgbImage::from_raw(plane.width, plane.height, rgb)
+        .ok_or_else(|| "HEIF: invalid image size".to_string())?;
+    let mut out = Vec::new();
+    image::codecs::jpeg::JpegEncoder::new_with_quality(&mut out, HEIF_JPEG_QUALITY)
+        .encode_image(&img)
+        .map_err(|e| e.to_string())?;
+    Ok(out)
+}
+
+#[cfg(not(feature = "heic"))]
+pub fn heif_to_jpeg(_bytes: &[u8]) -> Result<Vec<u8>, String> {
+    Err("HEIC/HEIF support is not compiled in".to_string())
+}
@@ -1,9 +1,13 @@
 // Author: Viorel LUPU
 // Purpose: Open the Help window (static HTML from Project-V-See help_dialog.py).
+// Its position and size are remembered like the main and viewer windows.
 
+use super::persistence::{PersistenceState, HELP_WINDOW_GEOMETRY_KEY};
+use super::window_geometry::{restore_window_geometry, track_window_geometry};
 use tauri::{AppHandle, Manager, WebviewUrl, WebviewWindowBuilder};
 
-/// Opens the Help window. If it already exists, focuses it.
+/// Opens the Help window at its saved geometry (default size when none is saved or it is off
+/// screen). If it already exists, focuses it.
 #[tauri::command]
 pub async fn open_help_window(app: AppHandle) -> Result<(), String> {
     let label = "help";
@@ -12,12 +16,15 @@ pub async fn open_help_window(app: AppHandle) -> Result<(), String> {
         return Ok(());
     }
     let url = WebviewUrl::App("help.html".into());
-    WebviewWindowBuilder::new(&app, label, url)
+    let window = WebviewWindowBuilder::new(&app, label, url)
         .title("V-See – Help")
         .inner_size(560.0, 520.0)
         .min_inner_size(400.0, 300.0)
         .resizable(true)
         .build()
         .map_err(|e| e.to_string())?;
+    let persistence = app.state::<PersistenceState>();
+    restore_window_geometry(&window, &persistence, HELP_WINDOW_GEOMETRY_KEY);
+    track_window_geometry(&window, HELP_WINDOW_GEOMETRY_KEY);
     Ok(())
 }
@@ -0,0 +1,197 @@
+// Author: Viorel LUPU
+// Date: 2026-10-14
+// Purpose: Tells whether a GIF or WebP is animated so the frontend can pick the right player
+// UI. GIF block structure and WebP chunk headers are walked without decoding any pixels, and
+// the bytes inspected are bounded so a huge file costs no more than a small one.
+
+use super::fs::normalize_path;
+use serde::Serialize;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+
+/// Bytes of a GIF scanned for image descriptors; frame_count is None when the file is longer.
+const MAX_GIF_SCAN_BYTES: u64 = 4 * 1024 * 1024;
+
+/// WebP chunks visited before giving up on the frame count (each visit is one small read).
+const MAX_WEBP_CHUNKS: u32 = 100_000;
+
+/// Purpose: Animation and size of an image file.
+/// Usage: Returned by get_image_animation_info; frame_count is None when it could not be
+/// counted within the scan limit (the GIF is longer than MAX_GIF_SCAN_BYTES).
+/// Reason for choice: width/height come from the same headers, so the frontend can size the
+/// player without a second call.
+#[derive(Debug, Clone, Serialize)]
+pub struct ImageAnimationInfo {
+    pub animated: bool,
+    pub frame_count: Option<u32>,
+    pub width: u32,
+    pub height: u32,
+}
+
+fn u16_le(b: &[u8], at: usize) -> Option<u32> {
+    Some(u16::from_le_bytes([*b.get(at)?, *b.get(at + 1)?]) as u32)
+}
+
+fn u24_le(b: &[u8], at: usize) -> Option<u32> {
+    Some(u32::from_le_bytes([
+        *b.get(at)?,
+        *b.get(at + 1)?,
+        *b.get(at + 2)?,
+        0,
+    ]))
+}
+
+/// Skips a chain of GIF data sub-blocks starting at pos; returns the position after the
+/// zero-length terminator, or None if the buffer ends first.
+fn skip_sub_blocks(buf: &[u8], mut pos: usize) -> Option<usize> {
+    loop {
+        let len = *buf.get(pos)? as usize;
+        pos += 1;
+        if len == 0 {
+            return Some(pos);
+        }
+        pos += len;
+    }
+}
+
+/// Counts image descriptors in a GIF. buf holds the start of the file; the count is only
+/// reported as complete when the trailer is reached inside buf.
+fn gif_info(buf: &[u8]) -> Result<ImageAnimationInfo, String> {
+    let invalid = || "Invalid GIF header.".to_string();
+    let width = u16_le(buf, 6).ok_or_else(invalid)?;
+    let height = u16_le(buf, 8).ok_or_else(invalid)?;
+    let packed = *buf.get(10).ok_or_else(invalid)?;
+    let mut pos = 13;
+    if packed & 0x80 != 0 {
+        pos += 3 << ((packed & 0x07) + 1);
+    }
+    let mut frames: u32 = 0;
+    let mut complete = false;
+    while let Some(&block) = buf.get(pos) {
+        let next = match block {
+            // Image descriptor: 10 bytes, optional local color table, LZW code size, data.
+            0x2C => {
+                frames += 1;
+                let Some(&local) = buf.get(pos + 9) else {
+                    break;
+                };
+                let mut data = pos + 10;
+                if local & 0x80 != 0 {
+                    data += 3 << ((local & 0x07) + 1);
+                }
+                skip_sub_blocks(buf, data + 1)
+            }
+            // Extension: label byte, then sub-blocks.
+            0x21 => skip_sub_blocks(buf, pos + 2),
+            0x3B => {
+                complete = true;
+                break;
+            }
+            _ => break,
+        };
+        match next {
+            Some(next) => pos = next,
+            None => break,
+        }
+    }
+    Ok(ImageAnimationInfo {
+        animated: frames > 1,
+        frame_count: complete.then_some(frames),
+        width,
+        height,
+    })
+}
+
+/// Reads the WebP chunk headers: VP8X gives the animation flag and canvas size, ANMF chunks
+/// are the frames, and a simple (VP8 / VP8L) file is one still frame.
+fn webp_info(f: &mut File) -> Result<ImageAnimationInfo, String> {
+    let mut riff = [0u8; 12];
+    f.read_exact(&mut riff).map_err(|e| e.to_string())?;
+    if &riff[0..4] != b"RIFF" || &riff[8..12] != b"WEBP" {
+        return Err("Invalid WebP header.".to_string());
+    }
+    let mut info = ImageAnimationInfo {
+        animated: false,
+        frame_count: None,
+        width: 0,
+        height: 0,
+    };
+    let mut frames: u32 = 0;
+    for _ in 0..MAX_WEBP_CHUNKS {
+        let mut header = [0u8; 8];
+        if f.read_exact(&mut header).is_err() {
+            // End of file: every chunk was visited.
+            info.frame_count = Some(if info.animated { frames } else { 1 });
+            break;
+        }
+        let size = u32::from_le_bytes([header[4], header[5], header[6], header[7]]) as u64;
+        let padded = size + (size & 1);
+        let mut head = [0u8; 10];
+        let peek = (size as usize).min(head.len());
+        f.read_exact(&mut head[..peek]).map_err(|e| e.to_string())?;
+        match &header[0..4] {
+            b"VP8X" => {
+                info.animated = head[0] & 0x02 != 0;
+                info.width = u24_le(&head, 4).unwrap_or(0) + 1;
+                info.height = u24_le(&head, 7).unwrap_or(0) + 1;
+            }
+            b"VP8 " if info.width == 0 => {
+                info.width = u16_le(&head, 6).unwrap_or(0) & 0x3FFF;
+                info.height = u16_le(&head, 8).unwrap_or(0) & 0x3FFF;
+            }
+            b"VP8L" if info.width == 0 => {
+                let bits = u32::from_le_bytes([head[1], head[2], head[3], head[4]]);
+                info.width = (bits & 0x3FFF) + 1;
+                info.height = ((bits >> 14) & 0x3FFF) + 1;
+            }
+            b"ANMF" => frames += 1,
+            _ => {}
+        }
+        f.seek(SeekFrom::Current((padded - peek as u64) as i64))
+            .map_err(|e| e.to_string())?;
+    }
+    Ok(info)
+}
+
+fn animation_info(path: &Path) -> Result<ImageAnimationInfo, String> {
+    let mut f = File::open(path).map_err(|e| e.to_string())?;
+    let mut head = [0u8; 32];
+    let n = f.read(&mut head).map_err(|e| e.to_string())?;
+    let kind = infer::get(&head[..n]).filter(|t| t.matcher_type() == infer::MatcherType::Image);
+    match kind.map(|t| t.mime_type()) {
+        Some("image/gif") => {
+            let mut buf = Vec::new();
+            f.seek(SeekFrom::Start(0)).map_err(|e| e.to_string())?;
+            f.take(MAX_GIF_SCAN_BYTES)
+                .read_to_end(&mut buf)
+                .map_err(|e| e.to_string())?;
+            gif_info(&buf)
+        }
+        Some("image/webp") => {
+            f.seek(SeekFrom::Start(0)).map_err(|e| e.to_string())?;
+            webp_info(&mut f)
+        }
+        Some(_) => {
+            let (width, height) = image::image_dimensions(path).map_err(|e| e.to_string())?;
+            Ok(ImageAnimationInfo {
+                animated: false,
+                frame_count: Some(1),
+                width,
+                height,
+            })
+        }
+        None => Err("Not an image file.".to_string()),
+    }
+}
+
+/// Returns whether the image at path is animated (GIF with several frames, WebP with the
+/// animation flag), its frame count and size, reading only headers. Other images are reported
+/// as static; non-images are an error.
+#[tauri::command]
+pub async fn get_image_animation_info(path: String) -> Result<ImageAnimationInfo, String> {
+    let p = normalize_path(&path)?;
+    tauri::async_runtime::spawn_blocking(move || animation_info(&p))
+        .await
+        .map_err(|e| e.to_string())?
+}
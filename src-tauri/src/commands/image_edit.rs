@@ -0,0 +1,112 @@
+// Author: Viorel LUPU
+// Date: 2026-10-14
+// Purpose: Edits that write back to image files (currently: permanent rotation).
+// Output goes to a temp file in the same folder and is renamed over the original, so a crash
+// or full disk never leaves a half-written photo behind.
+
+use super::fs::normalize_path;
+use image::codecs::jpeg::JpegEncoder;
+use image::metadata::Orientation;
+use image::{DynamicImage, ImageDecoder, ImageFormat, ImageReader};
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+
+/// JPEG quality when re-encoding a rotated photo (high, to keep generation loss small).
+const ROTATE_JPEG_QUALITY: u8 = 95;
+
+/// Formats rotate_image_file can re-encode in place without losing frames.
+const ROTATABLE_FORMATS: [ImageFormat; 5] = [
+    ImageFormat::Jpeg,
+    ImageFormat::Png,
+    ImageFormat::Bmp,
+    ImageFormat::Tiff,
+    ImageFormat::WebP,
+];
+
+/// Temp file next to path (same volume, so the final rename is atomic).
+fn temp_path_for(path: &Path) -> PathBuf {
+    let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("image");
+    path.with_file_name(format!(".{}.v-see-tmp", name))
+}
+
+/// Encodes img as format into path; JPEG keeps the original EXIF and ICC profile.
+fn write_image(
+    img: &DynamicImage,
+    format: ImageFormat,
+    path: &Path,
+    exif: Option<Vec<u8>>,
+    icc: Option<Vec<u8>>,
+) -> Result<(), String> {
+    let file = File::create(path).map_err(|e| e.to_string())?;
+    let mut writer = BufWriter::new(file);
+    if format == ImageFormat::Jpeg {
+        use image::ImageEncoder;
+        let mut encoder = JpegEncoder::new_with_quality(&mut writer, ROTATE_JPEG_QUALITY);
+        if let Some(exif) = exif {
+            let _ = encoder.set_exif_metadata(exif);
+        }
+        if let Some(icc) = icc {
+            let _ = encoder.set_icc_profile(icc);
+        }
+        img.write_with_encoder(encoder).map_err(|e| e.to_string())?;
+    } else {
+        img.write_to(&mut writer, format)
+            .map_err(|e| e.to_string())?;
+    }
+    let file = writer.into_inner().map_err(|e| e.to_string())?;
+    file.sync_all().map_err(|e| e.to_string())
+}
+
+/// Rotates the image as it is displayed (EXIF orientation applied) by degrees clockwise,
+/// writes it in its original format, and resets the EXIF orientation to normal.
+fn rotate_file(path: &Path, degrees: i32) -> Result<(), String> {
+    let reader = ImageReader::open(path)
+        .map_err(|e| e.to_string())?
+        .with_guessed_format()
+        .map_err(|e| e.to_string())?;
+    let format = reader
+        .format()
+        .filter(|f| ROTATABLE_FORMATS.contains(f))
+        .ok_or_else(|| "Rotation is not supported for this image format.".to_string())?;
+    let mut decoder = reader.into_decoder().map_err(|e| e.to_string())?;
+    let orientation = decoder.orientation().unwrap_or(Orientation::NoTransforms);
+    let mut exif = decoder.exif_metadata().ok().flatten();
+    let icc = decoder.icc_profile().ok().flatten();
+    let mut img = DynamicImage::from_decoder(decoder).map_err(|e| e.to_string())?;
+    img.apply_orientation(orientation);
+    let img = match degrees {
+        90 => img.rotate90(),
+        180 => img.rotate180(),
+        _ => img.rotate270(),
+    };
+    if let Some(chunk) = exif.as_mut() {
+        // Pixels are now upright; leaving the old tag would rotate them a second time.
+        let _ = Orientation::remove_from_exif_chunk(chunk);
+    }
+    let tmp = temp_path_for(path);
+    let result = write_image(&img, format, &tmp, exif, icc)
+        .and_then(|_| std::fs::rename(&tmp, path).map_err(|e| e.to_string()));
+    if result.is_err() {
+        let _ = std::fs::remove_file(&tmp);
+    }
+    result
+}
+
+/// Permanently rotates the image file at path by 90, 180, or 270 degrees clockwise.
+/// JPEG, PNG, BMP, TIFF, and WebP are re-encoded in their own format (JPEG keeps its EXIF
+/// data with the orientation reset); other formats return an error.
+#[tauri::command]
+pub async fn rotate_image_file(path: String, degrees: i32) -> Result<(), String> {
+    let path_buf = normalize_path(&path)?;
+    if !path_buf.is_file() {
+        return Err("Path is not a file.".to_string());
+    }
+    let degrees = degrees.rem_euclid(360);
+    if !matches!(degrees, 90 | 180 | 270) {
+        return Err("Rotation must be 90, 180, or 270 degrees.".to_string());
+    }
+    tauri::async_runtime::spawn_blocking(move || rotate_file(&path_buf, degrees))
+        .await
+        .map_err(|e| e.to_string())?
+}
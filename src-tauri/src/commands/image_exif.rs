@@ -0,0 +1,161 @@
+// Author: Viorel LUPU
+// Date: 2026-10-14
+// Purpose: EXIF capture metadata (camera, lens, exposure, date, GPS) for the viewer info panel.
+// Parsed with kamadak-exif; files without EXIF return an empty result instead of an error.
+
+use super::fs::normalize_path;
+use exif::{Exif, In, Reader, Tag, Value};
+use serde::Serialize;
+use std::fs::File;
+use std::io::BufReader;
+
+/// Purpose: Capture metadata shown in the viewer info panel.
+/// Usage: Returned by get_image_exif; every field is None when the tag is absent.
+/// Reason for choice: Numbers stay numeric (focal length in mm, f-number, ISO) so the UI can format
+/// them; gps is (latitude, longitude) in decimal degrees, ready for a map link.
+#[derive(Debug, Default, Serialize)]
+pub struct ImageExif {
+    pub camera_make: Option<String>,
+    pub camera_model: Option<String>,
+    pub lens: Option<String>,
+    pub focal_length: Option<f64>,
+    pub aperture: Option<f64>,
+    /// Exposure time as shown on cameras, e.g. "1/250" or "2".
+    pub shutter_speed: Option<String>,
+    pub iso: Option<u32>,
+    /// "YYYY-MM-DD HH:MM:SS" from DateTimeOriginal (or DateTime), camera local time.
+    pub date_taken: Option<String>,
+    pub gps: Option<(f64, f64)>,
+}
+
+/// Purpose: Result of get_image_metadata: the same EXIF values as ImageExif, flat.
+/// Usage: Viewer metadata overlay; missing tags (or no EXIF at all) are None.
+/// Reason for choice: GPS is split into gps_lat / gps_lon and the exposure fields use the EXIF
+/// tag names, which is what get_image_metadata callers expect.
+#[derive(Debug, Default, Serialize)]
+pub struct ImageMetadata {
+    pub camera_make: Option<String>,
+    pub camera_model: Option<String>,
+    pub lens: Option<String>,
+    pub iso: Option<u32>,
+    /// As shown on cameras, e.g. "1/250" or "2".
+    pub exposure_time: Option<String>,
+    pub f_number: Option<f64>,
+    /// Millimetres.
+    pub focal_length: Option<f64>,
+    /// "YYYY-MM-DD HH:MM:SS", camera local time.
+    pub date_taken: Option<String>,
+    pub gps_lat: Option<f64>,
+    pub gps_lon: Option<f64>,
+}
+
+/// Trimmed ASCII value of a tag (EXIF strings are often NUL/space padded).
+fn ascii(exif: &Exif, tag: Tag) -> Option<String> {
+    let field = exif.get_field(tag, In::PRIMARY)?;
+    match &field.value {
+        Value::Ascii(parts) => {
+            let text = parts
+                .iter()
+                .map(|p| String::from_utf8_lossy(p).into_owned())
+                .collect::<Vec<_>>()
+                .join(" ");
+            let text = text.trim_matches(|c: char| c == '\0' || c.is_whitespace());
+            (!text.is_empty()).then(|| text.to_string())
+        }
+        _ => None,
+    }
+}
+
+/// First rational of a tag as f64 (None for zero denominators).
+fn rational(exif: &Exif, tag: Tag) -> Option<f64> {
+    match &exif.get_field(tag, In::PRIMARY)?.value {
+        Value::Rational(v) => v.first().filter(|r| r.denom != 0).map(|r| r.to_f64()),
+        _ => None,
+    }
+}
+
+/// Exposure time as "1/N" below one second, else seconds ("2", "0.8" style values rounded).
+fn shutter_speed(exif: &Exif) -> Option<String> {
+    let field = exif.get_field(Tag::ExposureTime, In::PRIMARY)?;
+    let r = match &field.value {
+        Value::Rational(v) => *v.first()?,
+        _ => return None,
+    };
+    if r.num == 0 || r.denom == 0 {
+        return None;
+    }
+    let secs = r.to_f64();
+    if secs < 1.0 {
+        Some(format!("1/{}", (1.0 / secs).round() as u64))
+    } else if secs.fract() == 0.0 {
+        Some(format!("{}", secs as u64))
+    } else {
+        Some(format!("{:.1}", secs))
+    }
+}
+
+/// Converts a GPS degrees/minutes/seconds triple plus N/S/E/W reference to signed decimal degrees.
+fn gps_coord(exif: &Exif, tag: Tag, ref_tag: Tag, negative_ref: &str) -> Option<f64> {
+    let dms = match &exif.get_field(tag, In::PRIMARY)?.value {
+        Value::Rational(v) if v.len() >= 3 && v.iter().all(|r| r.denom != 0) => v.clone(),
+        _ => return None,
+    };
+    let degrees = dms[0].to_f64() + dms[1].to_f64() / 60.0 + dms[2].to_f64() / 3600.0;
+    let negative = ascii(exif, ref_tag).is_some_and(|r| r.eq_ignore_ascii_case(negative_ref));
+    Some(if negative { -degrees } else { degrees })
+}
+
+/// EXIF DateTime "YYYY:MM:DD HH:MM:SS" with the date separators turned into dashes.
+fn date_taken(exif: &Exif) -> Option<String> {
+    let raw = ascii(exif, Tag::DateTimeOriginal).or_else(|| ascii(exif, Tag::DateTime))?;
+    match raw.split_once(' ') {
+        Some((date, time)) => Some(format!("{} {}", date.replace(':', "-"), time)),
+        None => Some(raw.replace(':', "-")),
+    }
+}
+
+/// Returns camera, lens, ISO, exposure, capture date and GPS position from the image's EXIF.
+/// Files without EXIF (including non-images) return all fields empty; only an unreadable
+/// path is an error.
+#[tauri::command]
+pub fn get_image_metadata(path: String) -> Result<ImageMetadata, String> {
+    let p = normalize_path(&path)?;
+    let file = File::open(&p).map_err(|e| e.to_string())?;
+    let exif = match Reader::new().read_from_container(&mut BufReader::new(file)) {
+        Ok(exif) => exif,
+        Err(_) => return Ok(ImageMetadata::default()),
+    };
+    let iso = exif
+        .get_field(Tag::PhotographicSensitivity, In::PRIMARY)
+        .and_then(|f| f.value.get_uint(0));
+    Ok(ImageMetadata {
+        camera_make: ascii(&exif, Tag::Make),
+        camera_model: ascii(&exif, Tag::Model),
+        lens: ascii(&exif, Tag::LensModel),
+        iso,
+        exposure_time: shutter_speed(&exif),
+        f_number: rational(&exif, Tag::FNumber),
+        focal_length: rational(&exif, Tag::FocalLength),
+        date_taken: date_taken(&exif),
+        gps_lat: gps_coord(&exif, Tag::GPSLatitude, Tag::GPSLatitudeRef, "S"),
+        gps_lon: gps_coord(&exif, Tag::GPSLongitude, Tag::GPSLongitudeRef, "W"),
+    })
+}
+
+/// Returns camera settings, capture date, and GPS position from the image's EXIF data.
+/// Images without EXIF (or with unreadable EXIF) return all fields empty.
+#[tauri::command]
+pub fn get_image_exif(path: String) -> Result<ImageExif, String> {
+    let m = get_image_metadata(path)?;
+    Ok(ImageExif {
+        camera_make: m.camera_make,
+        camera_model: m.camera_model,
+        lens: m.lens,
+        focal_length: m.focal_length,
+        aperture: m.f_number,
+        shutter_speed: m.exposure_time,
+        iso: m.iso,
+        date_taken: m.date_taken,
+        gps: m.gps_lat.zip(m.gps_lon),
+    })
+}
@@ -0,0 +1,83 @@
+// Author: Viorel LUPU
+// Date: 2026-10-14
+// Purpose: Image thumbnails for the grid. Returns a downscaled data URL together with the
+// original dimensions so the frontend can reserve the right aspect ratio before it loads.
+
+use super::fs::normalize_path;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use image::{ImageFormat, ImageReader};
+use serde::Serialize;
+use std::io::Cursor;
+use std::path::Path;
+
+/// Longest thumbnail edge in pixels when the caller does not pass max_edge.
+pub const DEFAULT_THUMB_MAX_EDGE: u32 = 256;
+
+/// Purpose: Result of get_image_thumbnail.
+/// Usage: Grid sets the cell aspect ratio from orig_width/orig_height, then shows data_url.
+/// Reason for choice: Dimensions come from the file header, so they describe the original,
+/// not the thumbnail (first frame for animated GIFs).
+#[derive(Debug, Serialize)]
+pub struct ImageThumbnail {
+    pub data_url: String,
+    pub orig_width: u32,
+    pub orig_height: u32,
+}
+
+/// Reads dimensions from the header, then decodes (first frame only for GIF), scales down,
+/// and encodes as JPEG, or PNG when the image has transparency.
+pub fn make_image_thumbnail(path: &Path, max_edge: u32) -> Result<ImageThumbnail, String> {
+    let reader = ImageReader::open(path)
+        .map_err(|e| e.to_string())?
+        .with_guessed_format()
+        .map_err(|e| e.to_string())?;
+    let (orig_width, orig_height) = reader.into_dimensions().map_err(|e| e.to_string())?;
+    let img = ImageReader::open(path)
+        .map_err(|e| e.to_string())?
+        .with_guessed_format()
+        .map_err(|e| e.to_string())?
+        .decode()
+        .map_err(|e| e.to_string())?;
+    let thumb = if img.width().max(img.height()) > max_edge {
+        img.thumbnail(max_edge, max_edge)
+    } else {
+        img
+    };
+    let mut bytes = Vec::new();
+    let mime = if thumb.color().has_alpha() {
+        thumb
+            .write_to(&mut Cursor::new(&mut bytes), ImageFormat::Png)
+            .map_err(|e| e.to_string())?;
+        "image/png"
+    } else {
+        thumb
+            .into_rgb8()
+            .write_to(&mut Cursor::new(&mut bytes), ImageFormat::Jpeg)
+            .map_err(|e| e.to_string())?;
+        "image/jpeg"
+    };
+    Ok(ImageThumbnail {
+        data_url: format!("data:{};base64,{}", mime, STANDARD.encode(&bytes)),
+        orig_width,
+        orig_height,
+    })
+}
+
+/// Returns a thumbnail (longest edge max_edge, default 256) plus the original image size.
+/// Decoding runs on a background thread so large folders don't block other commands.
+#[tauri::command]
+pub async fn get_image_thumbnail(
+    path: String,
+    max_edge: Option<u32>,
+) -> Result<ImageThumbnail, String> {
+    let path_buf = normalize_path(&path)?;
+    if !path_buf.is_file() {
+        return Err("Path is not a file.".to_string());
+    }
+    let max_edge = max_edge
+        .filter(|&e| e > 0)
+        .unwrap_or(DEFAULT_THUMB_MAX_EDGE);
+    tauri::async_runtime::spawn_blocking(move || make_image_thumbnail(&path_buf, max_edge))
+        .await
+        .map_err(|e| e.to_string())?
+}
@@ -3,17 +3,108 @@
 // Purpose: Tauri command handlers module
 
 mod audio;
+mod audio_levels;
+mod audio_waveform;
+mod cache;
+mod cancel;
+mod clipboard;
 mod debug_log;
+mod diagnostics;
+mod drive;
+mod duplicates;
+mod external_apps;
+mod ffmpeg_locate;
+mod file_ops;
+mod folders;
+mod formats;
 mod fs;
+mod fs_watch;
+mod heif_decode;
 mod help;
+mod image_anim;
+mod image_edit;
+mod image_exif;
+mod image_thumb;
 mod persistence;
+mod play_history;
+mod playlists;
+mod raw_preview;
+mod settings;
+mod slideshow;
+mod svg_render;
+mod text_preview;
+mod thumbnail_prefetch;
 mod video_thumb;
 mod viewer;
+mod window_geometry;
 
-pub use audio::{play_audio, stop_audio, pause_audio, AudioState};
-pub use debug_log::{debug_log, get_debug_log_path};
-pub use fs::{get_folder_roots, get_parent_path, list_directory, read_file_as_audio_url, read_file_as_data_url};
+pub use audio::{
+    clear_loop_region, enqueue, get_audio_device_status, get_playback_speed, get_playback_status,
+    get_volume, list_audio_devices, pause_audio, play_audio, reinit_audio_device, resume_last_track,
+    set_audio_device, set_loop_region, set_playback_speed, set_queue, set_volume, skip_next,
+    stop_audio, AudioState,
+};
+pub use audio_waveform::get_audio_waveform;
+pub use cache::{clear_all_caches, clear_thumbnail_cache, get_cache_dir, set_cache_dir};
+pub use cancel::{cancel_operation, CancellationState};
+pub use clipboard::copy_text_to_clipboard;
+pub use debug_log::{
+    apply_log_format_env, debug_log, get_debug_log_path, get_recent_log_lines, set_log_format,
+    set_log_level,
+};
+pub use diagnostics::run_diagnostics;
+pub use drive::flush_drive;
+pub use duplicates::{compare_files, find_duplicate_images};
+pub use external_apps::{get_external_app, open_in_external_app, set_external_app};
+pub use ffmpeg_locate::{get_ffmpeg_status, init_ffmpeg_locations};
+pub use file_ops::{
+    copy_path, create_directory, delete_paths_to_recycle_bin, delete_to_trash, move_paths,
+    rename_path,
+};
+pub use folders::{
+    add_favorite_folder, get_recent_folders, list_favorite_folders, push_recent_folder,
+    remove_favorite_folder,
+};
+pub use formats::get_supported_formats;
+pub use fs::{
+    get_directory_stats, get_file_info, get_folder_roots, get_image_preview_data_url,
+    get_parent_path, glob_files, list_directory, list_directory_page, list_directory_streaming,
+    path_accessible, read_file_as_audio_url, read_file_as_data_url, read_file_range,
+    scan_media_recursive,
+};
+pub use fs_watch::{unwatch_directory, watch_directory, WatchState};
 pub use help::open_help_window;
-pub use persistence::{get_all_persisted, get_persistence_db_path, get_persisted, set_persisted, PersistenceState};
-pub use video_thumb::get_video_thumbnail_data_url;
-pub use viewer::{get_viewer_context, open_viewer_window, viewer_next, viewer_prev, ViewerState};
+pub use image_anim::get_image_animation_info;
+pub use image_edit::rotate_image_file;
+pub use image_exif::{get_image_exif, get_image_metadata};
+pub use image_thumb::get_image_thumbnail;
+pub use persistence::{
+    backup_persistence, check_persistence_integrity, clear_persisted, delete_persisted,
+    get_all_persisted, get_persisted, get_persisted_json, get_persistence_db_path,
+    restore_persistence, set_persisted, set_persisted_debounced, set_persisted_json,
+    set_persisted_many, AUDIO_DEVICE_KEY, MAIN_WINDOW_GEOMETRY_KEY, PLAYBACK_SPEED_KEY,
+    PLAYBACK_VOLUME_KEY, PersistenceState,
+};
+pub use play_history::get_play_history;
+pub use playlists::{list_playlists, load_playlist, save_playlist};
+pub use raw_preview::get_raw_preview_data_url;
+pub use settings::{
+    get_slideshow_interval, get_slideshow_music_enabled, set_slideshow_interval,
+    set_slideshow_music_enabled,
+};
+pub use slideshow::{
+    set_slideshow_paused, slideshow_is_running, start_slideshow, stop_slideshow, SlideshowState,
+};
+pub use text_preview::read_text_file;
+pub use thumbnail_prefetch::prefetch_thumbnails;
+pub use video_thumb::{
+    check_external_tools, get_video_thumbnail_at_percent, get_video_thumbnail_data_url,
+    get_video_thumbnail_strip, set_thumbnail_concurrency,
+};
+pub use viewer::{
+    get_viewer_context, get_viewer_view_state, open_viewer_window, set_viewer_fullscreen,
+    set_viewer_view_state, viewer_current, viewer_jump_to_path, viewer_next, viewer_prev,
+    viewer_remove_current, viewer_set_index, viewer_set_mode, viewer_toggle_fullscreen,
+    viewer_update_paths, ViewerState,
+};
+pub use window_geometry::{restore_window_geometry, track_window_geometry};
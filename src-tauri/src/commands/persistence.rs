@@ -1,104 +1,655 @@
 // Author: Viorel LUPU
 // Date: 2026-02-17
 // Purpose: SQLite persistence for V-See Windows. Mirrors Project-V-See persistence.py:
-// key-value store in app_state (key TEXT PRIMARY KEY, value TEXT). Location: config dir
-// next to app (portable) or APPDATA on Windows for installed app.
+// key-value store in app_state (key TEXT PRIMARY KEY, value TEXT). Location: Tauri's app data
+// dir (e.g. %APPDATA%\com.vsee.windows\state.db); older installs under APPDATA\V-See are copied over.
+// WAL journal + busy timeout so the main and viewer windows can write concurrently.
+// One connection is opened on first use and shared by every command behind a mutex.
+// Backups are taken with SQLite's online backup API into a backups folder next to state.db.
 
-use rusqlite::Connection;
+use rusqlite::{
+    Connection, DatabaseName, ErrorCode, OpenFlags, Transaction, TransactionBehavior,
+};
 use serde::Serialize;
-use std::path::PathBuf;
-use tauri::State;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, OnceLock};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Manager, State};
 
 /// Keys matching Project-V-See persistence.py (used by frontend; kept for reference).
 #[allow(dead_code)]
 pub const LAST_FOLDER_KEY: &str = "last_folder";
 #[allow(dead_code)]
 pub const LAST_MUSIC_FOLDER_KEY: &str = "last_music_folder";
-#[allow(dead_code)]
 pub const MAIN_WINDOW_GEOMETRY_KEY: &str = "main_window_geometry";
-#[allow(dead_code)]
 pub const VIEWER_WINDOW_GEOMETRY_KEY: &str = "viewer_window_geometry";
-#[allow(dead_code)]
+pub const HELP_WINDOW_GEOMETRY_KEY: &str = "help_window_geometry";
+/// Whole seconds (1–3600); read through get_slideshow_interval.
 pub const SLIDESHOW_INTERVAL_SECONDS_KEY: &str = "slideshow_interval_seconds";
-#[allow(dead_code)]
+/// "true"/"false"; read through get_slideshow_music_enabled.
 pub const SLIDESHOW_MUSIC_KEY: &str = "slideshow_music";
 #[allow(dead_code)]
 pub const SLIDESHOW_VIDEO_DURATION_KEY: &str = "slideshow_video_duration";
 #[allow(dead_code)]
 pub const LAST_SELECTED_FILE_KEY: &str = "last_selected_file";
-#[allow(dead_code)]
 pub const LAST_SELECTED_TRACK_KEY: &str = "last_selected_track";
+/// Playback position in seconds within last_selected_track, saved on pause/stop.
+pub const LAST_TRACK_POSITION_KEY: &str = "last_track_position";
+/// Last viewer zoom/fit/pan as JSON (ViewerViewState).
+pub const VIEWER_VIEW_STATE_KEY: &str = "viewer_view_state";
+/// "true"/"false": whether the viewer was last left in fullscreen.
+pub const VIEWER_FULLSCREEN_KEY: &str = "viewer_fullscreen";
+/// Last audio playback speed (e.g. "1.5"), applied to the next track and on startup.
+pub const PLAYBACK_SPEED_KEY: &str = "playback_speed";
+/// Last audio volume from 0.0 to 1.0 (e.g. "0.8"), applied on startup.
+pub const PLAYBACK_VOLUME_KEY: &str = "playback_volume";
+/// Name of the chosen audio output device; absent or empty means the system default.
+pub const AUDIO_DEVICE_KEY: &str = "audio_device";
+/// Recent folders, most recent first (JSON array of paths).
+pub const RECENT_FOLDERS_KEY: &str = "recent_folders";
+/// Pinned favorite folders in the order added (JSON array of paths).
+pub const FAVORITE_FOLDERS_KEY: &str = "favorite_folders";
+/// User-chosen cache directory; absent means the default app cache dir.
+pub const CACHE_DIR_KEY: &str = "cache_dir";
+/// Programs chosen to open images / videos instead of the OS default (absolute exe path).
+pub const EXTERNAL_IMAGE_APP_KEY: &str = "external_image_app";
+pub const EXTERNAL_VIDEO_APP_KEY: &str = "external_video_app";
+/// Recently played tracks, most recent first (JSON array of {path, played_at_unix}).
+pub const PLAY_HISTORY_KEY: &str = "play_history";
+/// Saved playlists are stored as "playlist:<name>" (JSON array of track paths).
+pub const PLAYLIST_KEY_PREFIX: &str = "playlist:";
 
-fn db_path() -> Result<PathBuf, String> {
+/// Pre-resolver location: APPDATA/V-See/state.db (Windows) or HOME/.config/v-see/state.db.
+/// Used when Tauri's path resolver fails, and as the source when migrating an existing install.
+fn legacy_db_path() -> Result<PathBuf, String> {
     #[cfg(target_os = "windows")]
     {
         let appdata = std::env::var("APPDATA").map_err(|_| "APPDATA not set".to_string())?;
-        let dir = PathBuf::from(appdata).join("V-See");
-        std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
-        Ok(dir.join("state.db"))
+        Ok(PathBuf::from(appdata).join("V-See").join("state.db"))
     }
     #[cfg(not(target_os = "windows"))]
     {
         let home = std::env::var("HOME").map_err(|_| "HOME not set".to_string())?;
-        let dir = PathBuf::from(home).join(".config").join("v-see");
-        std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
-        Ok(dir.join("state.db"))
+        Ok(PathBuf::from(home).join(".config").join("v-see").join("state.db"))
     }
 }
 
-fn ensure_schema(conn: &Connection) -> Result<(), String> {
-    conn.execute(
-        "CREATE TABLE IF NOT EXISTS app_state (key TEXT PRIMARY KEY, value TEXT)",
-        [],
-    )
-    .map_err(|e| e.to_string())?;
+/// How long SQLite itself waits on a lock held by another connection (main + viewer windows).
+const BUSY_TIMEOUT: Duration = Duration::from_millis(3000);
+
+/// Extra attempts when an operation still fails with SQLITE_BUSY/LOCKED after the timeout.
+const BUSY_RETRIES: u32 = 3;
+const BUSY_RETRY_DELAY: Duration = Duration::from_millis(100);
+
+/// Schema migrations in order; after applying MIGRATIONS[i] the database is at version i + 1
+/// (stored in PRAGMA user_version). Databases from before versioning report 0 but already
+/// have app_state, which is why version 1 uses IF NOT EXISTS and keeps their rows.
+/// To add version 2, append its SQL here; never edit or reorder an entry that has shipped.
+const MIGRATIONS: &[&str] = &[
+    // 1: flat key-value store.
+    "CREATE TABLE IF NOT EXISTS app_state (key TEXT PRIMARY KEY, value TEXT)",
+];
+
+/// Brings the schema up to the latest version, one migration per transaction, so a failure
+/// leaves the database at the last version that applied cleanly.
+fn ensure_schema(conn: &Connection) -> rusqlite::Result<()> {
+    let current: usize = conn.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+    for (index, sql) in MIGRATIONS.iter().enumerate().skip(current) {
+        let tx = conn.unchecked_transaction()?;
+        tx.execute_batch(sql)?;
+        tx.pragma_update(None, "user_version", index + 1)?;
+        tx.commit()?;
+    }
+    Ok(())
+}
+
+/// Opens state.db in WAL mode (readers don't block the writer; -wal/-shm files sit next to
+/// state.db) with a busy timeout, and makes sure the table exists.
+fn open_connection(path: &Path) -> rusqlite::Result<Connection> {
+    let conn = Connection::open(path)?;
+    conn.busy_timeout(BUSY_TIMEOUT)?;
+    // journal_mode is persistent in the file and the pragma returns the resulting mode as a row.
+    conn.query_row("PRAGMA journal_mode=WAL", [], |_| Ok(()))?;
+    ensure_schema(&conn)?;
+    Ok(conn)
+}
+
+/// Folder next to state.db holding state-<unix millis>.db backups.
+const BACKUP_DIR_NAME: &str = "backups";
+const BACKUP_FILE_PREFIX: &str = "state-";
+
+/// Backups kept by backup(); older ones are deleted after each new backup.
+const MAX_BACKUPS: usize = 5;
+
+/// Checks that path is a readable, intact SQLite database with the app_state table, without
+/// modifying it.
+fn validate_backup(path: &Path) -> Result<(), String> {
+    if !path.is_file() {
+        return Err("Backup file not found.".to_string());
+    }
+    let conn = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+        .map_err(|e| format!("Not a readable database: {}", e))?;
+    let check: String = conn
+        .query_row("PRAGMA quick_check", [], |row| row.get(0))
+        .map_err(|e| format!("Not a readable database: {}", e))?;
+    if check != "ok" {
+        return Err(format!("Backup is damaged: {}", check));
+    }
+    let tables: i64 = conn
+        .query_row(
+            "SELECT count(*) FROM sqlite_master WHERE type = 'table' AND name = 'app_state'",
+            [],
+            |row| row.get(0),
+        )
+        .map_err(|e| e.to_string())?;
+    if tables == 0 {
+        return Err("Not a V-See settings backup.".to_string());
+    }
     Ok(())
 }
 
-pub struct PersistenceState {}
+/// Deletes all but the newest MAX_BACKUPS backups in dir. Names embed the creation time with
+/// the same number of digits, so name order is age order.
+fn prune_backups(dir: &Path) {
+    let Ok(read) = std::fs::read_dir(dir) else {
+        return;
+    };
+    let mut backups: Vec<PathBuf> = read
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| {
+            p.file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.starts_with(BACKUP_FILE_PREFIX) && n.ends_with(".db"))
+        })
+        .collect();
+    backups.sort();
+    let excess = backups.len().saturating_sub(MAX_BACKUPS);
+    for old in backups.into_iter().take(excess) {
+        let _ = std::fs::remove_file(old);
+    }
+}
+
+/// Newest state-*.db in dir (see prune_backups for why name order is age order).
+fn newest_backup(dir: &Path) -> Option<PathBuf> {
+    std::fs::read_dir(dir)
+        .ok()?
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| {
+            p.file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.starts_with(BACKUP_FILE_PREFIX) && n.ends_with(".db"))
+        })
+        .max()
+}
+
+/// True if neither state.db nor its -wal file was modified after backup was written, so a
+/// new backup would only duplicate it (and push an older, different one out of rotation).
+fn unchanged_since(db_path: &Path, backup: &Path) -> bool {
+    let modified = |p: &Path| std::fs::metadata(p).and_then(|m| m.modified()).ok();
+    let Some(backed_up) = modified(backup) else {
+        return false;
+    };
+    let mut wal = db_path.as_os_str().to_owned();
+    wal.push("-wal");
+    [db_path, Path::new(&wal)]
+        .iter()
+        .all(|p| modified(p).is_none_or(|m| m <= backed_up))
+}
+
+/// True for transient lock errors that are worth retrying.
+fn is_busy(e: &rusqlite::Error) -> bool {
+    matches!(
+        e.sqlite_error_code(),
+        Some(ErrorCode::DatabaseBusy) | Some(ErrorCode::DatabaseLocked)
+    )
+}
+
+/// Default delay for set_persisted_debounced and window geometry saves.
+pub const DEFAULT_DEBOUNCE_MS: u64 = 500;
+
+/// Upper bound for set_persisted_debounced delays so a pending value can't sit unsaved for long.
+const MAX_DEBOUNCE_DELAY: Duration = Duration::from_secs(10);
+
+type PendingWrites = HashMap<String, (String, Instant)>;
+
+/// Purpose: Debounced writes waiting to be flushed: key -> (latest value, flush deadline).
+/// Usage: Filled by set_debounced, drained by the flusher thread and by flush_pending on exit.
+/// Reason for choice: One timer thread waiting on a Condvar for the earliest deadline instead of
+/// a thread per update, since window drags produce dozens of updates per second.
+#[derive(Default)]
+struct DebouncedWrites {
+    pending: Mutex<PendingWrites>,
+    changed: Condvar,
+}
+
+/// Where state.db lives: resolved through the app (normal runs) or a fixed file (tests).
+enum DbLocation {
+    App(AppHandle),
+    #[cfg(test)]
+    File(PathBuf),
+}
+
+/// Purpose: Access to the key-value store in state.db.
+/// Usage: Managed by Tauri (created in setup); commands call get/set or with_conn.
+/// Reason for choice: Holding the AppHandle lets the DB live in Tauri's app data dir on every platform.
+/// The connection is opened lazily so new() can't fail, then reused instead of reopening
+/// state.db (and re-running the pragmas) on every get/set.
+pub struct PersistenceState {
+    location: DbLocation,
+    conn: Mutex<Option<Connection>>,
+    debounced: Arc<DebouncedWrites>,
+    flusher: OnceLock<()>,
+}
 
 impl PersistenceState {
-    pub fn new() -> Self {
-        PersistenceState {}
+    /// Creates the state and, on first run with the resolver, copies a legacy state.db over
+    /// so existing installs keep their settings.
+    pub fn new(app: AppHandle) -> Self {
+        let state = PersistenceState {
+            location: DbLocation::App(app),
+            conn: Mutex::new(None),
+            debounced: Arc::new(DebouncedWrites::default()),
+            flusher: OnceLock::new(),
+        };
+        if let (Ok(path), Ok(legacy)) = (state.db_path(), legacy_db_path()) {
+            if path != legacy && !path.exists() && legacy.is_file() {
+                let _ = std::fs::copy(&legacy, &path);
+            }
+        }
+        state
+    }
+
+    /// state.db inside Tauri's app data dir (created if needed); falls back to the legacy
+    /// env-var location if the resolver fails.
+    pub fn db_path(&self) -> Result<PathBuf, String> {
+        let path = match &self.location {
+            DbLocation::App(app) => match app.path().app_data_dir() {
+                Ok(dir) => dir.join("state.db"),
+                Err(_) => legacy_db_path()?,
+            },
+            #[cfg(test)]
+            DbLocation::File(path) => path.clone(),
+        };
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+        }
+        Ok(path)
     }
 
+    /// State over the database at path, without an app; the debounced flusher is not started.
+    #[cfg(test)]
+    fn at_path(path: PathBuf) -> Self {
+        PersistenceState {
+            location: DbLocation::File(path),
+            conn: Mutex::new(None),
+            debounced: Arc::new(DebouncedWrites::default()),
+            flusher: OnceLock::new(),
+        }
+    }
+
+    /// Checks that state.db accepts writes: a test row is inserted inside a transaction that
+    /// is rolled back, so stored settings are never touched. A failed INSERT rolls back when
+    /// the transaction drops, so it can't stay open on the shared connection.
+    pub fn check_writable(&self) -> Result<(), String> {
+        self.with_conn(|conn| {
+            let tx = Transaction::new_unchecked(conn, TransactionBehavior::Immediate)?;
+            tx.execute(
+                "INSERT OR REPLACE INTO app_state (key, value) VALUES ('__write_test__', '')",
+                [],
+            )?;
+            tx.rollback()
+        })
+    }
+
+    /// Runs f on the shared connection (opened on first use), retrying a few times if the
+    /// database stays busy. The connection is unlocked while waiting between attempts so other
+    /// commands are not stalled behind the retry.
     fn with_conn<T, F>(&self, f: F) -> Result<T, String>
     where
-        F: FnOnce(&Connection) -> Result<T, String>,
+        F: Fn(&Connection) -> rusqlite::Result<T>,
     {
-        let path = db_path()?;
-        let conn = Connection::open(path).map_err(|e| e.to_string())?;
-        ensure_schema(&conn)?;
-        let out = f(&conn)?;
-        Ok(out)
+        let mut attempt = 0;
+        loop {
+            let result = {
+                let mut guard = self.conn.lock().map_err(|e| e.to_string())?;
+                f(self.open_shared(&mut guard)?)
+            };
+            match result {
+                Ok(out) => return Ok(out),
+                Err(e) if is_busy(&e) && attempt < BUSY_RETRIES => {
+                    attempt += 1;
+                    std::thread::sleep(BUSY_RETRY_DELAY);
+                }
+                Err(e) => return Err(e.to_string()),
+            }
+        }
+    }
+
+    /// The shared connection in slot, opening it first if this is the first use.
+    fn open_shared<'a>(
+        &self,
+        slot: &'a mut Option<Connection>,
+    ) -> Result<&'a mut Connection, String> {
+        match slot {
+            Some(conn) => Ok(conn),
+            None => {
+                let path = self.db_path()?;
+                Ok(slot.insert(open_connection(&path).map_err(|e| e.to_string())?))
+            }
+        }
+    }
+
+    /// Copies the live database to backups/state-<unix millis>.db with the online backup API
+    /// (consistent even while other windows write), keeps the newest MAX_BACKUPS, and returns
+    /// the new file.
+    pub fn backup(&self) -> Result<PathBuf, String> {
+        let db_path = self.db_path()?;
+        let dir = db_path
+            .parent()
+            .map(|d| d.join(BACKUP_DIR_NAME))
+            .ok_or_else(|| "No folder for backups.".to_string())?;
+        std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+        let millis = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis())
+            .unwrap_or(0);
+        let dest = dir.join(format!("{}{}.db", BACKUP_FILE_PREFIX, millis));
+        self.with_conn(|conn| conn.backup(DatabaseName::Main, &dest, None))?;
+        prune_backups(&dir);
+        Ok(dest)
+    }
+
+    /// Startup backup: like backup, but skipped (Ok(None)) when nothing changed since the newest
+    /// backup, and refused when the live database fails quick_check, so a damaged state.db
+    /// can't rotate the last good backups out of the folder.
+    pub fn backup_on_startup(&self) -> Result<Option<PathBuf>, String> {
+        let db_path = self.db_path()?;
+        let newest = db_path
+            .parent()
+            .and_then(|d| newest_backup(&d.join(BACKUP_DIR_NAME)));
+        if newest.is_some_and(|b| unchanged_since(&db_path, &b)) {
+            return Ok(None);
+        }
+        let check: String =
+            self.with_conn(|conn| conn.query_row("PRAGMA quick_check", [], |row| row.get(0)))?;
+        if check != "ok" {
+            return Err(format!("The settings database is damaged: {}", check));
+        }
+        self.backup().map(Some)
+    }
+
+    /// Replaces the stored settings with the contents of backup after checking it is an intact
+    /// settings database. Pending debounced writes are discarded so they can't overwrite the
+    /// restored values; an older backup is migrated to the current schema.
+    pub fn restore(&self, backup: &Path) -> Result<(), String> {
+        validate_backup(backup)?;
+        let mut pending = self.pending()?;
+        pending.clear();
+        let mut guard = self.conn.lock().map_err(|e| e.to_string())?;
+        let conn = self.open_shared(&mut guard)?;
+        conn.restore(DatabaseName::Main, backup, None::<fn(rusqlite::backup::Progress)>)
+            .map_err(|e| format!("Restore failed: {}", e))?;
+        ensure_schema(conn).map_err(|e| e.to_string())
+    }
+
+    /// Reads the value stored under key, or None if absent. A debounced value that hasn't been
+    /// flushed yet wins over the one in the DB.
+    /// Used by get_persisted and by backend code that needs saved state (e.g. viewer fullscreen).
+    pub fn get(&self, key: &str) -> Result<Option<String>, String> {
+        if let Some((value, _)) = self.pending()?.get(key) {
+            return Ok(Some(value.clone()));
+        }
+        self.with_conn(|conn| {
+            let mut stmt = conn.prepare("SELECT value FROM app_state WHERE key = ?")?;
+            let mut rows = stmt.query([key])?;
+            if let Some(row) = rows.next()? {
+                let value: String = row.get(0)?;
+                return Ok(Some(value));
+            }
+            Ok(None)
+        })
+    }
+
+    /// Inserts or replaces the value stored under key. Drops any pending debounced write for
+    /// the key so it can't overwrite this newer value later.
+    pub fn set(&self, key: &str, value: &str) -> Result<(), String> {
+        let mut pending = self.pending()?;
+        pending.remove(key);
+        self.write(key, value)
+    }
+
+    /// Debounced writes waiting to be flushed. Held while writing to the DB by set, delete,
+    /// restore and the flusher, so a flush can't land after (and overwrite) a newer value.
+    fn pending(&self) -> Result<MutexGuard<'_, PendingWrites>, String> {
+        self.debounced.pending.lock().map_err(|e| e.to_string())
+    }
+
+    /// Stores all pairs in one transaction: either every value is saved or none is. Pending
+    /// debounced writes for these keys are dropped, as in set.
+    pub fn set_many(&self, entries: &[(String, String)]) -> Result<(), String> {
+        let mut pending = self.pending()?;
+        for (key, _) in entries {
+            pending.remove(key);
+        }
+        self.with_conn(|conn| {
+            let tx = conn.unchecked_transaction()?;
+            {
+                let mut stmt = tx.prepare(
+                    "INSERT INTO app_state (key, value) VALUES (?1, ?2) ON CONFLICT(key) DO UPDATE SET value = ?2",
+                )?;
+                for (key, value) in entries {
+                    stmt.execute([key, value])?;
+                }
+            }
+            tx.commit()
+        })
+        .map_err(|e| format!("Settings were not saved: {}", e))
+    }
+
+    /// Removes key, including a debounced value for it that hasn't been flushed yet.
+    pub fn delete(&self, key: &str) -> Result<(), String> {
+        let mut pending = self.pending()?;
+        pending.remove(key);
+        self.with_conn(|conn| {
+            conn.execute("DELETE FROM app_state WHERE key = ?", [key])?;
+            Ok(())
+        })
+    }
+
+    /// Removes every stored value and pending debounced write. Only the rows go; the table
+    /// stays so the schema survives.
+    pub fn clear(&self) -> Result<(), String> {
+        let mut pending = self.pending()?;
+        pending.clear();
+        self.with_conn(|conn| {
+            conn.execute("DELETE FROM app_state", [])?;
+            Ok(())
+        })
+    }
+
+    fn write(&self, key: &str, value: &str) -> Result<(), String> {
+        self.with_conn(|conn| {
+            conn.execute(
+                "INSERT INTO app_state (key, value) VALUES (?1, ?2) ON CONFLICT(key) DO UPDATE SET value = ?2",
+                [key, value],
+            )?;
+            Ok(())
+        })
+    }
+
+    /// Keeps value in memory and writes it once delay has passed without another update for
+    /// key. Repeated calls only move the deadline, so a window drag ends in a single write.
+    pub fn set_debounced(&self, key: &str, value: &str, delay: Duration) {
+        self.flusher.get_or_init(|| match &self.location {
+            DbLocation::App(app) => {
+                let app = app.clone();
+                let debounced = Arc::clone(&self.debounced);
+                std::thread::spawn(move || run_flusher(app, debounced));
+            }
+            #[cfg(test)]
+            DbLocation::File(_) => {}
+        });
+        let deadline = Instant::now() + delay.min(MAX_DEBOUNCE_DELAY);
+        if let Ok(mut pending) = self.pending() {
+            pending.insert(key.to_string(), (value.to_string(), deadline));
+        }
+        self.debounced.changed.notify_one();
+    }
+
+    /// Writes every pending debounced value now. Called on app exit so the last update
+    /// (e.g. the final window geometry) isn't lost.
+    pub fn flush_pending(&self) -> Result<(), String> {
+        let mut pending = self.pending()?;
+        let mut first_err = None;
+        for (key, (value, _)) in pending.drain() {
+            if let Err(e) = self.write(&key, &value) {
+                first_err.get_or_insert(e);
+            }
+        }
+        first_err.map_or(Ok(()), Err)
+    }
+
+    /// Reads a JSON string array stored under key; missing or corrupt values read as empty.
+    /// Used for path lists (recent/favorite folders, playlists).
+    pub fn get_string_list(&self, key: &str) -> Result<Vec<String>, String> {
+        Ok(self
+            .get(key)?
+            .and_then(|v| serde_json::from_str(&v).ok())
+            .unwrap_or_default())
+    }
+
+    /// Stores list as a JSON string array under key.
+    pub fn set_string_list(&self, key: &str, list: &[String]) -> Result<(), String> {
+        let json = serde_json::to_string(list).map_err(|e| e.to_string())?;
+        self.set(key, &json)
+    }
+
+    /// All keys starting with prefix, sorted (e.g. every "playlist:" entry).
+    pub fn keys_with_prefix(&self, prefix: &str) -> Result<Vec<String>, String> {
+        self.with_conn(|conn| {
+            // substr instead of LIKE so '%' and '_' in the prefix are taken literally.
+            let mut stmt = conn.prepare(
+                "SELECT key FROM app_state WHERE substr(key, 1, length(?1)) = ?1 ORDER BY key",
+            )?;
+            let rows = stmt.query_map([prefix], |row| row.get::<_, String>(0))?;
+            Ok(rows.filter_map(|r| r.ok()).collect())
+        })
+    }
+}
+
+/// Body of the debounce timer thread: sleeps until the earliest deadline, then writes every
+/// entry that is due. The writes happen under the pending lock, so a set for the same key
+/// waits for them instead of being overwritten. Runs for the lifetime of the app.
+fn run_flusher(app: AppHandle, debounced: Arc<DebouncedWrites>) {
+    let Ok(mut pending) = debounced.pending.lock() else {
+        return;
+    };
+    loop {
+        let now = Instant::now();
+        let due: Vec<String> = pending
+            .iter()
+            .filter(|(_, (_, deadline))| *deadline <= now)
+            .map(|(key, _)| key.clone())
+            .collect();
+        if due.is_empty() {
+            let next = pending.values().map(|(_, deadline)| *deadline).min();
+            let woken = match next {
+                Some(deadline) => debounced
+                    .changed
+                    .wait_timeout(pending, deadline - now)
+                    .map(|(guard, _)| guard)
+                    .map_err(|_| ()),
+                None => debounced.changed.wait(pending).map_err(|_| ()),
+            };
+            pending = match woken {
+                Ok(guard) => guard,
+                Err(_) => return,
+            };
+            continue;
+        }
+        let state = app.state::<PersistenceState>();
+        for key in due {
+            if let Some((value, _)) = pending.remove(&key) {
+                let _ = state.write(&key, &value);
+            }
+        }
     }
 }
 
 #[tauri::command]
 pub fn get_persisted(key: String, state: State<PersistenceState>) -> Result<Option<String>, String> {
-    state.with_conn(|conn| {
-        let mut stmt = conn
-            .prepare("SELECT value FROM app_state WHERE key = ?")
-            .map_err(|e| e.to_string())?;
-        let mut rows = stmt.query([&key]).map_err(|e| e.to_string())?;
-        if let Some(row) = rows.next().map_err(|e| e.to_string())? {
-            let value: String = row.get(0).map_err(|e| e.to_string())?;
-            return Ok(Some(value));
-        }
-        Ok(None)
-    })
+    state.get(&key)
 }
 
 #[tauri::command]
 pub fn set_persisted(key: String, value: String, state: State<PersistenceState>) -> Result<(), String> {
-    state.with_conn(|conn| {
-        conn.execute(
-            "INSERT INTO app_state (key, value) VALUES (?1, ?2) ON CONFLICT(key) DO UPDATE SET value = ?2",
-            [&key, &value],
-        )
-        .map_err(|e| e.to_string())?;
-        Ok(())
-    })
+    state.set(&key, &value)
+}
+
+/// Reads key as JSON. A value that is not valid JSON (e.g. written by set_persisted as plain
+/// text) is an error rather than None, so it can't be mistaken for a missing key.
+#[tauri::command]
+pub fn get_persisted_json(
+    key: String,
+    state: State<PersistenceState>,
+) -> Result<Option<serde_json::Value>, String> {
+    match state.get(&key)? {
+        Some(text) => serde_json::from_str(&text)
+            .map(Some)
+            .map_err(|e| format!("Value of \"{}\" is not JSON: {}", key, e)),
+        None => Ok(None),
+    }
+}
+
+/// Stores value as JSON text in the same column as set_persisted, so both commands can read
+/// what the other wrote.
+#[tauri::command]
+pub fn set_persisted_json(
+    key: String,
+    value: serde_json::Value,
+    state: State<PersistenceState>,
+) -> Result<(), String> {
+    let text = serde_json::to_string(&value).map_err(|e| e.to_string())?;
+    state.set(&key, &text)
+}
+
+/// Saves several key-value pairs at once (e.g. window geometry, last folder and last file
+/// on shutdown), atomically.
+#[tauri::command]
+pub fn set_persisted_many(
+    entries: Vec<(String, String)>,
+    state: State<PersistenceState>,
+) -> Result<(), String> {
+    state.set_many(&entries)
+}
+
+/// Removes key; reading it afterwards returns None. Not an error if it was not set.
+#[tauri::command]
+pub fn delete_persisted(key: String, state: State<PersistenceState>) -> Result<(), String> {
+    state.delete(&key)
+}
+
+/// Removes all saved settings (for "Reset to defaults"). The app_state table itself is kept.
+#[tauri::command]
+pub fn clear_persisted(state: State<PersistenceState>) -> Result<(), String> {
+    state.clear()
+}
+
+/// Stores value under key after delay_ms (default 500) with no further updates for that key.
+/// Meant for high-frequency state such as slider positions or window geometry.
+#[tauri::command]
+pub fn set_persisted_debounced(
+    key: String,
+    value: String,
+    delay_ms: Option<u64>,
+    state: State<PersistenceState>,
+) -> Result<(), String> {
+    let delay = Duration::from_millis(delay_ms.unwrap_or(DEFAULT_DEBOUNCE_MS));
+    state.set_debounced(&key, &value, delay);
+    Ok(())
 }
 
 #[derive(Debug, Serialize)]
@@ -113,34 +664,193 @@ pub struct AllPersistedResult {
     pub entries: Vec<PersistedEntry>,
 }
 
+/// Backs up state.db to the backups folder next to it and returns the backup's path.
+/// The newest 5 backups are kept. Also run once in the background at startup.
+#[tauri::command]
+pub async fn backup_persistence(app: AppHandle) -> Result<String, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let path = app.state::<PersistenceState>().backup()?;
+        Ok(path.to_string_lossy().into_owned())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Replaces all settings with those in backup_path (see backup_persistence). The file is
+/// checked first; a damaged or unrelated database is refused and nothing changes.
+#[tauri::command]
+pub async fn restore_persistence(app: AppHandle, backup_path: String) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        app.state::<PersistenceState>().restore(Path::new(backup_path.trim()))
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Result of check_persistence_integrity. problems is empty when ok; row_count is None when
+/// app_state can't be read.
+#[derive(Debug, Serialize)]
+pub struct PersistenceIntegrity {
+    pub ok: bool,
+    pub problems: Vec<String>,
+    pub file_size: u64,
+    pub row_count: Option<u64>,
+}
+
+/// Runs PRAGMA integrity_check on a separate read-only connection, so a broken file is
+/// inspected as it is instead of being created or repaired by the shared one.
+fn check_integrity(path: &Path) -> Result<PersistenceIntegrity, String> {
+    let file_size = match std::fs::metadata(path) {
+        Ok(meta) => meta.len(),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            return Err("The settings database does not exist yet.".to_string())
+        }
+        Err(e) => return Err(format!("{}: {}", path.display(), e)),
+    };
+    let corrupt = |e: rusqlite::Error| format!("The settings database is corrupt: {}", e);
+    let conn =
+        Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY).map_err(corrupt)?;
+    conn.busy_timeout(BUSY_TIMEOUT).map_err(|e| e.to_string())?;
+    let mut stmt = conn.prepare("PRAGMA integrity_check").map_err(corrupt)?;
+    let problems: Vec<String> = stmt
+        .query_map([], |row| row.get::<_, String>(0))
+        .map_err(corrupt)?
+        .filter_map(|r| r.ok())
+        .filter(|line| line != "ok")
+        .collect();
+    let row_count = conn
+        .query_row("SELECT count(*) FROM app_state", [], |row| row.get::<_, i64>(0))
+        .ok()
+        .map(|n| n as u64);
+    Ok(PersistenceIntegrity {
+        ok: problems.is_empty(),
+        problems,
+        file_size,
+        row_count,
+    })
+}
+
+/// Checks state.db for corruption and reports its size and number of stored settings.
+/// A missing database and one that can't be opened as SQLite give different errors; when
+/// problems are found the UI can offer restore_persistence.
+#[tauri::command]
+pub async fn check_persistence_integrity(app: AppHandle) -> Result<PersistenceIntegrity, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let path = app.state::<PersistenceState>().db_path()?;
+        check_integrity(&path)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
 /// Returns the path to state.db (for display in UI).
 #[tauri::command]
-pub fn get_persistence_db_path(_state: State<PersistenceState>) -> Result<String, String> {
-    let path = db_path()?;
+pub fn get_persistence_db_path(state: State<PersistenceState>) -> Result<String, String> {
+    let path = state.db_path()?;
     Ok(path.to_string_lossy().into_owned())
 }
 
 /// Returns all key-value pairs in state.db for debugging / status display.
 #[tauri::command]
 pub fn get_all_persisted(state: State<PersistenceState>) -> Result<AllPersistedResult, String> {
-    let path = db_path()?;
+    let path = state.db_path()?;
     let path_str = path.to_string_lossy().into_owned();
     state.with_conn(|conn| {
-        let mut stmt = conn
-            .prepare("SELECT key, value FROM app_state ORDER BY key")
-            .map_err(|e| e.to_string())?;
-        let rows = stmt
-            .query_map([], |row| {
-                Ok(PersistedEntry {
-                    key: row.get(0)?,
-                    value: row.get(1)?,
-                })
+        let mut stmt = conn.prepare("SELECT key, value FROM app_state ORDER BY key")?;
+        let rows = stmt.query_map([], |row| {
+            Ok(PersistedEntry {
+                key: row.get(0)?,
+                value: row.get(1)?,
             })
-            .map_err(|e| e.to_string())?;
+        })?;
         let entries: Vec<PersistedEntry> = rows.filter_map(|r| r.ok()).collect();
         Ok(AllPersistedResult {
-            db_path: path_str,
+            db_path: path_str.clone(),
             entries,
         })
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Fresh empty folder under the system temp dir, unique per test and process.
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("v-see-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn concurrent_sets_all_succeed() {
+        let dir = temp_dir("persistence-stress");
+        let state = Arc::new(PersistenceState::at_path(dir.join("state.db")));
+        // A second connection to the same file, like a separate process, competes for the lock.
+        let other = Arc::new(PersistenceState::at_path(dir.join("state.db")));
+        let threads: Vec<_> = (0..8)
+            .map(|t| {
+                let state = Arc::clone(if t % 2 == 0 { &state } else { &other });
+                std::thread::spawn(move || {
+                    for i in 0..50 {
+                        state.set(&format!("key-{}-{}", t, i), &i.to_string()).unwrap();
+                        state.set("shared", &format!("{}-{}", t, i)).unwrap();
+                    }
+                })
+            })
+            .collect();
+        for thread in threads {
+            thread.join().unwrap();
+        }
+        for t in 0..8 {
+            assert_eq!(state.get(&format!("key-{}-49", t)).unwrap().as_deref(), Some("49"));
+        }
+        assert!(state.get("shared").unwrap().is_some());
+        drop((state, other));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn deleted_key_reads_back_as_none() {
+        let dir = temp_dir("persistence-delete");
+        let state = PersistenceState::at_path(dir.join("state.db"));
+        state.set("kept", "1").unwrap();
+        state.set("gone", "2").unwrap();
+        state.delete("gone").unwrap();
+        assert_eq!(state.get("gone").unwrap(), None);
+        assert_eq!(state.get("kept").unwrap().as_deref(), Some("1"));
+        // A pending debounced value is dropped too.
+        state.set_debounced("gone", "3", Duration::from_secs(5));
+        state.delete("gone").unwrap();
+        assert_eq!(state.get("gone").unwrap(), None);
+        drop(state);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn unversioned_database_is_migrated_with_its_rows() {
+        let dir = temp_dir("persistence-migrate");
+        let path = dir.join("state.db");
+        {
+            // A database from before versioning: user_version 0 but app_state already exists.
+            let conn = Connection::open(&path).unwrap();
+            conn.execute_batch(
+                "CREATE TABLE app_state (key TEXT PRIMARY KEY, value TEXT);
+                 INSERT INTO app_state (key, value) VALUES ('last_folder', 'C:\\Photos');",
+            )
+            .unwrap();
+        }
+        let conn = open_connection(&path).unwrap();
+        let version: usize = conn.query_row("PRAGMA user_version", [], |row| row.get(0)).unwrap();
+        assert_eq!(version, MIGRATIONS.len());
+        let value: String = conn
+            .query_row("SELECT value FROM app_state WHERE key = 'last_folder'", [], |row| {
+                row.get(0)
+            })
+            .unwrap();
+        assert_eq!(value, "C:\\Photos");
+        drop(conn);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
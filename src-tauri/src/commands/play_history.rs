@@ -0,0 +1,65 @@
+// Author: Viorel LUPU
+// Date: 2026-10-14
+// Purpose: "Recently played" history. The audio thread records every track that started
+// playing (failed decodes are skipped); the list is a capped JSON array in the persistence store.
+
+use super::persistence::{PersistenceState, PLAY_HISTORY_KEY};
+use serde::{Deserialize, Serialize};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::State;
+
+/// Plays kept in the history; older entries are dropped.
+const MAX_PLAY_HISTORY: usize = 500;
+
+/// Entries returned by get_play_history when the caller passes 0.
+const DEFAULT_PLAY_HISTORY_LIMIT: usize = 50;
+
+/// One started track and when it started (seconds since the Unix epoch).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlayHistoryEntry {
+    pub path: String,
+    pub played_at_unix: u64,
+}
+
+/// Stored history, most recent first; a missing or corrupt value reads as empty.
+fn load_history(persistence: &PersistenceState) -> Result<Vec<PlayHistoryEntry>, String> {
+    Ok(persistence
+        .get(PLAY_HISTORY_KEY)?
+        .and_then(|v| serde_json::from_str(&v).ok())
+        .unwrap_or_default())
+}
+
+/// Adds path at the front of the history with the current time, keeping MAX_PLAY_HISTORY entries.
+pub fn record_play(persistence: &PersistenceState, path: &str) -> Result<(), String> {
+    let played_at_unix = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let mut history = load_history(persistence)?;
+    history.insert(
+        0,
+        PlayHistoryEntry {
+            path: path.to_string(),
+            played_at_unix,
+        },
+    );
+    history.truncate(MAX_PLAY_HISTORY);
+    let json = serde_json::to_string(&history).map_err(|e| e.to_string())?;
+    persistence.set(PLAY_HISTORY_KEY, &json)
+}
+
+/// Returns up to limit recent plays, most recent first (limit 0 = default of 50).
+#[tauri::command]
+pub fn get_play_history(
+    limit: usize,
+    state: State<PersistenceState>,
+) -> Result<Vec<PlayHistoryEntry>, String> {
+    let limit = if limit == 0 {
+        DEFAULT_PLAY_HISTORY_LIMIT
+    } else {
+        limit
+    };
+    let mut history = load_history(&state)?;
+    history.truncate(limit);
+    Ok(history)
+}
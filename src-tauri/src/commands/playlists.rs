@@ -0,0 +1,69 @@
+// Author: Viorel LUPU
+// Date: 2026-10-14
+// Purpose: Named playlists (saved listening queues) in the persistence store, one JSON array
+// of track paths per "playlist:<name>" key.
+
+use super::persistence::{PersistenceState, PLAYLIST_KEY_PREFIX};
+use serde::Serialize;
+use std::path::Path;
+use tauri::State;
+
+/// Purpose: Result of load_playlist.
+/// Usage: UI queues paths and, if pruned > 0, tells the user some tracks are gone.
+/// Reason for choice: Pruning does not rewrite the saved playlist, so tracks on a disconnected
+/// drive come back once it is reconnected.
+#[derive(Debug, Serialize)]
+pub struct LoadedPlaylist {
+    pub paths: Vec<String>,
+    pub pruned: usize,
+}
+
+/// Validated, trimmed playlist name.
+fn playlist_name(name: &str) -> Result<&str, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Playlist name is empty.".to_string());
+    }
+    Ok(name)
+}
+
+/// Saves (or overwrites) the playlist name with the given track paths, in order.
+#[tauri::command]
+pub fn save_playlist(
+    name: String,
+    paths: Vec<String>,
+    state: State<PersistenceState>,
+) -> Result<(), String> {
+    let key = format!("{}{}", PLAYLIST_KEY_PREFIX, playlist_name(&name)?);
+    state.set_string_list(&key, &paths)
+}
+
+/// Loads the playlist name (empty if it doesn't exist). With prune_missing (default true),
+/// tracks whose files no longer exist are left out and counted in `pruned`.
+#[tauri::command]
+pub fn load_playlist(
+    name: String,
+    prune_missing: Option<bool>,
+    state: State<PersistenceState>,
+) -> Result<LoadedPlaylist, String> {
+    let key = format!("{}{}", PLAYLIST_KEY_PREFIX, playlist_name(&name)?);
+    let mut paths = state.get_string_list(&key)?;
+    let before = paths.len();
+    if prune_missing.unwrap_or(true) {
+        paths.retain(|p| Path::new(p).is_file());
+    }
+    Ok(LoadedPlaylist {
+        pruned: before - paths.len(),
+        paths,
+    })
+}
+
+/// Returns the names of all saved playlists, sorted.
+#[tauri::command]
+pub fn list_playlists(state: State<PersistenceState>) -> Result<Vec<String>, String> {
+    Ok(state
+        .keys_with_prefix(PLAYLIST_KEY_PREFIX)?
+        .into_iter()
+        .map(|k| k[PLAYLIST_KEY_PREFIX.len()..].to_string())
+        .collect())
+}
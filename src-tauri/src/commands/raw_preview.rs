@@ -0,0 +1,250 @@
+// Author: Viorel LUPU
+// Date: 2026-10-14
+// Purpose: Preview for camera RAW files (CR2, NEF, ARW, DNG). These are TIFF containers that
+// embed a JPEG rendered by the camera; the IFDs are walked to find the largest baseline JPEG
+// and it is returned as a data URL, so no RAW decoding is needed. Portrait shots are turned
+// upright using the RAW file's own orientation tag, which the embedded JPEG usually lacks.
+
+use super::fs::{encode_preview, normalize_path};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use image::metadata::Orientation;
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+
+/// Largest embedded JPEG returned (full-size previews of high-resolution bodies are ~10 MB).
+const MAX_RAW_PREVIEW_SIZE: u64 = 32 * 1024 * 1024;
+
+/// IFDs visited per file and entries read per IFD; real files have a handful of each.
+const MAX_RAW_IFDS: usize = 64;
+const MAX_IFD_ENTRIES: u16 = 1024;
+
+const TAG_COMPRESSION: u16 = 0x0103;
+const TAG_STRIP_OFFSETS: u16 = 0x0111;
+const TAG_ORIENTATION: u16 = 0x0112;
+const TAG_STRIP_BYTE_COUNTS: u16 = 0x0117;
+const TAG_SUB_IFDS: u16 = 0x014A;
+const TAG_JPEG_OFFSET: u16 = 0x0201;
+const TAG_JPEG_LENGTH: u16 = 0x0202;
+const TAG_EXIF_IFD: u16 = 0x8769;
+
+/// TIFF compression values that mean the strip is a JPEG stream (old-style and new-style).
+const JPEG_COMPRESSIONS: [u32; 2] = [6, 7];
+
+/// Reads numbers from a TIFF file in its byte order.
+struct TiffReader {
+    file: File,
+    len: u64,
+    little_endian: bool,
+}
+
+impl TiffReader {
+    fn read_at(&mut self, offset: u64, buf: &mut [u8]) -> Option<()> {
+        if offset.checked_add(buf.len() as u64)? > self.len {
+            return None;
+        }
+        self.file.seek(SeekFrom::Start(offset)).ok()?;
+        self.file.read_exact(buf).ok()
+    }
+
+    fn u16_at(&mut self, offset: u64) -> Option<u16> {
+        let mut b = [0u8; 2];
+        self.read_at(offset, &mut b)?;
+        Some(if self.little_endian {
+            u16::from_le_bytes(b)
+        } else {
+            u16::from_be_bytes(b)
+        })
+    }
+
+    fn u32_at(&mut self, offset: u64) -> Option<u32> {
+        let mut b = [0u8; 4];
+        self.read_at(offset, &mut b)?;
+        Some(if self.little_endian {
+            u32::from_le_bytes(b)
+        } else {
+            u32::from_be_bytes(b)
+        })
+    }
+
+    /// Values of a SHORT or LONG entry (inline when they fit in 4 bytes), at most 16.
+    fn values(&mut self, entry: u64, kind: u16, count: u32) -> Vec<u32> {
+        let size = match kind {
+            3 => 2,
+            4 | 13 => 4,
+            _ => return Vec::new(),
+        };
+        let count = count.min(16) as u64;
+        let base = if size * count <= 4 {
+            entry + 8
+        } else {
+            match self.u32_at(entry + 8) {
+                Some(offset) => offset as u64,
+                None => return Vec::new(),
+            }
+        };
+        (0..count)
+            .filter_map(|i| match size {
+                2 => self.u16_at(base + i * 2).map(u32::from),
+                _ => self.u32_at(base + i * 4),
+            })
+            .collect()
+    }
+}
+
+/// What one IFD contributes: JPEG candidates (offset, length), child IFDs, orientation.
+#[derive(Default)]
+struct IfdInfo {
+    jpegs: Vec<(u64, u64)>,
+    children: Vec<u64>,
+    orientation: Option<u32>,
+    next: Option<u64>,
+}
+
+fn read_ifd(r: &mut TiffReader, offset: u64) -> Option<IfdInfo> {
+    let count = r.u16_at(offset)?.min(MAX_IFD_ENTRIES);
+    let mut info = IfdInfo::default();
+    let (mut compression, mut strips, mut strip_lengths) = (None, Vec::new(), Vec::new());
+    let (mut jpeg_offset, mut jpeg_length) = (None, None);
+    for i in 0..count as u64 {
+        let entry = offset + 2 + i * 12;
+        let tag = r.u16_at(entry)?;
+        let kind = r.u16_at(entry + 2)?;
+        let n = r.u32_at(entry + 4)?;
+        match tag {
+            TAG_COMPRESSION => compression = r.values(entry, kind, n).first().copied(),
+            TAG_STRIP_OFFSETS => strips = r.values(entry, kind, n),
+            TAG_STRIP_BYTE_COUNTS => strip_lengths = r.values(entry, kind, n),
+            TAG_ORIENTATION => info.orientation = r.values(entry, kind, n).first().copied(),
+            TAG_JPEG_OFFSET => jpeg_offset = r.values(entry, kind, n).first().copied(),
+            TAG_JPEG_LENGTH => jpeg_length = r.values(entry, kind, n).first().copied(),
+            TAG_SUB_IFDS | TAG_EXIF_IFD => info
+                .children
+                .extend(r.values(entry, kind, n).into_iter().map(u64::from)),
+            _ => {}
+        }
+    }
+    if let (Some(o), Some(l)) = (jpeg_offset, jpeg_length) {
+        info.jpegs.push((o as u64, l as u64));
+    }
+    // A single-strip JPEG-compressed image (CR2 full-size preview, DNG preview IFD).
+    if compression.is_some_and(|c| JPEG_COMPRESSIONS.contains(&c))
+        && strips.len() == 1
+        && strip_lengths.len() == 1
+    {
+        info.jpegs.push((strips[0] as u64, strip_lengths[0] as u64));
+    }
+    let next = r.u32_at(offset + 2 + count as u64 * 12)?;
+    info.next = (next != 0).then_some(next as u64);
+    Some(info)
+}
+
+/// True if the bytes at offset are a JPEG a browser can show: SOI followed (before the image
+/// data) by a baseline, extended or progressive frame header. Lossless JPEG (SOF3), which DNG
+/// and NEF use for the sensor data itself, is rejected.
+fn is_viewable_jpeg(r: &mut TiffReader, offset: u64, length: u64) -> bool {
+    let end = offset.saturating_add(length);
+    let mut soi = [0u8; 2];
+    if r.read_at(offset, &mut soi).is_none() || soi != [0xFF, 0xD8] {
+        return false;
+    }
+    let mut pos = offset + 2;
+    while pos + 4 <= end {
+        let mut marker = [0u8; 4];
+        if r.read_at(pos, &mut marker).is_none() || marker[0] != 0xFF {
+            return false;
+        }
+        match marker[1] {
+            0xC0..=0xC2 => return true,
+            0xC3 | 0xC5..=0xC7 | 0xC9..=0xCB | 0xCD..=0xCF | 0xDA => return false,
+            _ => {}
+        }
+        pos += 2 + u16::from_be_bytes([marker[2], marker[3]]) as u64;
+    }
+    false
+}
+
+/// Finds the largest viewable embedded JPEG. Returns (offset, length, IFD0 orientation).
+fn find_preview(path: &Path) -> Result<(u64, u64, Option<u32>), String> {
+    let file = File::open(path).map_err(|e| e.to_string())?;
+    let len = file.metadata().map_err(|e| e.to_string())?.len();
+    let mut r = TiffReader {
+        file,
+        len,
+        little_endian: true,
+    };
+    let not_raw = || "Not a supported RAW file (CR2, NEF, ARW or DNG).".to_string();
+    let mut header = [0u8; 8];
+    r.read_at(0, &mut header).ok_or_else(not_raw)?;
+    r.little_endian = match &header[0..2] {
+        b"II" => true,
+        b"MM" => false,
+        _ => return Err(not_raw()),
+    };
+    if r.u16_at(2) != Some(42) {
+        return Err(not_raw());
+    }
+    let first = r.u32_at(4).ok_or_else(not_raw)? as u64;
+    let mut queue = vec![first];
+    let mut seen = HashSet::new();
+    let mut orientation = None;
+    let mut best: Option<(u64, u64)> = None;
+    while let Some(offset) = queue.pop() {
+        if seen.len() >= MAX_RAW_IFDS || !seen.insert(offset) {
+            continue;
+        }
+        let Some(ifd) = read_ifd(&mut r, offset) else {
+            continue;
+        };
+        if offset == first {
+            orientation = ifd.orientation;
+        }
+        for (o, l) in ifd.jpegs {
+            let bigger = best.is_none_or(|(_, bl)| l > bl);
+            let in_file = o.checked_add(l).is_some_and(|end| end <= len);
+            if bigger && in_file && l <= MAX_RAW_PREVIEW_SIZE && is_viewable_jpeg(&mut r, o, l) {
+                best = Some((o, l));
+            }
+        }
+        queue.extend(ifd.children);
+        queue.extend(ifd.next);
+    }
+    let (offset, length) =
+        best.ok_or_else(|| "This RAW file has no embedded preview.".to_string())?;
+    Ok((offset, length, orientation))
+}
+
+fn raw_preview(path: &Path) -> Result<String, String> {
+    let (offset, length, orientation) = find_preview(path)?;
+    let mut f = File::open(path).map_err(|e| e.to_string())?;
+    f.seek(SeekFrom::Start(offset)).map_err(|e| e.to_string())?;
+    let mut bytes = vec![0u8; length as usize];
+    f.read_exact(&mut bytes).map_err(|e| e.to_string())?;
+    let orientation = orientation
+        .and_then(|o| Orientation::from_exif(o as u8))
+        .unwrap_or(Orientation::NoTransforms);
+    if orientation != Orientation::NoTransforms {
+        if let Ok(mut img) = image::load_from_memory_with_format(&bytes, image::ImageFormat::Jpeg) {
+            img.apply_orientation(orientation);
+            if let Ok((upright, _)) = encode_preview(img) {
+                bytes = upright;
+            }
+        }
+    }
+    Ok(format!(
+        "data:image/jpeg;base64,{}",
+        STANDARD.encode(&bytes)
+    ))
+}
+
+/// Returns the JPEG preview embedded in a camera RAW file (CR2, NEF, ARW, DNG) as a data URL,
+/// turned upright. Errors when the file is not a TIFF-based RAW or carries no usable preview,
+/// so the UI can show a placeholder.
+#[tauri::command]
+pub async fn get_raw_preview_data_url(path: String) -> Result<String, String> {
+    let p = normalize_path(&path)?;
+    tauri::async_runtime::spawn_blocking(move || raw_preview(&p))
+        .await
+        .map_err(|e| e.to_string())?
+}
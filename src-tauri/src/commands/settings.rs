@@ -0,0 +1,63 @@
+// Author: Viorel LUPU
+// Date: 2026-10-14
+// Purpose: Typed commands for well-known settings stored in the persistence key-value store.
+// Parsing, validation and defaults live here so the frontend and backend agree on formats;
+// get_persisted / set_persisted remain for any other key.
+
+use super::persistence::{PersistenceState, SLIDESHOW_INTERVAL_SECONDS_KEY, SLIDESHOW_MUSIC_KEY};
+use tauri::State;
+
+/// Slideshow interval bounds and default, in seconds (matches the viewer's prompt).
+const MIN_SLIDESHOW_INTERVAL: u64 = 1;
+const MAX_SLIDESHOW_INTERVAL: u64 = 3600;
+const DEFAULT_SLIDESHOW_INTERVAL: u64 = 3;
+
+/// Reads "true"/"false" (also "1"/"0", "yes"/"no"); anything else is None.
+fn parse_bool(value: &str) -> Option<bool> {
+    match value.trim().to_lowercase().as_str() {
+        "true" | "1" | "yes" => Some(true),
+        "false" | "0" | "no" => Some(false),
+        _ => None,
+    }
+}
+
+/// Slideshow interval in seconds; absent or out-of-range values read as the default (3s).
+#[tauri::command]
+pub fn get_slideshow_interval(state: State<PersistenceState>) -> Result<u64, String> {
+    Ok(state
+        .get(SLIDESHOW_INTERVAL_SECONDS_KEY)?
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        .filter(|n| (MIN_SLIDESHOW_INTERVAL..=MAX_SLIDESHOW_INTERVAL).contains(n))
+        .unwrap_or(DEFAULT_SLIDESHOW_INTERVAL))
+}
+
+/// Stores the slideshow interval; errors if secs is outside 1..=3600.
+#[tauri::command]
+pub fn set_slideshow_interval(secs: u64, state: State<PersistenceState>) -> Result<(), String> {
+    if !(MIN_SLIDESHOW_INTERVAL..=MAX_SLIDESHOW_INTERVAL).contains(&secs) {
+        return Err(format!(
+            "Slideshow interval must be {}–{} seconds.",
+            MIN_SLIDESHOW_INTERVAL, MAX_SLIDESHOW_INTERVAL
+        ));
+    }
+    state.set(SLIDESHOW_INTERVAL_SECONDS_KEY, &secs.to_string())
+}
+
+/// Whether music plays during the slideshow; absent or malformed reads as false.
+#[tauri::command]
+pub fn get_slideshow_music_enabled(state: State<PersistenceState>) -> Result<bool, String> {
+    Ok(state
+        .get(SLIDESHOW_MUSIC_KEY)?
+        .as_deref()
+        .and_then(parse_bool)
+        .unwrap_or(false))
+}
+
+/// Stores the slideshow music flag as "true"/"false".
+#[tauri::command]
+pub fn set_slideshow_music_enabled(
+    enabled: bool,
+    state: State<PersistenceState>,
+) -> Result<(), String> {
+    state.set(SLIDESHOW_MUSIC_KEY, if enabled { "true" } else { "false" })
+}
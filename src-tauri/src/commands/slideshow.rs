@@ -0,0 +1,210 @@
+// Author: Viorel LUPU
+// Date: 2026-10-14
+// Purpose: Slideshow timing in the backend. A JS timer drifts and is throttled while the viewer
+// window is in the background; here one thread per viewer advances ViewerState on a fixed
+// cadence and each step is announced with viewer://index-changed like manual navigation.
+// The thread ends on stop_slideshow, when the viewer window closes, or when its list is empty.
+
+use super::viewer::advance_viewer;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager, State};
+
+/// Interval limits in seconds; the upper one matches set_slideshow_interval.
+const MIN_SLIDESHOW_INTERVAL_SECONDS: f64 = 0.5;
+const MAX_SLIDESHOW_INTERVAL_SECONDS: f64 = 3600.0;
+
+/// Purpose: Running slideshows (viewer window label -> its control block).
+/// Usage: Managed by Tauri; start_slideshow inserts, stop_slideshow and viewer close remove.
+/// Reason for choice: Each viewer runs its own slideshow, the same way ViewerState keys
+/// navigation by label; the thread holds its own Arc so removal from the map is enough to
+/// stop it once it is woken.
+#[derive(Default)]
+pub struct SlideshowState {
+    shows: Mutex<HashMap<String, Arc<Slideshow>>>,
+}
+
+impl SlideshowState {
+    /// Stops the slideshow of label, if one is running; called when the viewer window closes.
+    pub fn stop(&self, label: &str) {
+        let show = match self.shows.lock() {
+            Ok(mut shows) => shows.remove(label),
+            Err(_) => None,
+        };
+        if let Some(show) = show {
+            show.update(|c| c.stopped = true);
+        }
+    }
+
+    fn get(&self, label: &str) -> Option<Arc<Slideshow>> {
+        self.shows.lock().ok()?.get(label).cloned()
+    }
+
+    /// Removes label only if it still maps to show (a newer slideshow may have replaced it).
+    fn remove_if_same(&self, label: &str, show: &Arc<Slideshow>) {
+        if let Ok(mut shows) = self.shows.lock() {
+            if shows.get(label).is_some_and(|s| Arc::ptr_eq(s, show)) {
+                shows.remove(label);
+            }
+        }
+    }
+}
+
+struct SlideshowControl {
+    interval: Duration,
+    paused: bool,
+    stopped: bool,
+}
+
+/// Control block shared by the commands and the slideshow thread; changes wake the thread.
+struct Slideshow {
+    control: Mutex<SlideshowControl>,
+    wake: Condvar,
+}
+
+impl Slideshow {
+    fn update(&self, f: impl FnOnce(&mut SlideshowControl)) {
+        if let Ok(mut control) = self.control.lock() {
+            f(&mut control);
+        }
+        self.wake.notify_all();
+    }
+}
+
+/// Payload of slideshow-stopped, emitted when a viewer's slideshow ends for any reason.
+#[derive(Debug, Clone, Serialize)]
+pub struct SlideshowStopped {
+    pub label: String,
+}
+
+/// Slideshow thread: waits out the interval (or a pause), then advances the viewer. The next
+/// step is due one interval after the previous one was due, so the cadence does not drift.
+fn run_slideshow(app: AppHandle, label: String, show: Arc<Slideshow>) {
+    let mut last = Instant::now();
+    let Ok(mut control) = show.control.lock() else {
+        return;
+    };
+    loop {
+        if control.stopped {
+            break;
+        }
+        if control.paused {
+            control = match show.wake.wait(control) {
+                Ok(c) => c,
+                Err(_) => return,
+            };
+            // A resumed slideshow shows the current image for a full interval.
+            last = Instant::now();
+            continue;
+        }
+        let interval = control.interval;
+        let due = last + interval;
+        let now = Instant::now();
+        if now < due {
+            // Woken early by an interval change or pause: loop and re-check.
+            control = match show.wake.wait_timeout(control, due - now) {
+                Ok((c, _)) => c,
+                Err(_) => return,
+            };
+            continue;
+        }
+        drop(control);
+        if advance_viewer(&app, &label).is_none() {
+            break;
+        }
+        // After a long stall (e.g. system sleep) restart the cadence instead of catching up.
+        last = if now.duration_since(due) > interval {
+            Instant::now()
+        } else {
+            due
+        };
+        control = match show.control.lock() {
+            Ok(c) => c,
+            Err(_) => return,
+        };
+    }
+    app.state::<SlideshowState>().remove_if_same(&label, &show);
+    let _ = app.emit("slideshow-stopped", SlideshowStopped { label });
+}
+
+fn interval_from_seconds(seconds: f64) -> Result<Duration, String> {
+    if !seconds.is_finite()
+        || !(MIN_SLIDESHOW_INTERVAL_SECONDS..=MAX_SLIDESHOW_INTERVAL_SECONDS).contains(&seconds)
+    {
+        return Err(format!(
+            "Slideshow interval must be between {} and {} seconds.",
+            MIN_SLIDESHOW_INTERVAL_SECONDS, MAX_SLIDESHOW_INTERVAL_SECONDS
+        ));
+    }
+    Ok(Duration::from_secs_f64(seconds))
+}
+
+/// Starts the slideshow of the viewer window label, advancing every interval_seconds
+/// (0.5–3600) and wrapping at the end. If it is already running, only the interval changes
+/// (the next step is timed from the previous one) and a paused slideshow resumes.
+#[tauri::command]
+pub fn start_slideshow(
+    app: AppHandle,
+    label: String,
+    interval_seconds: f64,
+    state: State<'_, SlideshowState>,
+) -> Result<(), String> {
+    let interval = interval_from_seconds(interval_seconds)?;
+    if app.get_webview_window(&label).is_none() {
+        return Err("Viewer window is not open.".to_string());
+    }
+    let mut shows = state.shows.lock().map_err(|e| e.to_string())?;
+    if let Some(show) = shows.get(&label) {
+        show.update(|c| {
+            c.interval = interval;
+            c.paused = false;
+        });
+        return Ok(());
+    }
+    let show = Arc::new(Slideshow {
+        control: Mutex::new(SlideshowControl {
+            interval,
+            paused: false,
+            stopped: false,
+        }),
+        wake: Condvar::new(),
+    });
+    let thread_show = Arc::clone(&show);
+    let thread_label = label.clone();
+    std::thread::Builder::new()
+        .name(format!("slideshow-{}", label))
+        .spawn(move || run_slideshow(app, thread_label, thread_show))
+        .map_err(|e| e.to_string())?;
+    shows.insert(label, show);
+    Ok(())
+}
+
+/// Stops the slideshow of the viewer window label. Not an error if none is running.
+#[tauri::command]
+pub fn stop_slideshow(label: String, state: State<'_, SlideshowState>) -> Result<(), String> {
+    state.stop(&label);
+    Ok(())
+}
+
+/// Pauses or resumes the slideshow of label; a resumed slideshow waits a full interval before
+/// the next step.
+#[tauri::command]
+pub fn set_slideshow_paused(
+    label: String,
+    paused: bool,
+    state: State<'_, SlideshowState>,
+) -> Result<(), String> {
+    let show = state
+        .get(&label)
+        .ok_or_else(|| "No slideshow is running.".to_string())?;
+    show.update(|c| c.paused = paused);
+    Ok(())
+}
+
+/// True if a slideshow is running (or paused) for the viewer window label.
+#[tauri::command]
+pub fn slideshow_is_running(label: String, state: State<'_, SlideshowState>) -> bool {
+    state.get(&label).is_some()
+}
@@ -0,0 +1,50 @@
+// Author: Viorel LUPU
+// Date: 2026-10-14
+// Purpose: Rasterize SVG files to PNG on the backend (resvg) so the webview never renders
+// untrusted SVG markup, which can carry scripts and external references. External image
+// references are never loaded; only inline data URLs are drawn.
+
+use resvg::{tiny_skia, usvg};
+use std::sync::{Arc, OnceLock};
+
+/// Longest edge of the rendered PNG when the caller does not pass one.
+pub const DEFAULT_SVG_RASTER_EDGE: u32 = 2048;
+
+/// Upper bound for the requested edge, so a huge target can't allocate an enormous bitmap.
+const MAX_SVG_RASTER_EDGE: u32 = 8192;
+
+/// System fonts are loaded once (slow) and shared by every render.
+static FONT_DB: OnceLock<Arc<usvg::fontdb::Database>> = OnceLock::new();
+
+fn font_db() -> Arc<usvg::fontdb::Database> {
+    Arc::clone(FONT_DB.get_or_init(|| {
+        let mut db = usvg::fontdb::Database::new();
+        db.load_system_fonts();
+        Arc::new(db)
+    }))
+}
+
+/// Renders SVG bytes to PNG, scaled so the longest edge equals max_edge (clamped to
+/// 1..=MAX_SVG_RASTER_EDGE), whatever size the document declares.
+pub fn rasterize_svg(bytes: &[u8], max_edge: u32) -> Result<Vec<u8>, String> {
+    let mut options = usvg::Options {
+        fontdb: font_db(),
+        ..Default::default()
+    };
+    // File paths and URLs in <image href> are ignored; the default resolver would read them.
+    options.image_href_resolver.resolve_string = Box::new(|_, _| None);
+    let tree = usvg::Tree::from_data(bytes, &options).map_err(|e| format!("SVG: {}", e))?;
+    let size = tree.size();
+    let edge = max_edge.clamp(1, MAX_SVG_RASTER_EDGE) as f32;
+    let scale = edge / size.width().max(size.height());
+    let width = (size.width() * scale).round().max(1.0) as u32;
+    let height = (size.height() * scale).round().max(1.0) as u32;
+    let mut pixmap = tiny_skia::Pixmap::new(width, height)
+        .ok_or_else(|| "SVG: invalid output size".to_string())?;
+    resvg::render(
+        &tree,
+        tiny_skia::Transform::from_scale(scale, scale),
+        &mut pixmap.as_mut(),
+    );
+    pixmap.encode_png().map_err(|e| format!("SVG: {}", e))
+}
@@ -0,0 +1,79 @@
+// Author: Viorel LUPU
+// Date: 2026-10-14
+// Purpose: Text preview for files that are neither images nor media (txt, md, logs, code).
+// Reads a bounded prefix, picks the encoding from the BOM (UTF-8 / UTF-16) or by checking for
+// valid UTF-8 with a Windows-1252 (Latin-1) fallback, and refuses binary files.
+
+use super::fs::{friendly_error, normalize_path};
+use encoding_rs::{Encoding, UTF_16BE, UTF_16LE, UTF_8, WINDOWS_1252};
+use serde::Serialize;
+use std::io::Read;
+
+/// Upper bound for max_bytes so a preview never loads a huge file.
+const MAX_TEXT_PREVIEW_BYTES: usize = 4 * 1024 * 1024;
+
+/// Bytes checked for NUL when deciding whether a file without a UTF-16 BOM is binary.
+const BINARY_SNIFF_BYTES: usize = 8 * 1024;
+
+/// Purpose: Result of read_text_file.
+/// Usage: The preview pane shows text and a "file truncated" note when truncated is true.
+/// Reason for choice: encoding is reported (e.g. "UTF-8", "windows-1252") so a wrong guess is
+/// visible to the user rather than silently garbling the text.
+#[derive(Debug, Serialize)]
+pub struct TextPreview {
+    pub text: String,
+    pub encoding: String,
+    pub truncated: bool,
+}
+
+/// Encoding and BOM length for the prefix. Without a BOM the prefix is UTF-8 if it is valid
+/// (a character cut off at the end is allowed), otherwise Windows-1252.
+fn detect_encoding(bytes: &[u8]) -> (&'static Encoding, usize) {
+    if let Some(found) = Encoding::for_bom(bytes) {
+        return found;
+    }
+    match std::str::from_utf8(bytes) {
+        Ok(_) => (UTF_8, 0),
+        Err(e) if e.error_len().is_none() => (UTF_8, 0),
+        Err(_) => (WINDOWS_1252, 0),
+    }
+}
+
+/// Reads up to max_bytes of the file at path and decodes it as text.
+/// Errors with "Binary file." when the sampled prefix contains NUL bytes (unless it is UTF-16).
+#[tauri::command]
+pub async fn read_text_file(path: String, max_bytes: usize) -> Result<TextPreview, String> {
+    let p = normalize_path(&path)?;
+    if p.is_dir() {
+        return Err("Path is a directory.".to_string());
+    }
+    let max_bytes = max_bytes.clamp(1, MAX_TEXT_PREVIEW_BYTES);
+    tauri::async_runtime::spawn_blocking(move || {
+        let file = std::fs::File::open(&p).map_err(|e| friendly_error(&e))?;
+        let len = file.metadata().map_err(|e| friendly_error(&e))?.len();
+        let mut bytes = Vec::with_capacity(max_bytes.min(len as usize));
+        file.take(max_bytes as u64)
+            .read_to_end(&mut bytes)
+            .map_err(|e| friendly_error(&e))?;
+        let (encoding, bom_len) = detect_encoding(&bytes);
+        let is_utf16 = encoding == UTF_16LE || encoding == UTF_16BE;
+        let sniff = &bytes[..bytes.len().min(BINARY_SNIFF_BYTES)];
+        if !is_utf16 && sniff.contains(&0) {
+            return Err("Binary file.".to_string());
+        }
+        let truncated = len > bytes.len() as u64;
+        let body = &bytes[bom_len..];
+        let mut text = encoding.decode_without_bom_handling(body).0.into_owned();
+        if truncated && text.ends_with('\u{FFFD}') {
+            // The cut fell inside a multi-byte character.
+            text.pop();
+        }
+        Ok(TextPreview {
+            text,
+            encoding: encoding.name().to_string(),
+            truncated,
+        })
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
@@ -0,0 +1,172 @@
+// Author: Viorel LUPU
+// Date: 2026-10-14
+// Purpose: Batch thumbnail prefetch for the grid. One call queues a whole folder's thumbnails;
+// each finished tile arrives as a thumbnail-ready event instead of one command per tile.
+// Work goes through the same concurrency limit as get_video_thumbnail_data_url and stops
+// when the operation is cancelled (e.g. the user scrolls or navigates away).
+
+use super::cancel::{CancelToken, CancellationState};
+use super::formats::{image_mime, VIDEO_EXTENSIONS};
+use super::fs::normalize_path;
+use super::image_thumb::{make_image_thumbnail, DEFAULT_THUMB_MAX_EDGE};
+use super::video_thumb::{
+    acquire_thumbnail_permit, cached_video_thumbnail, ThumbFormat, ThumbnailOptions,
+};
+use serde::Serialize;
+use std::path::Path;
+use std::sync::Arc;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// Payload of thumbnail-ready: the finished thumbnail for one path of the batch.
+#[derive(Debug, Clone, Serialize)]
+struct ThumbnailReady {
+    operation_id: String,
+    path: String,
+    data_url: String,
+}
+
+/// Payload of thumbnail-failed: the tile should show its placeholder.
+#[derive(Debug, Clone, Serialize)]
+struct ThumbnailFailed {
+    operation_id: String,
+    path: String,
+    error: String,
+}
+
+/// Payload of thumbnail-prefetch-done, emitted once after the last thumbnail (or on cancel).
+#[derive(Debug, Clone, Serialize)]
+struct ThumbnailPrefetchDone {
+    operation_id: String,
+    total: usize,
+    cancelled: bool,
+}
+
+/// Thumbnail data URL for an image or video (through the video thumbnail cache); other files
+/// are an error.
+fn thumbnail_for(app: &AppHandle, path: &Path, max_edge: u32) -> Result<String, String> {
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("")
+        .to_lowercase();
+    if VIDEO_EXTENSIONS.contains(&ext.as_str()) {
+        let options = ThumbnailOptions {
+            at_secs: None,
+            max_edge: Some(max_edge),
+            format: ThumbFormat::Jpeg,
+        };
+        return cached_video_thumbnail(app, &path.to_string_lossy(), &options);
+    }
+    if ext != "svg" && image_mime(&ext).is_some() {
+        return make_image_thumbnail(path, max_edge).map(|t| t.data_url);
+    }
+    Err("No thumbnail for this file type.".to_string())
+}
+
+/// Queues every path behind the thumbnail limiter and emits the result of each as it
+/// finishes. A path only gets a blocking task once it holds a slot, so a large batch waits
+/// without tying up Tauri's blocking pool; invalid paths fail at once without taking one.
+/// Returns whether it was cancelled.
+async fn run_prefetch(
+    app: &AppHandle,
+    paths: Vec<String>,
+    max_edge: u32,
+    operation_id: &str,
+    cancel: &Arc<CancelToken>,
+) -> bool {
+    let mut workers = Vec::new();
+    for path in paths {
+        if cancel.is_cancelled() {
+            break;
+        }
+        let path_buf = match normalize_path(&path) {
+            Ok(p) => p,
+            Err(error) => {
+                let _ = app.emit(
+                    "thumbnail-failed",
+                    ThumbnailFailed {
+                        operation_id: operation_id.to_string(),
+                        path,
+                        error,
+                    },
+                );
+                continue;
+            }
+        };
+        let permit = match acquire_thumbnail_permit().await {
+            Ok(p) => p,
+            Err(_) => break,
+        };
+        // Cancelled while waiting for a slot: don't start the extraction.
+        if cancel.is_cancelled() {
+            break;
+        }
+        let app = app.clone();
+        let cancel = Arc::clone(cancel);
+        let operation_id = operation_id.to_string();
+        workers.push(tauri::async_runtime::spawn_blocking(move || {
+            let _permit = permit;
+            let result = thumbnail_for(&app, &path_buf, max_edge);
+            if cancel.is_cancelled() {
+                return;
+            }
+            let _ = match result {
+                Ok(data_url) => app.emit(
+                    "thumbnail-ready",
+                    ThumbnailReady {
+                        operation_id,
+                        path,
+                        data_url,
+                    },
+                ),
+                Err(error) => app.emit(
+                    "thumbnail-failed",
+                    ThumbnailFailed {
+                        operation_id,
+                        path,
+                        error,
+                    },
+                ),
+            };
+        }));
+    }
+    for worker in workers {
+        let _ = worker.await;
+    }
+    cancel.is_cancelled()
+}
+
+/// Generates thumbnails (longest edge max_edge) for paths in the background and returns at
+/// once. Each result arrives as thumbnail-ready { operation_id, path, data_url } (or
+/// thumbnail-failed { operation_id, path, error }), then thumbnail-prefetch-done.
+/// cancel_operation(operation_id) stops pending work; results not yet emitted are dropped.
+#[tauri::command]
+pub fn prefetch_thumbnails(
+    app: AppHandle,
+    paths: Vec<String>,
+    max_edge: u32,
+    operation_id: String,
+) -> Result<(), String> {
+    let max_edge = if max_edge == 0 {
+        DEFAULT_THUMB_MAX_EDGE
+    } else {
+        max_edge
+    };
+    let cancel = Arc::new(
+        app.state::<CancellationState>()
+            .register(Some(operation_id.clone())),
+    );
+    tauri::async_runtime::spawn(async move {
+        let total = paths.len();
+        let cancelled = run_prefetch(&app, paths, max_edge, &operation_id, &cancel).await;
+        let _ = app.emit(
+            "thumbnail-prefetch-done",
+            ThumbnailPrefetchDone {
+                operation_id,
+                total,
+                cancelled,
+            },
+        );
+    });
+    Ok(())
+}
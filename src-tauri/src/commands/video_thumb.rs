@@ -1,38 +1,513 @@
 // Author: Viorel LUPU
 // Date: 2026-02-17
-// Purpose: Video thumbnail extraction via ffmpeg (one frame as PNG), returned as data URL.
-// If ffmpeg is missing or fails, returns an error so the frontend can show a placeholder.
+// Purpose: Video thumbnail extraction via ffmpeg (one frame as PNG or JPEG), returned as data URL.
+// When ffmpeg is not installed, H.264-in-MP4 files fall back to an in-process keyframe decode
+// (mp4 demuxer + OpenH264). Otherwise returns an error so the frontend can show a placeholder.
+// Extractions are queued behind a concurrency limit so large folders do not start hundreds of ffmpegs.
 
+use super::cache::{cache_subdir, read_cached, stable_hash, write_cached, VIDEO_THUMBNAIL_CACHE};
+use super::ffmpeg_locate::{resolve_ffmpeg, resolve_ffmpeg_tools, resolve_ffprobe};
+use super::fs::normalize_path;
 use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufReader, Cursor};
+use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::sync::{Mutex, OnceLock, RwLock};
+use std::time::UNIX_EPOCH;
+use tauri::AppHandle;
+use tokio::sync::{Semaphore, SemaphorePermit};
 
-/// Extracts a single frame from the video at path (at ~1s to skip black intro).
-/// Returns a data URL (data:image/png;base64,...) or an error string.
+/// Message shown when no frame could be produced without ffmpeg.
+const FFMPEG_NOT_FOUND: &str =
+    "ffmpeg not found. Install ffmpeg and add it to PATH (or set FFMPEG_PATH).";
+
+/// Containers the built-in fallback can demux (ISO base media / QuickTime).
+const FALLBACK_EXTENSIONS: [&str; 3] = ["mp4", "m4v", "mov"];
+
+/// How many samples the fallback feeds the decoder before giving up (keyframe is usually first).
+const MAX_FALLBACK_SAMPLES: u32 = 120;
+
+/// Default frame time (seconds): ~1s skips black intros.
+const DEFAULT_THUMB_AT_SECS: f64 = 1.0;
+
+/// Image encoding for a video thumbnail.
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ThumbFormat {
+    #[default]
+    Png,
+    /// Much smaller data URLs; preferred for the thumbnail grid.
+    Jpeg,
+}
+
+impl ThumbFormat {
+    fn mime(self) -> &'static str {
+        match self {
+            ThumbFormat::Png => "image/png",
+            ThumbFormat::Jpeg => "image/jpeg",
+        }
+    }
+
+    /// ffmpeg encoder name for this format.
+    fn ffmpeg_codec(self) -> &'static str {
+        match self {
+            ThumbFormat::Png => "png",
+            ThumbFormat::Jpeg => "mjpeg",
+        }
+    }
+}
+
+/// Purpose: Optional settings for get_video_thumbnail_data_url.
+/// Usage: JS passes { at_secs, max_edge, format: "png" | "jpeg" }; any field may be omitted.
+/// Reason for choice: One struct keeps the command signature stable as options grow; omitted
+/// options reproduce the original behavior (PNG at 1s, full size).
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct ThumbnailOptions {
+    /// Frame time in seconds (default 1s); for clips shorter than that, the midpoint is used.
+    pub at_secs: Option<f64>,
+    /// Longest edge in pixels; larger frames are scaled down, smaller ones are left as is.
+    pub max_edge: Option<u32>,
+    pub format: ThumbFormat,
+}
+
+/// Result of check_external_tools: which video helpers are runnable and their versions.
+#[derive(Debug, Clone, Serialize)]
+pub struct ExternalTools {
+    pub ffmpeg: bool,
+    pub ffprobe: bool,
+    pub ffmpeg_version: Option<String>,
+    pub ffprobe_version: Option<String>,
+}
+
+/// Binaries the cached ExternalTools was probed from (ffmpeg, ffprobe).
+type ToolPaths = (Option<PathBuf>, Option<PathBuf>);
+
+/// Last probe result, keyed by the located binaries: once ffmpeg_locate finds a different
+/// (or newly installed) copy, the versions are probed again.
+static EXTERNAL_TOOLS: RwLock<Option<(ToolPaths, ExternalTools)>> = RwLock::new(None);
+
+/// Runs `<program> -version` and returns the first line of its output, or None if it can't run.
+fn tool_version_line(program: &Path) -> Option<String> {
+    let output = Command::new(program).arg("-version").output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    Some(stdout.lines().next().unwrap_or("").trim().to_string())
+}
+
+/// Extracts "6.1.1" from a first line like "ffmpeg version 6.1.1 Copyright (c) ...".
+fn parse_version(first_line: &str) -> Option<String> {
+    let mut parts = first_line.split_whitespace();
+    while let Some(word) = parts.next() {
+        if word == "version" {
+            return parts.next().map(|v| v.to_string());
+        }
+    }
+    None
+}
+
+fn detect_external_tools(paths: &ToolPaths) -> ExternalTools {
+    let ffmpeg_line = paths.0.as_deref().and_then(tool_version_line);
+    let ffprobe_line = paths.1.as_deref().and_then(tool_version_line);
+    ExternalTools {
+        ffmpeg: ffmpeg_line.is_some(),
+        ffprobe: ffprobe_line.is_some(),
+        ffmpeg_version: ffmpeg_line.as_deref().and_then(parse_version),
+        ffprobe_version: ffprobe_line.as_deref().and_then(parse_version),
+    }
+}
+
+/// Detected tools, probing when the located binaries changed since the last call (blocks
+/// while ffmpeg/ffprobe run).
+pub fn external_tools() -> ExternalTools {
+    let paths = resolve_ffmpeg_tools();
+    if let Ok(cached) = EXTERNAL_TOOLS.read() {
+        if let Some((_, tools)) = cached.as_ref().filter(|(p, _)| *p == paths) {
+            return tools.clone();
+        }
+    }
+    let tools = detect_external_tools(&paths);
+    if let Ok(mut cached) = EXTERNAL_TOOLS.write() {
+        *cached = Some((paths, tools.clone()));
+    }
+    tools
+}
+
+/// Reports whether ffmpeg/ffprobe can be run (and their versions) so the frontend can
+/// disable video features up front and show setup instructions. The versions are cached until
+/// a different ffmpeg/ffprobe is located (see get_ffmpeg_status).
 #[tauri::command]
-pub fn get_video_thumbnail_data_url(path: String) -> Result<String, String> {
+pub async fn check_external_tools() -> Result<ExternalTools, String> {
+    tauri::async_runtime::spawn_blocking(external_tools)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Converts one AVCC sample (4-byte big-endian length before each NAL) to Annex B start codes.
+fn avcc_to_annex_b(sample: &[u8], out: &mut Vec<u8>) {
+    let mut i = 0;
+    while i + 4 <= sample.len() {
+        let len = u32::from_be_bytes([sample[i], sample[i + 1], sample[i + 2], sample[i + 3]]) as usize;
+        i += 4;
+        let end = (i + len).min(sample.len());
+        out.extend_from_slice(&[0, 0, 0, 1]);
+        out.extend_from_slice(&sample[i..end]);
+        i = end;
+    }
+}
+
+/// Decodes the first displayable frame of the H.264 track in an MP4/MOV.
+/// Only the common H.264-in-MP4 case is handled; anything else returns an error.
+fn extract_mp4_keyframe(path: &Path) -> Result<image::RgbImage, String> {
+    use openh264::decoder::Decoder;
+    use openh264::formats::YUVSource;
+
+    let file = File::open(path).map_err(|e| e.to_string())?;
+    let size = file.metadata().map_err(|e| e.to_string())?.len();
+    let mut reader = mp4::Mp4Reader::read_header(BufReader::new(file), size).map_err(|e| e.to_string())?;
+    let (track_id, sample_count, sps, pps) = {
+        let track = reader
+            .tracks()
+            .values()
+            .find(|t| matches!(t.media_type(), Ok(mp4::MediaType::H264)))
+            .ok_or_else(|| "No H.264 video track.".to_string())?;
+        let sps = track.sequence_parameter_set().map_err(|e| e.to_string())?.to_vec();
+        let pps = track.picture_parameter_set().map_err(|e| e.to_string())?.to_vec();
+        (track.track_id(), track.sample_count(), sps, pps)
+    };
+    let mut decoder = Decoder::new().map_err(|e| e.to_string())?;
+    let mut packet = Vec::new();
+    for nal in [&sps, &pps] {
+        packet.extend_from_slice(&[0, 0, 0, 1]);
+        packet.extend_from_slice(nal);
+    }
+    let _ = decoder.decode(&packet);
+    for sample_id in 1..=sample_count.min(MAX_FALLBACK_SAMPLES) {
+        let sample = match reader.read_sample(track_id, sample_id) {
+            Ok(Some(s)) => s,
+            _ => continue,
+        };
+        packet.clear();
+        avcc_to_annex_b(&sample.bytes, &mut packet);
+        if let Ok(Some(yuv)) = decoder.decode(&packet) {
+            let (width, height) = yuv.dimensions();
+            let mut rgb = vec![0u8; width * height * 3];
+            yuv.write_rgb8(&mut rgb);
+            return image::RgbImage::from_raw(width as u32, height as u32, rgb)
+                .ok_or_else(|| "Decoded frame has unexpected size.".to_string());
+        }
+    }
+    Err("No frame decoded.".to_string())
+}
+
+/// Scales the frame down to fit max_edge (if set) and encodes it in the requested format.
+fn encode_frame(img: image::RgbImage, options: &ThumbnailOptions) -> Result<Vec<u8>, String> {
+    let img = match options.max_edge {
+        Some(edge) if edge > 0 && img.width().max(img.height()) > edge => {
+            image::DynamicImage::ImageRgb8(img).thumbnail(edge, edge).into_rgb8()
+        }
+        _ => img,
+    };
+    let format = match options.format {
+        ThumbFormat::Png => image::ImageFormat::Png,
+        ThumbFormat::Jpeg => image::ImageFormat::Jpeg,
+    };
+    let mut out = Vec::new();
+    img.write_to(&mut Cursor::new(&mut out), format)
+        .map_err(|e| e.to_string())?;
+    Ok(out)
+}
+
+/// Used when ffmpeg is missing: tries the built-in MP4 decoder, else returns the install guidance.
+/// The built-in decoder always uses the first keyframe; at_secs is ignored.
+fn fallback_thumbnail(path: &Path, options: &ThumbnailOptions) -> Result<Vec<u8>, String> {
+    let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("").to_lowercase();
+    if !FALLBACK_EXTENSIONS.contains(&ext.as_str()) {
+        return Err(FFMPEG_NOT_FOUND.to_string());
+    }
+    let frame = extract_mp4_keyframe(path).map_err(|_| FFMPEG_NOT_FOUND.to_string())?;
+    encode_frame(frame, options)
+}
+
+/// Upper bound for set_thumbnail_concurrency.
+const MAX_THUMBNAIL_CONCURRENCY: usize = 64;
+
+/// Purpose: Counting semaphore limiting how many thumbnails are extracted at once.
+/// Usage: Commands await a ThumbnailPermit before handing the extraction to spawn_blocking;
+/// extra requests wait in acquire_thumbnail_permit without holding a blocking thread.
+/// Reason for choice: Opening a folder of hundreds of videos would otherwise start one ffmpeg
+/// per file at once; an async semaphore keeps waiting requests off Tauri's blocking pool.
+/// The limit changes at runtime by adding permits, or by retiring them as they come back.
+struct ThumbnailLimiter {
+    slots: Semaphore,
+    /// (limit, permits still to retire after the limit was lowered)
+    state: Mutex<(usize, usize)>,
+}
+
+/// Held while one thumbnail is being extracted; frees the slot on drop, or retires it when
+/// the limit was lowered in the meantime.
+pub struct ThumbnailPermit(Option<SemaphorePermit<'static>>);
+
+impl Drop for ThumbnailPermit {
+    fn drop(&mut self) {
+        let Some(permit) = self.0.take() else {
+            return;
+        };
+        if let Ok(mut state) = thumbnail_limiter().state.lock() {
+            if state.1 > 0 {
+                state.1 -= 1;
+                permit.forget();
+            }
+        }
+    }
+}
+
+/// Defaults to one concurrent extraction per CPU.
+static THUMBNAIL_LIMITER: OnceLock<ThumbnailLimiter> = OnceLock::new();
+
+fn thumbnail_limiter() -> &'static ThumbnailLimiter {
+    THUMBNAIL_LIMITER.get_or_init(|| {
+        let cpus = std::thread::available_parallelism().map_or(4, |n| n.get());
+        ThumbnailLimiter {
+            slots: Semaphore::new(cpus),
+            state: Mutex::new((cpus, 0)),
+        }
+    })
+}
+
+/// Waits until a slot is free, then takes it.
+pub async fn acquire_thumbnail_permit() -> Result<ThumbnailPermit, String> {
+    let permit = thumbnail_limiter()
+        .slots
+        .acquire()
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(ThumbnailPermit(Some(permit)))
+}
+
+/// Sets how many video thumbnails may be extracted at once (clamped to 1..=64) and returns
+/// the applied value. Queued requests start as soon as the new limit allows; lowering it lets
+/// running extractions finish.
+#[tauri::command]
+pub fn set_thumbnail_concurrency(limit: usize) -> Result<usize, String> {
+    let limit = limit.clamp(1, MAX_THUMBNAIL_CONCURRENCY);
+    let limiter = thumbnail_limiter();
+    let mut state = limiter.state.lock().map_err(|e| e.to_string())?;
+    let (current, owed) = *state;
+    if limit > current {
+        // Cancel pending retirements first, then hand out the rest as new slots.
+        let added = limit - current;
+        let cancelled = owed.min(added);
+        limiter.slots.add_permits(added - cancelled);
+        *state = (limit, owed - cancelled);
+    } else {
+        let removed = current - limit;
+        let retired = limiter.slots.forget_permits(removed);
+        *state = (limit, owed + removed - retired);
+    }
+    Ok(limit)
+}
+
+/// Extracts a single frame from the video at path (default ~1s to skip black intro, PNG,
+/// full size; see ThumbnailOptions). Falls back to the built-in H.264/MP4 keyframe decoder
+/// when ffmpeg is not installed. Returns a data URL (data:image/png;base64,... or
+/// data:image/jpeg;base64,...) or an error string.
+/// Runs on a background thread; at most set_thumbnail_concurrency extractions run at once
+/// (default: CPU count) and the rest wait their turn. Results are cached on disk per path,
+/// modification time and options (see clear_thumbnail_cache); a hit doesn't run ffmpeg.
+#[tauri::command]
+pub async fn get_video_thumbnail_data_url(
+    app: AppHandle,
+    path: String,
+    options: Option<ThumbnailOptions>,
+) -> Result<String, String> {
+    let options = options.unwrap_or_default();
+    let permit = acquire_thumbnail_permit().await?;
+    tauri::async_runtime::spawn_blocking(move || {
+        let _permit = permit;
+        cached_video_thumbnail(&app, &path, &options)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Highest position get_video_thumbnail_at_percent accepts; the very last frame is often black.
+const MAX_THUMB_PERCENT: f64 = 0.99;
+
+/// Container duration in seconds from ffprobe, or None if ffprobe is missing or can't tell.
+fn probe_duration_secs(path: &str) -> Option<f64> {
+    let output = Command::new(resolve_ffprobe()?)
+        .args(["-v", "error", "-show_entries", "format=duration"])
+        .args(["-of", "default=noprint_wrappers=1:nokey=1", path])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let secs: f64 = String::from_utf8_lossy(&output.stdout).trim().parse().ok()?;
+    (secs.is_finite() && secs > 0.0).then_some(secs)
+}
+
+/// Like get_video_thumbnail_data_url, but the frame is taken at percent (0.0..=0.99) of the
+/// duration reported by ffprobe, for a poster frame that represents the video. Falls back to
+/// the first frame when the duration is unknown. options.at_secs is ignored. Cached like
+/// get_video_thumbnail_data_url.
+#[tauri::command]
+pub async fn get_video_thumbnail_at_percent(
+    app: AppHandle,
+    path: String,
+    percent: f64,
+    options: Option<ThumbnailOptions>,
+) -> Result<String, String> {
+    let percent = if percent.is_finite() {
+        percent.clamp(0.0, MAX_THUMB_PERCENT)
+    } else {
+        0.0
+    };
+    let mut options = options.unwrap_or_default();
+    let permit = acquire_thumbnail_permit().await?;
+    tauri::async_runtime::spawn_blocking(move || {
+        let _permit = permit;
+        options.at_secs = Some(probe_duration_secs(&path).map_or(0.0, |d| d * percent));
+        cached_video_thumbnail(&app, &path, &options)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Frame count bounds for get_video_thumbnail_strip.
+const DEFAULT_STRIP_FRAMES: usize = 5;
+const MAX_STRIP_FRAMES: usize = 12;
+
+/// Extracts frame_count (default 5, 1..=12) evenly spaced frames of the video for a
+/// contact-sheet preview and returns them in time order as data URLs. Frames sit in the
+/// middle of equal slices of the duration, so neither the first nor the last (often black)
+/// frame is used. options.at_secs is ignored. Errors if ffprobe can't tell the duration.
+#[tauri::command]
+pub async fn get_video_thumbnail_strip(
+    app: AppHandle,
+    path: String,
+    frame_count: Option<usize>,
+    options: Option<ThumbnailOptions>,
+) -> Result<Vec<String>, String> {
+    let count = frame_count
+        .unwrap_or(DEFAULT_STRIP_FRAMES)
+        .clamp(1, MAX_STRIP_FRAMES);
+    let mut options = options.unwrap_or_default();
+    // One slot for the whole strip, so a strip doesn't crowd out grid thumbnails.
+    let permit = acquire_thumbnail_permit().await?;
+    tauri::async_runtime::spawn_blocking(move || {
+        let _permit = permit;
+        let duration = probe_duration_secs(&path).ok_or_else(|| {
+            "Could not determine the video duration (is ffprobe installed?).".to_string()
+        })?;
+        (0..count)
+            .map(|i| {
+                options.at_secs = Some(duration * (i as f64 + 0.5) / count as f64);
+                cached_video_thumbnail(&app, &path, &options)
+            })
+            .collect()
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Cache file name for a thumbnail: hash of the path, its modification time, and the options
+/// that change the output. None if the file's modification time can't be read.
+fn thumbnail_cache_name(path: &Path, options: &ThumbnailOptions) -> Option<String> {
+    let modified = std::fs::metadata(path).ok()?.modified().ok()?;
+    let nanos = modified.duration_since(UNIX_EPOCH).ok()?.as_nanos();
+    let at_secs = options.at_secs.unwrap_or(DEFAULT_THUMB_AT_SECS).max(0.0);
+    let hash = stable_hash(&[
+        path.to_string_lossy().as_bytes(),
+        &nanos.to_le_bytes(),
+        &at_secs.to_le_bytes(),
+        &options.max_edge.unwrap_or(0).to_le_bytes(),
+    ]);
+    let ext = match options.format {
+        ThumbFormat::Png => "png",
+        ThumbFormat::Jpeg => "jpg",
+    };
+    Some(format!("{:016x}.{}", hash, ext))
+}
+
+/// video_thumbnail through the on-disk thumbnail cache, keyed by path + modification time, so
+/// scrolling back over a folder doesn't start ffmpeg again. Callers hold a ThumbnailPermit.
+pub fn cached_video_thumbnail(
+    app: &AppHandle,
+    path: &str,
+    options: &ThumbnailOptions,
+) -> Result<String, String> {
+    let to_data_url = |bytes: &[u8]| {
+        format!("data:{};base64,{}", options.format.mime(), STANDARD.encode(bytes))
+    };
+    let path_buf = normalize_path(path)?;
+    let cache = cache_subdir(app, VIDEO_THUMBNAIL_CACHE)
+        .ok()
+        .zip(thumbnail_cache_name(&path_buf, options));
+    let Some((dir, name)) = cache else {
+        return video_thumbnail(path, options).map(|b| to_data_url(&b));
+    };
+    if let Some(bytes) = read_cached(&dir, &name) {
+        return Ok(to_data_url(&bytes));
+    }
+    let bytes = video_thumbnail(path, options)?;
+    write_cached(&dir, &name, &bytes);
+    Ok(to_data_url(&bytes))
+}
+
+/// Runs ffmpeg to grab the frame at at_secs and returns its output (stdout = image bytes).
+fn run_ffmpeg_frame(
+    path: &str,
+    at_secs: f64,
+    options: &ThumbnailOptions,
+) -> std::io::Result<std::process::Output> {
+    let ffmpeg = resolve_ffmpeg().ok_or(std::io::ErrorKind::NotFound)?;
+    let seek = format!("{:.3}", at_secs);
+    let mut cmd = Command::new(ffmpeg);
+    cmd.args(["-y", "-loglevel", "error", "-ss", &seek, "-i", path, "-vframes", "1"]);
+    if let Some(edge) = options.max_edge.filter(|&e| e > 0) {
+        // Fit inside edge x edge without upscaling small videos.
+        let filter = format!(
+            "scale='min({0},iw)':'min({0},ih)':force_original_aspect_ratio=decrease",
+            edge
+        );
+        cmd.args(["-vf", &filter]);
+    }
+    cmd.args(["-f", "image2", "-vcodec", options.format.ffmpeg_codec()]);
+    if options.format == ThumbFormat::Jpeg {
+        cmd.args(["-q:v", "4"]);
+    }
+    cmd.arg("pipe:1");
+    cmd.output()
+}
+
+/// Extracts one frame as encoded PNG/JPEG bytes (ffmpeg, else the built-in MP4 decoder).
+/// When the requested time (default 1s) is past the end of a short clip, the frame at the
+/// middle of the video (duration from ffprobe) is used instead of failing.
+fn video_thumbnail(path: &str, options: &ThumbnailOptions) -> Result<Vec<u8>, String> {
     let path_buf = std::path::PathBuf::from(&path);
     if !path_buf.is_file() {
         return Err("File not found.".to_string());
     }
-    let output = Command::new("ffmpeg")
-        .args([
-            "-y",
-            "-loglevel", "error",
-            "-ss", "1",
-            "-i", &path,
-            "-vframes", "1",
-            "-f", "image2",
-            "-vcodec", "png",
-            "pipe:1",
-        ])
-        .output()
-        .map_err(|e| {
-            if e.kind() == std::io::ErrorKind::NotFound {
-                "ffmpeg not found. Install ffmpeg and add it to PATH.".to_string()
-            } else {
-                e.to_string()
-            }
-        })?;
+    let at_secs = options.at_secs.unwrap_or(DEFAULT_THUMB_AT_SECS).max(0.0);
+    let mut output = match run_ffmpeg_frame(path, at_secs, options) {
+        Ok(o) => o,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            return fallback_thumbnail(&path_buf, options)
+        }
+        Err(e) => return Err(e.to_string()),
+    };
+    if !output.status.success() || output.stdout.is_empty() {
+        // Seeking past the end gives no frame; retry at the midpoint if that was the cause.
+        if let Some(duration) = probe_duration_secs(path).filter(|&d| at_secs >= d) {
+            output = run_ffmpeg_frame(path, duration / 2.0, options).map_err(|e| e.to_string())?;
+        }
+    }
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
         return Err(format!("ffmpeg failed: {}", stderr.trim()));
@@ -40,6 +515,5 @@ pub fn get_video_thumbnail_data_url(path: String) -> Result<String, String> {
     if output.stdout.is_empty() {
         return Err("No frame produced.".to_string());
     }
-    let b64 = STANDARD.encode(&output.stdout);
-    Ok(format!("data:image/png;base64,{}", b64))
+    Ok(output.stdout)
 }
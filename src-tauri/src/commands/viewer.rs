@@ -1,107 +1,723 @@
 // Author: Viorel LUPU
 // Date: 2026-02-17
-// Purpose: Viewer (Display) window: open a second window with image list and index.
-// The viewer window reads context via get_viewer_context and navigates with viewer_prev/viewer_next.
+// Purpose: Viewer (Display) windows: open one or more windows, each with its own image list and index.
+// Each viewer window reads its context via get_viewer_context(label) and navigates with
+// viewer_prev/viewer_next(label). Contexts are keyed by window label and removed when the window closes.
+// Each context also holds the view state (zoom, fit mode, pan), mirrored to persistence so the
+// next viewer and the next session start with the same fit mode and zoom.
+// Every change of the current item is announced with viewer://index-changed so the main window
+// can keep its thumbnail selection on the image being shown.
 
+use super::persistence::{
+    PersistenceState, DEFAULT_DEBOUNCE_MS, VIEWER_FULLSCREEN_KEY, VIEWER_VIEW_STATE_KEY,
+    VIEWER_WINDOW_GEOMETRY_KEY,
+};
+use super::slideshow::SlideshowState;
+use super::window_geometry::{restore_window_geometry, track_window_geometry};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::sync::Mutex;
-use tauri::{AppHandle, Manager, State, WebviewUrl, WebviewWindowBuilder};
+use std::time::Duration;
+use tauri::{
+    AppHandle, Emitter, Manager, PhysicalPosition, PhysicalSize, State, WebviewUrl, WebviewWindow,
+    WebviewWindowBuilder, WindowEvent,
+};
 
+/// Prefix for viewer window labels (viewer-1, viewer-2, ...).
+const VIEWER_LABEL_PREFIX: &str = "viewer-";
+
+/// Zoom factor limits (1.0 = 100%).
+const MIN_VIEWER_ZOOM: f32 = 0.05;
+const MAX_VIEWER_ZOOM: f32 = 32.0;
+
+/// How an image is sized when it loads.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FitMode {
+    /// Whole image visible, scaled down to the window.
+    #[default]
+    FitWindow,
+    /// Image width matches the window; tall images scroll.
+    FitWidth,
+    /// One image pixel per screen pixel.
+    ActualSize,
+}
+
+/// Purpose: Zoom, fit mode, and pan offset of a viewer window.
+/// Usage: JS sends { zoom, fit_mode: "fit_window" | "fit_width" | "actual_size", pan_x, pan_y }
+/// through set_viewer_view_state; missing fields take their defaults.
+/// Reason for choice: Kept per window in ViewerContext so side-by-side viewers zoom
+/// independently; the last change is persisted as the default for new viewers.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ViewerViewState {
+    pub zoom: f32,
+    pub fit_mode: FitMode,
+    pub pan_x: f32,
+    pub pan_y: f32,
+}
+
+impl Default for ViewerViewState {
+    fn default() -> Self {
+        ViewerViewState {
+            zoom: 1.0,
+            fit_mode: FitMode::default(),
+            pan_x: 0.0,
+            pan_y: 0.0,
+        }
+    }
+}
+
+impl ViewerViewState {
+    /// State for a newly loaded image: keeps the fit mode, resets zoom and pan.
+    fn for_new_image(self) -> Self {
+        ViewerViewState {
+            fit_mode: self.fit_mode,
+            ..Default::default()
+        }
+    }
+
+    /// Clamps zoom to MIN..=MAX_VIEWER_ZOOM; non-finite values fall back to the defaults.
+    fn sanitized(self) -> Self {
+        let finite_or = |v: f32, d: f32| if v.is_finite() { v } else { d };
+        ViewerViewState {
+            zoom: finite_or(self.zoom, 1.0).clamp(MIN_VIEWER_ZOOM, MAX_VIEWER_ZOOM),
+            fit_mode: self.fit_mode,
+            pan_x: finite_or(self.pan_x, 0.0),
+            pan_y: finite_or(self.pan_y, 0.0),
+        }
+    }
+}
+
+/// Payload of viewer://index-changed, emitted whenever a viewer moves to another item.
+#[derive(Debug, Clone, Serialize)]
+pub struct ViewerIndexChanged {
+    pub label: String,
+    pub index: usize,
+    pub path: String,
+}
+
+/// View state saved by the last set_viewer_view_state, or the default.
+fn saved_view_state(persistence: &PersistenceState) -> ViewerViewState {
+    persistence
+        .get(VIEWER_VIEW_STATE_KEY)
+        .ok()
+        .flatten()
+        .and_then(|v| serde_json::from_str::<ViewerViewState>(&v).ok())
+        .map(ViewerViewState::sanitized)
+        .unwrap_or_default()
+}
+
+/// Purpose: Holds the navigation context of every open viewer window.
+/// Usage: Managed by Tauri; commands look up the context by window label.
+/// Reason for choice: A label-keyed map lets several viewers (e.g. side-by-side comparison) navigate independently.
 pub struct ViewerState {
-    pub inner: Mutex<ViewerContext>,
+    pub inner: Mutex<ViewerWindows>,
+}
+
+pub struct ViewerWindows {
+    pub contexts: HashMap<String, ViewerContext>,
+    /// Counter used to generate unique labels; never reused within a session.
+    pub next_id: u64,
 }
 
 pub struct ViewerContext {
     pub paths: Vec<String>,
     pub index: usize,
+    /// Next/prev walk order (a shuffled permutation of indices) instead of the list order.
+    pub shuffle: bool,
+    /// Wrap around at either end; when false, moving past the end returns None.
+    pub repeat: bool,
+    /// Shuffled walk order, starting at the item that was current when it was built.
+    /// Empty unless shuffle is on; rebuilt whenever paths changes.
+    order: Vec<usize>,
+    /// Position and size the window had before entering fullscreen; restored on exit.
+    pub windowed_geometry: Option<(PhysicalPosition<i32>, PhysicalSize<u32>)>,
+    pub view: ViewerViewState,
+}
+
+impl ViewerContext {
+    fn new(paths: Vec<String>, index: usize, view: ViewerViewState) -> Self {
+        ViewerContext {
+            paths,
+            index,
+            shuffle: false,
+            repeat: true,
+            order: Vec::new(),
+            windowed_geometry: None,
+            view,
+        }
+    }
+
+    /// Rebuilds the shuffle order for the current paths, with the current item first so a full
+    /// pass shows every other item once before coming back to it.
+    fn reshuffle(&mut self) {
+        self.order.clear();
+        if !self.shuffle || self.paths.is_empty() {
+            return;
+        }
+        let current = self.index.min(self.paths.len() - 1);
+        self.order = (0..self.paths.len()).filter(|&i| i != current).collect();
+        fastrand::shuffle(&mut self.order);
+        self.order.insert(0, current);
+    }
+
+    /// Index of the next (forward) or previous item in walk order, or None when the list is
+    /// empty or the end is reached without repeat.
+    fn step_index(&self, forward: bool) -> Option<usize> {
+        let len = self.paths.len();
+        if len == 0 {
+            return None;
+        }
+        let shuffled = self.shuffle && self.order.len() == len;
+        let pos = if shuffled {
+            self.order
+                .iter()
+                .position(|&i| i == self.index)
+                .unwrap_or(0)
+        } else {
+            self.index.min(len - 1)
+        };
+        let pos = if forward {
+            match pos + 1 {
+                p if p < len => p,
+                _ if self.repeat => 0,
+                _ => return None,
+            }
+        } else {
+            match pos.checked_sub(1) {
+                Some(p) => p,
+                None if self.repeat => len - 1,
+                None => return None,
+            }
+        };
+        Some(if shuffled { self.order[pos] } else { pos })
+    }
+
+    /// Path and file name of the current item, or None when the list is empty.
+    fn current_item(&self) -> Option<(String, String)> {
+        let path = self.paths.get(self.index)?.clone();
+        let name = std::path::Path::new(&path)
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("")
+            .to_string();
+        Some((path, name))
+    }
+}
+
+/// Makes index the current item of the viewer label (resetting zoom and pan), emits
+/// viewer://index-changed, and returns the new path and name. index must be in range.
+fn select_index(
+    app: &AppHandle,
+    label: &str,
+    ctx: &mut ViewerContext,
+    index: usize,
+) -> Option<(String, String)> {
+    ctx.index = index;
+    ctx.view = ctx.view.for_new_image();
+    let item = ctx.current_item()?;
+    let payload = ViewerIndexChanged {
+        label: label.to_string(),
+        index,
+        path: item.0.clone(),
+    };
+    let _ = app.emit("viewer://index-changed", payload);
+    Some(item)
+}
+
+/// Like select_index for a jump (not a step): the shuffle order is rebuilt from the new
+/// item, so the next pass starts there instead of continuing the old walk.
+fn jump_to_index(
+    app: &AppHandle,
+    label: &str,
+    ctx: &mut ViewerContext,
+    index: usize,
+) -> Option<(String, String)> {
+    ctx.index = index;
+    ctx.reshuffle();
+    select_index(app, label, ctx, index)
 }
 
 impl Default for ViewerState {
     fn default() -> Self {
         ViewerState {
-            inner: Mutex::new(ViewerContext {
-                paths: Vec::new(),
-                index: 0,
+            inner: Mutex::new(ViewerWindows {
+                contexts: HashMap::new(),
+                next_id: 1,
             }),
         }
     }
 }
 
-/// Opens the viewer window with the given paths and start index. If the viewer window
-/// already exists, focuses it and updates the context.
+/// Opens a viewer window with the given paths and start index and returns its label.
+/// If `label` names a viewer window that is still open, focuses it and replaces its context;
+/// otherwise a new window with a unique label (viewer-1, viewer-2, ...) is created.
 #[tauri::command]
 pub async fn open_viewer_window(
     app: AppHandle,
     paths: Vec<String>,
     start_index: usize,
+    label: Option<String>,
     state: State<'_, ViewerState>,
-) -> Result<(), String> {
+) -> Result<String, String> {
     let index = if paths.is_empty() {
         0
     } else {
         start_index.min(paths.len().saturating_sub(1))
     };
-    {
-        let mut ctx = state.inner.lock().map_err(|e| e.to_string())?;
-        ctx.paths = paths;
-        ctx.index = index;
-    }
-    let label = "viewer";
-    if let Some(w) = app.webview_windows().get(label) {
-        let _ = w.set_focus();
-        return Ok(());
+    let view = saved_view_state(&app.state::<PersistenceState>());
+    if let Some(label) = label {
+        if let Some(w) = app.webview_windows().get(&label) {
+            {
+                let mut viewers = state.inner.lock().map_err(|e| e.to_string())?;
+                let ctx = viewers
+                    .contexts
+                    .entry(label.clone())
+                    .or_insert_with(|| ViewerContext::new(Vec::new(), 0, view));
+                ctx.paths = paths;
+                ctx.index = index;
+                ctx.view = ctx.view.for_new_image();
+                ctx.reshuffle();
+            }
+            let _ = w.set_focus();
+            return Ok(label);
+        }
     }
+    let label = {
+        let mut viewers = state.inner.lock().map_err(|e| e.to_string())?;
+        let label = format!("{}{}", VIEWER_LABEL_PREFIX, viewers.next_id);
+        viewers.next_id += 1;
+        viewers
+            .contexts
+            .insert(label.clone(), ViewerContext::new(paths, index, view));
+        label
+    };
     let url = WebviewUrl::App("viewer.html".into());
-    WebviewWindowBuilder::new(&app, label, url)
+    let window = WebviewWindowBuilder::new(&app, &label, url)
         .title("V-See – Viewer")
         .inner_size(1200.0, 800.0)
         .min_inner_size(400.0, 300.0)
         .resizable(true)
         .build()
-        .map_err(|e| e.to_string())?;
+        .map_err(|e| {
+            if let Ok(mut viewers) = state.inner.lock() {
+                viewers.contexts.remove(&label);
+            }
+            e.to_string()
+        })?;
+    // Drop the context when the window goes away so closed viewers don't leak.
+    let app_handle = app.clone();
+    let closed_label = label.clone();
+    window.on_window_event(move |event| {
+        if let WindowEvent::Destroyed = event {
+            let state = app_handle.state::<ViewerState>();
+            if let Ok(mut viewers) = state.inner.lock() {
+                viewers.contexts.remove(&closed_label);
+            };
+            app_handle.state::<SlideshowState>().stop(&closed_label);
+        }
+    });
+    let persistence = app.state::<PersistenceState>();
+    restore_window_geometry(&window, &persistence, VIEWER_WINDOW_GEOMETRY_KEY);
+    track_window_geometry(&window, VIEWER_WINDOW_GEOMETRY_KEY);
+    let restore_fullscreen = persistence
+        .get(VIEWER_FULLSCREEN_KEY)
+        .ok()
+        .flatten()
+        .map(|v| v == "true")
+        .unwrap_or(false);
+    if restore_fullscreen {
+        let mut viewers = state.inner.lock().map_err(|e| e.to_string())?;
+        if let Some(ctx) = viewers.contexts.get_mut(&label) {
+            apply_fullscreen(&window, ctx, true)?;
+        }
+    }
+    Ok(label)
+}
+
+/// Enters or leaves fullscreen for a viewer window. On enter, remembers the windowed
+/// position/size in the context; on exit, restores them so the window comes back as it was.
+fn apply_fullscreen(
+    window: &WebviewWindow,
+    ctx: &mut ViewerContext,
+    fullscreen: bool,
+) -> Result<(), String> {
+    let is_fullscreen = window.is_fullscreen().map_err(|e| e.to_string())?;
+    if fullscreen {
+        if !is_fullscreen {
+            let pos = window.outer_position().map_err(|e| e.to_string())?;
+            let size = window.inner_size().map_err(|e| e.to_string())?;
+            ctx.windowed_geometry = Some((pos, size));
+        }
+        window.set_fullscreen(true).map_err(|e| e.to_string())?;
+    } else {
+        window.set_fullscreen(false).map_err(|e| e.to_string())?;
+        if let Some((pos, size)) = ctx.windowed_geometry.take() {
+            let _ = window.set_size(size);
+            let _ = window.set_position(pos);
+        }
+    }
     Ok(())
 }
 
-/// Returns the current viewer context (paths and index) for the viewer window to display.
+/// Sets fullscreen on/off for the viewer window with the given label and persists the choice
+/// so the next viewer opens the same way.
+#[tauri::command]
+pub fn set_viewer_fullscreen(
+    app: AppHandle,
+    label: String,
+    fullscreen: bool,
+    state: State<'_, ViewerState>,
+    persistence: State<'_, PersistenceState>,
+) -> Result<(), String> {
+    let window = app
+        .get_webview_window(&label)
+        .ok_or_else(|| "Viewer window is not open.".to_string())?;
+    {
+        let mut viewers = state.inner.lock().map_err(|e| e.to_string())?;
+        let ctx = viewers
+            .contexts
+            .get_mut(&label)
+            .ok_or_else(|| format!("No viewer context for window '{}'.", label))?;
+        apply_fullscreen(&window, ctx, fullscreen)?;
+    }
+    persistence.set(
+        VIEWER_FULLSCREEN_KEY,
+        if fullscreen { "true" } else { "false" },
+    )
+}
+
+/// Flips fullscreen for the viewer window with the given label (same handling and persistence
+/// as set_viewer_fullscreen) and returns the new state.
 #[tauri::command]
-pub fn get_viewer_context(state: State<'_, ViewerState>) -> Result<(Vec<String>, usize), String> {
-    let ctx = state.inner.lock().map_err(|e| e.to_string())?;
+pub fn viewer_toggle_fullscreen(
+    app: AppHandle,
+    label: String,
+    state: State<'_, ViewerState>,
+    persistence: State<'_, PersistenceState>,
+) -> Result<bool, String> {
+    let window = app
+        .get_webview_window(&label)
+        .ok_or_else(|| "Viewer window is not open.".to_string())?;
+    let fullscreen = !window.is_fullscreen().map_err(|e| e.to_string())?;
+    set_viewer_fullscreen(app, label, fullscreen, state, persistence)?;
+    Ok(fullscreen)
+}
+
+/// Returns the current context (paths and index) of the viewer window with the given label.
+#[tauri::command]
+pub fn get_viewer_context(
+    label: String,
+    state: State<'_, ViewerState>,
+) -> Result<(Vec<String>, usize), String> {
+    let viewers = state.inner.lock().map_err(|e| e.to_string())?;
+    let ctx = viewers
+        .contexts
+        .get(&label)
+        .ok_or_else(|| format!("No viewer context for window '{}'.", label))?;
     Ok((ctx.paths.clone(), ctx.index))
 }
 
-/// Moves to the previous item (wrap to end) and returns the current path and name.
+/// Moves to the previous item and returns the current path and name. Follows the shuffle
+/// order when shuffle is on; at the start it wraps to the end, or returns None without repeat.
+#[tauri::command]
+pub fn viewer_prev(
+    app: AppHandle,
+    label: String,
+    state: State<'_, ViewerState>,
+) -> Result<Option<(String, String)>, String> {
+    let mut viewers = state.inner.lock().map_err(|e| e.to_string())?;
+    let ctx = match viewers.contexts.get_mut(&label) {
+        Some(ctx) => ctx,
+        None => return Ok(None),
+    };
+    Ok(ctx
+        .step_index(false)
+        .and_then(|index| select_index(&app, &label, ctx, index)))
+}
+
+/// Moves to the next item and returns the current path and name. Follows the shuffle order
+/// when shuffle is on; at the end it wraps to the start, or returns None without repeat.
+#[tauri::command]
+pub fn viewer_next(
+    app: AppHandle,
+    label: String,
+    state: State<'_, ViewerState>,
+) -> Result<Option<(String, String)>, String> {
+    let mut viewers = state.inner.lock().map_err(|e| e.to_string())?;
+    let ctx = match viewers.contexts.get_mut(&label) {
+        Some(ctx) => ctx,
+        None => return Ok(None),
+    };
+    Ok(ctx
+        .step_index(true)
+        .and_then(|index| select_index(&app, &label, ctx, index)))
+}
+
+/// Moves the viewer label to its next item on behalf of the slideshow thread. None when the
+/// viewer is gone, its list is empty, or the end is reached without repeat, which ends the
+/// slideshow.
+pub(crate) fn advance_viewer(app: &AppHandle, label: &str) -> Option<(String, String)> {
+    let state = app.state::<ViewerState>();
+    let mut viewers = state.inner.lock().ok()?;
+    let ctx = viewers.contexts.get_mut(label)?;
+    let index = ctx.step_index(true)?;
+    select_index(app, label, ctx, index)
+}
+
+/// Jumps to the item at index (clamped to the list) and returns its path and name, e.g. when a
+/// thumbnail is clicked in the main window. None if the label is unknown or the list is empty.
 #[tauri::command]
-pub fn viewer_prev(state: State<'_, ViewerState>) -> Result<Option<(String, String)>, String> {
-    let mut ctx = state.inner.lock().map_err(|e| e.to_string())?;
+pub fn viewer_set_index(
+    app: AppHandle,
+    label: String,
+    index: usize,
+    state: State<'_, ViewerState>,
+) -> Result<Option<(String, String)>, String> {
+    let mut viewers = state.inner.lock().map_err(|e| e.to_string())?;
+    let ctx = match viewers.contexts.get_mut(&label) {
+        Some(ctx) => ctx,
+        None => return Ok(None),
+    };
     if ctx.paths.is_empty() {
         return Ok(None);
     }
-    ctx.index = if ctx.index == 0 {
-        ctx.paths.len() - 1
+    let index = index.min(ctx.paths.len() - 1);
+    Ok(jump_to_index(&app, &label, ctx, index))
+}
+
+/// Jumps to the item whose path equals path and returns its path and name. None if the label
+/// is unknown or path is not in the viewer's list (the current item is left unchanged).
+#[tauri::command]
+pub fn viewer_jump_to_path(
+    app: AppHandle,
+    label: String,
+    path: String,
+    state: State<'_, ViewerState>,
+) -> Result<Option<(String, String)>, String> {
+    let mut viewers = state.inner.lock().map_err(|e| e.to_string())?;
+    let ctx = match viewers.contexts.get_mut(&label) {
+        Some(ctx) => ctx,
+        None => return Ok(None),
+    };
+    match ctx.paths.iter().position(|p| *p == path) {
+        Some(index) => Ok(jump_to_index(&app, &label, ctx, index)),
+        None => Ok(None),
+    }
+}
+
+/// Replaces the path list of a viewer, e.g. after files were deleted or renamed. With
+/// keep_current the item being shown stays current if it is still in the list; otherwise (or
+/// when it is gone) the index is clamped to the new list. Returns the current path and name;
+/// None if the label is unknown or the new list is empty.
+#[tauri::command]
+pub fn viewer_update_paths(
+    app: AppHandle,
+    label: String,
+    paths: Vec<String>,
+    keep_current: bool,
+    state: State<'_, ViewerState>,
+) -> Result<Option<(String, String)>, String> {
+    let mut viewers = state.inner.lock().map_err(|e| e.to_string())?;
+    let ctx = match viewers.contexts.get_mut(&label) {
+        Some(ctx) => ctx,
+        None => return Ok(None),
+    };
+    let shown = ctx.paths.get(ctx.index).cloned();
+    let kept = if keep_current {
+        shown
+            .as_ref()
+            .and_then(|s| paths.iter().position(|p| p == s))
     } else {
-        ctx.index - 1
+        None
     };
-    let path = ctx.paths[ctx.index].clone();
-    let name = std::path::Path::new(&path)
-        .file_name()
-        .and_then(|n| n.to_str())
-        .unwrap_or("")
-        .to_string();
-    Ok(Some((path, name)))
+    let index = kept.unwrap_or_else(|| ctx.index.min(paths.len().saturating_sub(1)));
+    ctx.paths = paths;
+    ctx.index = index;
+    ctx.reshuffle();
+    if ctx.paths.is_empty() {
+        return Ok(None);
+    }
+    if ctx.paths.get(index) == shown.as_ref() {
+        // Same image still shown: keep its zoom and pan, nothing to announce.
+        return Ok(ctx.current_item());
+    }
+    Ok(select_index(&app, &label, ctx, index))
 }
 
-/// Moves to the next item (wrap to start) and returns the current path and name.
+/// Drops the current item from a viewer's list (e.g. after it was deleted) and moves to the
+/// item that followed it, wrapping to the start. When the list becomes empty the viewer
+/// window is closed and None is returned.
 #[tauri::command]
-pub fn viewer_next(state: State<'_, ViewerState>) -> Result<Option<(String, String)>, String> {
-    let mut ctx = state.inner.lock().map_err(|e| e.to_string())?;
+pub fn viewer_remove_current(
+    app: AppHandle,
+    label: String,
+    state: State<'_, ViewerState>,
+) -> Result<Option<(String, String)>, String> {
+    let mut viewers = state.inner.lock().map_err(|e| e.to_string())?;
+    let ctx = match viewers.contexts.get_mut(&label) {
+        Some(ctx) => ctx,
+        None => return Ok(None),
+    };
+    if ctx.index < ctx.paths.len() {
+        ctx.paths.remove(ctx.index);
+    }
     if ctx.paths.is_empty() {
+        ctx.index = 0;
+        drop(viewers);
+        if let Some(w) = app.get_webview_window(&label) {
+            let _ = w.close();
+        }
         return Ok(None);
     }
-    ctx.index = (ctx.index + 1) % ctx.paths.len();
-    let path = ctx.paths[ctx.index].clone();
-    let name = std::path::Path::new(&path)
-        .file_name()
-        .and_then(|n| n.to_str())
-        .unwrap_or("")
-        .to_string();
-    Ok(Some((path, name)))
+    if ctx.index >= ctx.paths.len() {
+        ctx.index = 0;
+    }
+    ctx.reshuffle();
+    let index = ctx.index;
+    Ok(select_index(&app, &label, ctx, index))
+}
+
+/// Sets shuffle (random walk order) and repeat (wrap at the ends) for the viewer label.
+/// Turning shuffle on builds a new order starting at the current item.
+#[tauri::command]
+pub fn viewer_set_mode(
+    label: String,
+    shuffle: bool,
+    repeat: bool,
+    state: State<'_, ViewerState>,
+) -> Result<(), String> {
+    let mut viewers = state.inner.lock().map_err(|e| e.to_string())?;
+    let ctx = viewers
+        .contexts
+        .get_mut(&label)
+        .ok_or_else(|| format!("No viewer context for window '{}'.", label))?;
+    ctx.repeat = repeat;
+    if ctx.shuffle != shuffle {
+        ctx.shuffle = shuffle;
+        ctx.reshuffle();
+    }
+    Ok(())
+}
+
+/// Returns the current path and name without moving, or None if the label is unknown or the
+/// list is empty. Used by context actions (copy path, reveal in Explorer).
+#[tauri::command]
+pub fn viewer_current(
+    label: String,
+    state: State<'_, ViewerState>,
+) -> Result<Option<(String, String)>, String> {
+    let viewers = state.inner.lock().map_err(|e| e.to_string())?;
+    Ok(viewers
+        .contexts
+        .get(&label)
+        .and_then(ViewerContext::current_item))
+}
+
+/// Stores the zoom, fit mode, and pan of the viewer window with the given label and returns
+/// the applied (clamped) state. Also saved (debounced) as the default for new viewers and the
+/// next session. Moving to another image keeps the fit mode and resets zoom and pan.
+#[tauri::command]
+pub fn set_viewer_view_state(
+    label: String,
+    state: ViewerViewState,
+    viewers: State<'_, ViewerState>,
+    persistence: State<'_, PersistenceState>,
+) -> Result<ViewerViewState, String> {
+    let view = state.sanitized();
+    {
+        let mut viewers = viewers.inner.lock().map_err(|e| e.to_string())?;
+        let ctx = viewers
+            .contexts
+            .get_mut(&label)
+            .ok_or_else(|| format!("No viewer context for window '{}'.", label))?;
+        ctx.view = view;
+    }
+    let json = serde_json::to_string(&view).map_err(|e| e.to_string())?;
+    persistence.set_debounced(
+        VIEWER_VIEW_STATE_KEY,
+        &json,
+        Duration::from_millis(DEFAULT_DEBOUNCE_MS),
+    );
+    Ok(view)
+}
+
+/// Returns the view state of the viewer window with the given label, or the saved default
+/// when the label has no context.
+#[tauri::command]
+pub fn get_viewer_view_state(
+    label: String,
+    viewers: State<'_, ViewerState>,
+    persistence: State<'_, PersistenceState>,
+) -> Result<ViewerViewState, String> {
+    let viewers = viewers.inner.lock().map_err(|e| e.to_string())?;
+    Ok(match viewers.contexts.get(&label) {
+        Some(ctx) => ctx.view,
+        None => saved_view_state(&persistence),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context(len: usize, index: usize) -> ViewerContext {
+        let paths = (0..len).map(|i| format!("/photos/{}.jpg", i)).collect();
+        ViewerContext::new(paths, index, ViewerViewState::default())
+    }
+
+    #[test]
+    fn step_wraps_with_repeat() {
+        let ctx = context(3, 2);
+        assert_eq!(ctx.step_index(true), Some(0));
+        let ctx = context(3, 0);
+        assert_eq!(ctx.step_index(false), Some(2));
+        assert_eq!(ctx.step_index(true), Some(1));
+    }
+
+    #[test]
+    fn step_stops_at_the_ends_without_repeat() {
+        let mut ctx = context(3, 2);
+        ctx.repeat = false;
+        assert_eq!(ctx.step_index(true), None);
+        assert_eq!(ctx.step_index(false), Some(1));
+        ctx.index = 0;
+        assert_eq!(ctx.step_index(false), None);
+        assert_eq!(context(0, 0).step_index(true), None);
+    }
+
+    #[test]
+    fn reshuffle_starts_at_the_current_item_and_visits_each_once() {
+        let mut ctx = context(10, 4);
+        ctx.shuffle = true;
+        ctx.reshuffle();
+        assert_eq!(ctx.order[0], 4);
+        let mut seen = ctx.order.clone();
+        seen.sort_unstable();
+        assert_eq!(seen, (0..10).collect::<Vec<_>>());
+        // A full pass in shuffle order comes back to the start, or stops before it.
+        let mut visited = vec![ctx.index];
+        for _ in 1..10 {
+            ctx.index = ctx.step_index(true).unwrap();
+            visited.push(ctx.index);
+        }
+        assert_eq!(visited, ctx.order);
+        assert_eq!(ctx.step_index(true), Some(4));
+        ctx.repeat = false;
+        assert_eq!(ctx.step_index(true), None);
+        ctx.index = 4;
+        assert_eq!(ctx.step_index(false), None);
+    }
+
+    #[test]
+    fn reshuffle_clears_the_order_when_shuffle_is_off() {
+        let mut ctx = context(5, 1);
+        ctx.shuffle = true;
+        ctx.reshuffle();
+        ctx.shuffle = false;
+        ctx.reshuffle();
+        assert!(ctx.order.is_empty());
+        assert_eq!(ctx.step_index(true), Some(2));
+    }
 }
@@ -0,0 +1,142 @@
+// Author: Viorel LUPU
+// Date: 2026-10-14
+// Purpose: Save and restore window position/size through the persistence store.
+// Geometry is stored as JSON {x, y, width, height} in physical pixels and clamped to a
+// visible monitor work area on restore (e.g. when the monitor it was on was unplugged).
+
+use super::persistence::{PersistenceState, DEFAULT_DEBOUNCE_MS};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tauri::{Manager, PhysicalPosition, PhysicalSize, WebviewWindow, WindowEvent};
+
+/// Saved sizes below this are treated as corrupt and ignored.
+const MIN_RESTORED_WIDTH: u32 = 200;
+const MIN_RESTORED_HEIGHT: u32 = 150;
+
+/// Purpose: Saved window position and size (physical pixels).
+/// Usage: Serialized as JSON under the *_window_geometry persistence keys.
+/// Reason for choice: Physical pixels match what Tauri reports for position/size, so no DPI conversion is needed.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct WindowGeometry {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Work area of one monitor as (x, y, width, height), excluding the taskbar.
+type WorkArea = (i32, i32, u32, u32);
+
+/// Work areas of all monitors, primary first (used as the fallback target).
+fn work_areas(window: &WebviewWindow) -> Vec<WorkArea> {
+    let mut areas: Vec<WorkArea> = Vec::new();
+    let mut push = |m: &tauri::Monitor| {
+        let r = m.work_area();
+        let area = (r.position.x, r.position.y, r.size.width, r.size.height);
+        if area.2 > 0 && area.3 > 0 && !areas.contains(&area) {
+            areas.push(area);
+        }
+    };
+    if let Ok(Some(primary)) = window.primary_monitor() {
+        push(&primary);
+    }
+    if let Ok(monitors) = window.available_monitors() {
+        for m in &monitors {
+            push(m);
+        }
+    }
+    areas
+}
+
+/// Fits the geometry into the monitor that contains its title bar, or the primary monitor
+/// if none does (monitor unplugged, resolution changed). Returns None if nothing usable.
+fn clamp_to_work_areas(geom: WindowGeometry, areas: &[WorkArea]) -> Option<WindowGeometry> {
+    if geom.width < MIN_RESTORED_WIDTH || geom.height < MIN_RESTORED_HEIGHT {
+        return None;
+    }
+    let fallback = *areas.first()?;
+    // A point just inside the title bar: if that is visible the user can grab the window.
+    let probe_x = geom.x.saturating_add(geom.width.min(200) as i32 / 2);
+    let probe_y = geom.y.saturating_add(10);
+    let (ax, ay, aw, ah) = areas
+        .iter()
+        .copied()
+        .find(|&(ax, ay, aw, ah)| {
+            probe_x >= ax && probe_x < ax + aw as i32 && probe_y >= ay && probe_y < ay + ah as i32
+        })
+        .unwrap_or(fallback);
+    let width = geom.width.min(aw);
+    let height = geom.height.min(ah);
+    Some(WindowGeometry {
+        x: geom.x.clamp(ax, ax + (aw - width) as i32),
+        y: geom.y.clamp(ay, ay + (ah - height) as i32),
+        width,
+        height,
+    })
+}
+
+/// Reads geometry saved under key and applies it to the window, clamped to the current
+/// work area. Returns false (window left as built) when nothing valid is stored.
+pub fn restore_window_geometry(
+    window: &WebviewWindow,
+    persistence: &PersistenceState,
+    key: &str,
+) -> bool {
+    let saved = match persistence.get(key) {
+        Ok(Some(v)) => v,
+        _ => return false,
+    };
+    let geom: WindowGeometry = match serde_json::from_str(&saved) {
+        Ok(g) => g,
+        Err(_) => return false,
+    };
+    let geom = match clamp_to_work_areas(geom, &work_areas(window)) {
+        Some(g) => g,
+        None => return false,
+    };
+    let _ = window.set_size(PhysicalSize::new(geom.width, geom.height));
+    let _ = window.set_position(PhysicalPosition::new(geom.x, geom.y));
+    true
+}
+
+/// Stores the window's current geometry under key (debounced, so a drag ends in one write).
+/// Skipped while fullscreen, maximized, or minimized so the restored window gets its normal
+/// size back.
+fn save_window_geometry(window: &WebviewWindow, key: &str) {
+    if window.is_fullscreen().unwrap_or(false)
+        || window.is_maximized().unwrap_or(false)
+        || window.is_minimized().unwrap_or(false)
+    {
+        return;
+    }
+    let (pos, size) = match (window.outer_position(), window.inner_size()) {
+        (Ok(p), Ok(s)) => (p, s),
+        _ => return,
+    };
+    let geom = WindowGeometry {
+        x: pos.x,
+        y: pos.y,
+        width: size.width,
+        height: size.height,
+    };
+    if let Ok(json) = serde_json::to_string(&geom) {
+        window
+            .app_handle()
+            .state::<PersistenceState>()
+            .set_debounced(key, &json, Duration::from_millis(DEFAULT_DEBOUNCE_MS));
+    }
+}
+
+/// Saves the window's geometry under key whenever it is moved or resized, and once more when
+/// it is asked to close.
+pub fn track_window_geometry(window: &WebviewWindow, key: &'static str) {
+    let w = window.clone();
+    window.on_window_event(move |event| {
+        if matches!(
+            event,
+            WindowEvent::Moved(_) | WindowEvent::Resized(_) | WindowEvent::CloseRequested { .. }
+        ) {
+            save_window_geometry(&w, key);
+        }
+    });
+}
@@ -6,47 +6,211 @@
 
 mod commands;
 
+use tauri::Manager;
+
 use commands::{
-    debug_log, get_all_persisted, get_debug_log_path, get_folder_roots, get_persistence_db_path,
-    get_persisted, get_parent_path, get_video_thumbnail_data_url, get_viewer_context, list_directory,
-    open_help_window, open_viewer_window, pause_audio, play_audio, read_file_as_audio_url,
-    read_file_as_data_url, set_persisted, stop_audio, viewer_next, viewer_prev, AudioState,
-    PersistenceState, ViewerState,
+    add_favorite_folder, apply_log_format_env, backup_persistence, cancel_operation,
+    check_external_tools, check_persistence_integrity, clear_all_caches, clear_loop_region,
+    clear_persisted, clear_thumbnail_cache, compare_files, copy_path, copy_text_to_clipboard,
+    create_directory, debug_log, delete_paths_to_recycle_bin, delete_persisted, delete_to_trash,
+    enqueue, find_duplicate_images, flush_drive, get_all_persisted, get_audio_device_status,
+    get_audio_waveform, get_cache_dir, get_debug_log_path, get_directory_stats, get_external_app,
+    get_ffmpeg_status, get_file_info, get_folder_roots, get_image_animation_info, get_image_exif,
+    get_image_metadata, get_image_preview_data_url, get_image_thumbnail, get_parent_path,
+    get_persisted, get_persisted_json, get_persistence_db_path, get_play_history,
+    get_playback_speed, get_playback_status, get_raw_preview_data_url, get_recent_folders,
+    get_recent_log_lines, get_slideshow_interval, get_slideshow_music_enabled,
+    get_supported_formats, get_video_thumbnail_at_percent, get_video_thumbnail_data_url,
+    get_video_thumbnail_strip, get_viewer_context, get_viewer_view_state, get_volume, glob_files,
+    init_ffmpeg_locations, list_audio_devices, list_directory, list_directory_page,
+    list_directory_streaming, list_favorite_folders, list_playlists, load_playlist, move_paths,
+    open_help_window, open_in_external_app, open_viewer_window, path_accessible, pause_audio,
+    play_audio, prefetch_thumbnails, push_recent_folder, read_file_as_audio_url,
+    read_file_as_data_url, read_file_range, read_text_file, reinit_audio_device,
+    remove_favorite_folder, rename_path, restore_persistence, restore_window_geometry,
+    resume_last_track, rotate_image_file, run_diagnostics, save_playlist, scan_media_recursive,
+    set_audio_device, set_cache_dir, set_external_app, set_log_format, set_log_level,
+    set_loop_region, set_persisted, set_persisted_debounced, set_persisted_json, set_persisted_many,
+    set_playback_speed, set_queue, set_slideshow_interval, set_slideshow_music_enabled,
+    set_slideshow_paused, set_thumbnail_concurrency, set_viewer_fullscreen, set_viewer_view_state,
+    set_volume, skip_next, slideshow_is_running, start_slideshow, stop_audio, stop_slideshow,
+    track_window_geometry, unwatch_directory, viewer_current, viewer_jump_to_path, viewer_next,
+    viewer_prev, viewer_remove_current, viewer_set_index, viewer_set_mode, viewer_toggle_fullscreen,
+    viewer_update_paths, watch_directory, AUDIO_DEVICE_KEY, AudioState, CancellationState,
+    MAIN_WINDOW_GEOMETRY_KEY, PLAYBACK_SPEED_KEY, PLAYBACK_VOLUME_KEY, PersistenceState,
+    SlideshowState, ViewerState, WatchState,
 };
 
 fn main() {
     tauri::Builder::default()
-        .manage(
-            AudioState::new().unwrap_or_else(|e| {
-                eprintln!("Audio init failed: {}", e);
-                panic!("AudioState::new failed");
-            }),
-        )
-        .manage(PersistenceState::new())
+        .plugin(tauri_plugin_clipboard_manager::init())
+        .manage(AudioState::new())
         .manage(ViewerState::default())
+        .manage(SlideshowState::default())
+        .manage(CancellationState::default())
+        .manage(WatchState::default())
         .invoke_handler(tauri::generate_handler![
             get_folder_roots,
+            flush_drive,
             list_directory,
+            list_directory_page,
+            list_directory_streaming,
             get_parent_path,
+            get_file_info,
+            path_accessible,
+            get_directory_stats,
+            copy_path,
+            delete_paths_to_recycle_bin,
+            move_paths,
+            rename_path,
+            watch_directory,
+            unwatch_directory,
+            delete_to_trash,
+            create_directory,
+            glob_files,
+            scan_media_recursive,
+            find_duplicate_images,
+            compare_files,
+            cancel_operation,
             read_file_as_data_url,
+            read_file_range,
+            get_image_preview_data_url,
             read_file_as_audio_url,
+            read_text_file,
             get_video_thumbnail_data_url,
+            get_video_thumbnail_at_percent,
+            get_video_thumbnail_strip,
+            set_thumbnail_concurrency,
+            get_image_thumbnail,
+            prefetch_thumbnails,
+            get_image_exif,
+            get_image_metadata,
+            rotate_image_file,
+            get_external_app,
+            open_in_external_app,
+            set_external_app,
+            get_supported_formats,
+            check_external_tools,
+            get_ffmpeg_status,
             open_help_window,
             open_viewer_window,
             get_viewer_context,
             viewer_prev,
+            viewer_set_index,
+            viewer_jump_to_path,
+            viewer_update_paths,
+            viewer_remove_current,
+            viewer_set_mode,
             viewer_next,
+            viewer_current,
+            get_viewer_view_state,
+            set_viewer_view_state,
+            copy_text_to_clipboard,
+            set_viewer_fullscreen,
+            viewer_toggle_fullscreen,
             debug_log,
             get_debug_log_path,
+            get_cache_dir,
+            set_cache_dir,
+            clear_all_caches,
+            clear_thumbnail_cache,
+            set_log_format,
             get_persisted,
             set_persisted,
+            set_persisted_many,
+            get_persisted_json,
+            set_persisted_json,
+            delete_persisted,
+            clear_persisted,
+            backup_persistence,
+            restore_persistence,
+            check_persistence_integrity,
+            set_persisted_debounced,
             get_all_persisted,
             get_persistence_db_path,
+            get_slideshow_interval,
+            get_slideshow_music_enabled,
+            set_slideshow_interval,
+            set_slideshow_music_enabled,
             play_audio,
+            set_queue,
+            enqueue,
+            skip_next,
+            resume_last_track,
             stop_audio,
             pause_audio,
+            set_playback_speed,
+            get_playback_speed,
+            set_volume,
+            get_volume,
+            get_playback_status,
+            list_audio_devices,
+            set_audio_device,
+            reinit_audio_device,
+            get_audio_device_status,
+            get_audio_waveform,
+            set_loop_region,
+            clear_loop_region,
+            push_recent_folder,
+            get_recent_folders,
+            add_favorite_folder,
+            remove_favorite_folder,
+            save_playlist,
+            load_playlist,
+            list_playlists,
+            get_play_history,
+            list_favorite_folders,
+            run_diagnostics,
+            get_recent_log_lines,
+            set_log_level,
+            start_slideshow,
+            stop_slideshow,
+            set_slideshow_paused,
+            slideshow_is_running,
+            get_image_animation_info,
+            get_raw_preview_data_url,
         ])
-        .setup(|_app| Ok(()))
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .setup(|app| {
+            apply_log_format_env();
+            app.manage(PersistenceState::new(app.handle().clone()));
+            init_ffmpeg_locations(app.handle());
+            let backup_app = app.handle().clone();
+            std::thread::spawn(move || {
+                if let Err(e) = backup_app.state::<PersistenceState>().backup_on_startup() {
+                    eprintln!("Settings backup failed: {}", e);
+                }
+            });
+            if let Some(main_window) = app.get_webview_window("main") {
+                let persistence = app.state::<PersistenceState>();
+                restore_window_geometry(&main_window, &persistence, MAIN_WINDOW_GEOMETRY_KEY);
+                track_window_geometry(&main_window, MAIN_WINDOW_GEOMETRY_KEY);
+            }
+            let persistence = app.state::<PersistenceState>();
+            let saved_speed = persistence.get(PLAYBACK_SPEED_KEY).ok().flatten();
+            if let Some(speed) = saved_speed.and_then(|v| v.parse::<f32>().ok()) {
+                let _ = app.state::<AudioState>().apply_speed(speed);
+            }
+            let saved_volume = persistence.get(PLAYBACK_VOLUME_KEY).ok().flatten();
+            if let Some(level) = saved_volume.and_then(|v| v.parse::<f32>().ok()) {
+                let _ = app.state::<AudioState>().apply_volume(level);
+            }
+            let saved_device = persistence.get(AUDIO_DEVICE_KEY).ok().flatten();
+            if let Some(name) = saved_device.filter(|n| !n.is_empty()) {
+                let _ = app
+                    .state::<AudioState>()
+                    .restore_device(app.handle().clone(), Some(name));
+            }
+            Ok(())
+        })
+        .build(tauri::generate_context!())
+        .expect("error while running tauri application")
+        .run(|app, event| {
+            if let tauri::RunEvent::Exit = event {
+                // Debounced writes (window geometry, sliders) still in memory go to disk now.
+                if let Some(persistence) = app.try_state::<PersistenceState>() {
+                    let _ = persistence.flush_pending();
+                }
+                app.state::<WatchState>().clear();
+            }
+        });
 }
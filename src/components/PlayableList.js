@@ -2,15 +2,29 @@
 // Date: 2026-02-17
 // Purpose: Lists audio files in the selected music folder and lets user select a track for playback.
 
-const AUDIO_EXT = new Set([
+// Default until the backend's get_supported_formats answers (see loadSupportedFormats).
+let AUDIO_EXT = new Set([
     'mp3', 'wav', 'ogg', 'm4a', 'aac', 'flac', 'wma', 'opus', 'webm'
 ]);
+let formatsLoaded = false;
 
 /** Tauri invoke; null when not running inside the app. */
 function getInvoke() {
     return window.__TAURI__?.core?.invoke;
 }
 
+/** Replaces the audio extension set with the backend's table (once per session). */
+async function loadSupportedFormats() {
+    const inv = getInvoke();
+    if (formatsLoaded || !inv) return;
+    try {
+        const formats = await inv('get_supported_formats');
+        AUDIO_EXT = new Set(formats.audio);
+        formatsLoaded = true;
+    } catch (_) {
+    }
+}
+
 /** True if the filename has an audio extension we can play. */
 function isAudioFile(name) {
     const ext = name.split('.').pop()?.toLowerCase() || '';
@@ -33,6 +47,7 @@ export async function loadPlayableList(listEl, folderPath, onSelect, initialSele
         listEl.innerHTML = '<p class="placeholder">Tauri API not available</p>';
         return [];
     }
+    await loadSupportedFormats();
     let result;
     try {
         result = await invokeFn('list_directory', { path: folderPath });
@@ -3,12 +3,16 @@
 // Purpose: Renders a grid of image/video thumbnails for the selected folder.
 // HEIC/PDF show as a frame with label; JPG, PNG, ICO, SVG and videos show as thumbnails.
 
-const IMAGE_EXT = new Set([
+// Defaults until the backend's get_supported_formats answers (see loadSupportedFormats).
+let IMAGE_EXT = new Set([
     'jpg', 'jpeg', 'png', 'gif', 'bmp', 'webp', 'tiff', 'tif', 'ico', 'svg'
 ]);
-const VIDEO_EXT = new Set(['mp4', 'mov', 'avi', 'webm', 'mkv', 'm4v', 'wmv']);
+let VIDEO_EXT = new Set(['mp4', 'mov', 'avi', 'webm', 'mkv', 'm4v', 'wmv']);
+let formatsLoaded = false;
 const HEIC_EXT = new Set(['heic', 'heif']);
 const PDF_EXT = new Set(['pdf']);
+/** Video grid thumbnails: small JPEGs keep the data URLs compact. */
+const VIDEO_THUMB_OPTIONS = { max_edge: 320, format: 'jpeg' };
 
 /** Tauri invoke; null when not running inside the app. */
 function getInvoke() {
@@ -20,6 +24,19 @@ function getConvertFileSrc() {
     return window.__TAURI__?.core?.convertFileSrc;
 }
 
+/** Replaces the image/video extension sets with the backend's table (once per session). */
+async function loadSupportedFormats() {
+    const inv = getInvoke();
+    if (formatsLoaded || !inv) return;
+    try {
+        const formats = await inv('get_supported_formats');
+        IMAGE_EXT = new Set(formats.image);
+        VIDEO_EXT = new Set(formats.video);
+        formatsLoaded = true;
+    } catch (_) {
+    }
+}
+
 /** Lowercase file extension from a filename. */
 function getExt(name) {
     return (name.split('.').pop() || '').toLowerCase();
@@ -50,7 +67,7 @@ function isVideo(name) {
  * Loads the given folder path and renders thumbnails into gridEl.
  * HEIC/PDF: frame with "HEIC" or "PDF" text. Others: image or video thumbnail.
  * @param {(path: string, name: string) => void} [onSelect] - Single click: preview + persist selection
- * @param {(paths: string[], index: number) => void} [onActivate] - Double click: open viewer window
+ * @param {(paths: string[], index: number, newWindow: boolean) => void} [onActivate] - Double click: open viewer window (Ctrl: open an additional one)
  * @param {string} [initialSelectedPath] - Optional: path to select after load (restore; defensive: ignored if not in list)
  */
 export async function loadThumbnails(gridEl, folderPath, onSelect, onActivate, initialSelectedPath) {
@@ -62,6 +79,7 @@ export async function loadThumbnails(gridEl, folderPath, onSelect, onActivate, i
         gridEl.innerHTML = '<p class="placeholder">Tauri API not available</p>';
         return;
     }
+    await loadSupportedFormats();
     let result;
     try {
         result = await invokeFn('list_directory', { path: folderPath });
@@ -111,7 +129,7 @@ export async function loadThumbnails(gridEl, folderPath, onSelect, onActivate, i
                     const inv = getInvoke();
                     if (!inv) return;
                     try {
-                        const dataUrl = await inv('get_video_thumbnail_data_url', { path: file.path });
+                        const dataUrl = await inv('get_video_thumbnail_data_url', { path: file.path, options: VIDEO_THUMB_OPTIONS });
                         if (img.parentNode && cell.getAttribute('data-path') === file.path) {
                             img.src = dataUrl;
                         }
@@ -157,7 +175,7 @@ export async function loadThumbnails(gridEl, folderPath, onSelect, onActivate, i
         });
         cell.addEventListener('dblclick', (e) => {
             e.preventDefault();
-            if (onActivate) onActivate(paths, i);
+            if (onActivate) onActivate(paths, i, e.ctrlKey);
         });
         gridEl.appendChild(cell);
     }
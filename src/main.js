@@ -93,7 +93,7 @@ function showPreview(filePath, name) {
             return;
         }
         try {
-            const dataUrl = await invokeFn('read_file_as_data_url', { path: filePath });
+            const dataUrl = await invokeFn('get_image_preview_data_url', { path: filePath });
             img.src = dataUrl;
             img.onerror = setPlaceholder;
         } catch (_) {
@@ -162,6 +162,8 @@ document.addEventListener('DOMContentLoaded', async () => {
     }
 
     let selectedTrackPath = null;
+    /** Label of the most recently opened viewer window; double-click reuses it, Ctrl+double-click opens another. */
+    let lastViewerLabel = null;
 
     /** Writes current folder/file/track selection to the state DB. Called on beforeunload/pagehide so last selection is saved before exit. */
     function persistCurrentState() {
@@ -175,7 +177,11 @@ document.addEventListener('DOMContentLoaded', async () => {
         createFolderTree(photosContainer, 'Photos folder', (path, opts) => {
             setHelpMessage('');
             currentPhotosFolder = path || currentPhotosFolder;
-            if (!opts?.programmatic) persist('last_folder', path);
+            if (!opts?.programmatic) {
+                persist('last_folder', path);
+                const inv = window.__TAURI__?.core?.invoke;
+                if (typeof inv === 'function' && path) inv('push_recent_folder', { path }).catch(() => {});
+            }
             const initialFile = pendingRestoreSelectedFile || undefined;
             if (pendingRestoreSelectedFile) pendingRestoreSelectedFile = null;
             loadThumbnails(thumbGrid, path, (filePath, name) => {
@@ -184,10 +190,13 @@ document.addEventListener('DOMContentLoaded', async () => {
                 currentPhotosFolder = dirname(filePath) || currentPhotosFolder;
                 persist('last_selected_file', filePath);
                 persist('last_folder', dirname(filePath));
-            }, (paths, startIndex) => {
+            }, (paths, startIndex, newWindow) => {
                 const inv = window.__TAURI__?.core?.invoke;
                 if (typeof inv === 'function' && paths && paths.length) {
-                    inv('open_viewer_window', { paths, startIndex }).catch((e) => setHelpMessage('Could not open viewer: ' + (e?.message || e), true));
+                    const label = newWindow ? null : lastViewerLabel;
+                    inv('open_viewer_window', { paths, startIndex, label })
+                        .then((l) => { lastViewerLabel = l || lastViewerLabel; })
+                        .catch((e) => setHelpMessage('Could not open viewer: ' + (e?.message || e), true));
                 }
             }, initialFile).then(() => {});
         }, reportError, folderToRestore || undefined);
@@ -338,6 +347,23 @@ document.addEventListener('DOMContentLoaded', async () => {
                 updatePlayButtonIcon();
             }
         }).catch(() => {});
+        // Native playback (play_audio) reports decode failures asynchronously.
+        eventApi.listen('audio-play-result', (event) => {
+            const result = event?.payload;
+            if (result && !result.ok) setHelpMessage('Playback failed: ' + (result.error || 'unknown error'), true);
+        }).catch(() => {});
+        // Keep the thumbnail selection on the image the viewer is showing.
+        eventApi.listen('viewer://index-changed', (event) => {
+            const path = event?.payload?.path;
+            if (!path || !thumbGrid) return;
+            for (const cell of thumbGrid.querySelectorAll('.thumbnail-cell')) {
+                if (cell.getAttribute('data-path') === path) {
+                    cell.click();
+                    cell.scrollIntoView({ block: 'nearest', behavior: 'auto' });
+                    break;
+                }
+            }
+        }).catch(() => {});
     }
 
     if (helpBtn) {
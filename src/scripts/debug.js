@@ -3,7 +3,7 @@
  * Date: 2026-02-17
  * Purpose: Debug logging – echoes console.log/warn/error and unhandled errors to a log file.
  * Load this script first (before main.js) so all logs are captured.
- * Log file: Windows %APPDATA%\V-See\logs\v-see.log
+ * Log file: logs\v-see.log in the app data dir (Windows %APPDATA%\com.vsee.windows\logs\v-see.log)
  */
 (function () {
     'use strict';
@@ -27,12 +27,12 @@
         }
     }
 
-    /** Sends a log line to the Tauri debug_log command (file + terminal). */
-    function sendToLog(level, message) {
+    /** Sends a log line to the Tauri debug_log command (file + terminal). source is optional. */
+    function sendToLog(level, message, source) {
         try {
             var invokeFn = window.__TAURI__ && window.__TAURI__.core && window.__TAURI__.core.invoke;
             if (typeof invokeFn !== 'function') return;
-            invokeFn('debug_log', { level: level, message: message }).catch(function () {});
+            invokeFn('debug_log', { level: level, message: message, source: source || null }).catch(function () {});
         } catch (e) {}
     }
 
@@ -54,7 +54,7 @@
         }
         var orig = level === 'warn' ? origWarn : level === 'error' ? origError : origLog;
         orig.apply(console, ['[' + tag + ']'].concat(args));
-        sendToLog(level, message);
+        sendToLog(level, message, tag);
     }
     vseeLog.debug = function () { vseeLog.apply(null, ['debug'].concat(Array.prototype.slice.call(arguments))); };
     vseeLog.log = function () { vseeLog.apply(null, ['log'].concat(Array.prototype.slice.call(arguments))); };
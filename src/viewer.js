@@ -6,7 +6,9 @@
 (function () {
     const invoke = window.__TAURI__?.core?.invoke;
     const convertFileSrc = window.__TAURI__?.core?.convertFileSrc;
-    const getCurrent = window.__TAURI__?.window?.getCurrent;
+    const getCurrentWindow = window.__TAURI__?.window?.getCurrentWindow;
+    /** Label of this viewer window (viewer-1, viewer-2, ...); the backend keys each viewer's context by it. */
+    const viewerLabel = (getCurrentWindow && getCurrentWindow().label) || '';
 
     const contentEl = document.getElementById('viewer-content');
     const filenameEl = document.getElementById('viewer-filename');
@@ -19,8 +21,12 @@
 
     let paths = [];
     let index = 0;
-    let slideshowTimer = null;
+    /** True while the backend slideshow (start_slideshow) runs for this window. */
+    let slideshowRunning = false;
+    /** Path currently displayed; index-changed events for it are ignored. */
+    let shownPath = null;
     let slideshowIntervalMs = 3000;
+    let isFullscreen = false;
 
     /** Basename of path (last segment after / or \). */
     function getName(path) {
@@ -55,6 +61,22 @@
         filenameEl.textContent = '';
     }
 
+    /** Sizes img for the backend view state: fit mode, then zoom and pan on top. */
+    function applyViewState(img, view) {
+        if (!view) return;
+        if (view.fit_mode === 'fit_width') {
+            img.style.width = '100%';
+            img.style.maxWidth = 'none';
+            img.style.maxHeight = 'none';
+        } else if (view.fit_mode === 'actual_size') {
+            img.style.maxWidth = 'none';
+            img.style.maxHeight = 'none';
+        }
+        if (view.zoom !== 1 || view.pan_x || view.pan_y) {
+            img.style.transform = 'translate(' + view.pan_x + 'px, ' + view.pan_y + 'px) scale(' + view.zoom + ')';
+        }
+    }
+
     /** Renders the current image or video in the content area; HEIC/PDF show a placeholder. */
     function showImage(path, name) {
         const ext = getExt(name);
@@ -81,6 +103,11 @@
         img.style.maxWidth = '100%';
         img.style.maxHeight = '100%';
         img.style.objectFit = 'contain';
+        if (invoke) {
+            invoke('get_viewer_view_state', { label: viewerLabel })
+                .then(function (view) { applyViewState(img, view); })
+                .catch(function () {});
+        }
         contentEl.innerHTML = '';
         contentEl.appendChild(img);
         filenameEl.textContent = name;
@@ -110,6 +137,7 @@
 
     /** Updates the viewer to show the given file (calls showImage or placeholder). */
     function updateDisplay(path, name) {
+        shownPath = path || null;
         if (!path) {
             setPlaceholder('No image');
             return;
@@ -120,7 +148,7 @@
     /** Moves to the previous item and updates the display (via viewer_prev). */
     function goPrev() {
         if (!invoke) return;
-        invoke('viewer_prev').then(function (result) {
+        invoke('viewer_prev', { label: viewerLabel }).then(function (result) {
             if (result && result[0]) updateDisplay(result[0], result[1]);
         }).catch(function () {});
     }
@@ -128,30 +156,37 @@
     /** Moves to the next item and updates the display (via viewer_next). */
     function goNext() {
         if (!invoke) return;
-        invoke('viewer_next').then(function (result) {
+        invoke('viewer_next', { label: viewerLabel }).then(function (result) {
             if (result && result[0]) updateDisplay(result[0], result[1]);
         }).catch(function () {});
     }
 
-    /** Starts the slideshow timer (advance to next image at interval). */
+    /** Marks the slideshow as running or stopped and updates the button. */
+    function setSlideshowRunning(on) {
+        slideshowRunning = on;
+        btnSlideshow.textContent = on ? 'Slideshow OFF' : 'Slideshow ON';
+    }
+
+    /**
+     * Starts the backend slideshow (advance to next image at interval). Calling it while running
+     * only changes the interval. Steps arrive as viewer://index-changed events.
+     */
     function startSlideshow() {
-        if (slideshowTimer) return;
-        slideshowTimer = setInterval(goNext, slideshowIntervalMs);
-        btnSlideshow.textContent = 'Slideshow OFF';
+        if (!invoke) return;
+        invoke('start_slideshow', { label: viewerLabel, intervalSeconds: slideshowIntervalMs / 1000 })
+            .then(function () { setSlideshowRunning(true); })
+            .catch(function () {});
     }
 
-    /** Stops the slideshow timer. */
+    /** Stops the backend slideshow. */
     function stopSlideshow() {
-        if (slideshowTimer) {
-            clearInterval(slideshowTimer);
-            slideshowTimer = null;
-        }
-        btnSlideshow.textContent = 'Slideshow ON';
+        if (invoke) invoke('stop_slideshow', { label: viewerLabel }).catch(function () {});
+        setSlideshowRunning(false);
     }
 
     /** Toggles slideshow on or off. */
     function toggleSlideshow() {
-        if (slideshowTimer) stopSlideshow();
+        if (slideshowRunning) stopSlideshow();
         else startSlideshow();
     }
 
@@ -164,22 +199,25 @@
         }
     }
 
-    /** Toggles fullscreen for the viewer window. */
+    /** Enters or leaves fullscreen via the backend (which restores the windowed size on exit and persists the choice). */
+    function setFullscreen(on) {
+        if (!invoke) return;
+        invoke('set_viewer_fullscreen', { label: viewerLabel, fullscreen: on }).then(function () {
+            isFullscreen = on;
+        }).catch(function () {});
+    }
+
+    /** Toggles fullscreen for the viewer window (the backend reads the real window state). */
     function toggleFullscreen() {
-        const w = getCurrent && getCurrent();
-        if (!w) return;
-        w.isFullscreen().then(function (full) {
-            w.setFullscreen(!full);
+        if (!invoke) return;
+        invoke('viewer_toggle_fullscreen', { label: viewerLabel }).then(function (on) {
+            isFullscreen = on === true;
         }).catch(function () {});
     }
 
     document.addEventListener('keydown', function (e) {
         if (e.key === 'Escape') {
-            getCurrent && getCurrent().then(function (w) {
-                w.isFullscreen().then(function (full) {
-                    if (full) w.setFullscreen(false);
-                });
-            });
+            if (isFullscreen) setFullscreen(false);
             return;
         }
         if (e.key === 'ArrowLeft') { goPrev(); e.preventDefault(); }
@@ -196,11 +234,8 @@
             var n = parseInt(interval, 10);
             if (!isNaN(n) && n >= 1 && n <= 3600) {
                 slideshowIntervalMs = n * 1000;
-                if (invoke) invoke('set_persisted', { key: 'slideshow_interval_seconds', value: String(n) }).catch(function () {});
-                if (slideshowTimer) {
-                    stopSlideshow();
-                    startSlideshow();
-                }
+                if (invoke) invoke('set_slideshow_interval', { secs: n }).catch(function () {});
+                if (slideshowRunning) startSlideshow();
             }
         }
     });
@@ -211,13 +246,27 @@
             setPlaceholder('Tauri API not available');
             return;
         }
-        invoke('get_persisted', { key: 'slideshow_interval_seconds' }).then(function (val) {
-            if (val) {
-                var n = parseInt(val, 10);
-                if (!isNaN(n) && n >= 1 && n <= 3600) slideshowIntervalMs = n * 1000;
-            }
+        var eventApi = window.__TAURI__ && window.__TAURI__.event;
+        if (eventApi && typeof eventApi.listen === 'function') {
+            // Slideshow steps (and navigation from other windows) move this viewer.
+            eventApi.listen('viewer://index-changed', function (event) {
+                var p = event && event.payload;
+                if (!p || p.label !== viewerLabel || !p.path || p.path === shownPath) return;
+                index = p.index;
+                updateDisplay(p.path, getName(p.path));
+            }).catch(function () {});
+            eventApi.listen('slideshow-stopped', function (event) {
+                var p = event && event.payload;
+                if (p && p.label === viewerLabel) setSlideshowRunning(false);
+            }).catch(function () {});
+        }
+        invoke('get_slideshow_interval').then(function (secs) {
+            if (typeof secs === 'number') slideshowIntervalMs = secs * 1000;
+        }).catch(function () {});
+        invoke('get_persisted', { key: 'viewer_fullscreen' }).then(function (val) {
+            isFullscreen = val === 'true';
         }).catch(function () {});
-        invoke('get_viewer_context').then(function (result) {
+        invoke('get_viewer_context', { label: viewerLabel }).then(function (result) {
             if (!result || !result[0] || !result[0].length) {
                 setPlaceholder('No images');
                 return;
@@ -233,7 +282,7 @@
         });
         window.addEventListener('focus', function syncFromState() {
             if (!invoke || !paths.length) return;
-            invoke('get_viewer_context').then(function (result) {
+            invoke('get_viewer_context', { label: viewerLabel }).then(function (result) {
                 if (!result || !result[0] || !result[0].length) return;
                 paths = result[0];
                 index = typeof result[1] === 'number' ? result[1] : 0;